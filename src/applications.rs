@@ -0,0 +1,319 @@
+//! This module implements applications of integrals and derivatives to physics and other sciences.
+//!
+//! The functions are thin wrappers around the numerical methods of the `integrals` and `derivatives` modules: their purpose is to give the calculations a name, and to document the formulas they implement.
+
+use std::cell::RefCell;
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+use crate::derivatives::Derivative;
+use crate::integrals::{Function, Integral, SummationStrategy};
+use crate::roots;
+use crate::utils::numeric::{EPSILON, approx_eq};
+
+/// Computes the work done by a force which depends on the position, moving along a straight line from `start` to `end`.
+///
+/// The work is `W = ∫ F(x) dx` over `[start, end]`, computed with Simpson's 1/3 rule.
+/// It is positive when the force points in the direction of motion, and negative when it opposes it.
+///
+/// # Arguments
+///
+/// * `force` - The component of the force along the line, as a function of the position.
+/// * `start` - The starting position.
+/// * `end` - The ending position.
+/// * `num_intervals` - The number of slices of the path.
+///
+/// # Returns
+///
+/// The approximate work done by the force, in the units of the force times the units of the position (e.g. joules for newtons and metres).
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::applications::work_done;
+///
+/// // Lifting a 2 kg mass by 3 m against gravity
+/// let work = work_done(Box::new(|_| 2.0 * 9.81), 0.0, 3.0, 10);
+/// assert!((work - 58.86).abs() < 1e-9);
+/// ```
+pub fn work_done(force: Function, start: f64, end: f64, num_intervals: u64) -> f64 {
+    Integral::new(force, start, end, num_intervals).simpson_integration_one_third()
+}
+
+/// Computes the probability that a random variable with the given density falls within `[x1, x2]`, i.e. `P(x1 ≤ X ≤ x2)`.
+///
+/// The density does not need to be normalised: it is divided by its integral over its support `[a, b]`.
+/// Both integrals are computed with Simpson's 1/3 rule.
+///
+/// # Arguments
+///
+/// * `density` - The probability density function, possibly up to a constant factor.
+/// * `a` - The lower bound of the support of the density.
+/// * `b` - The upper bound of the support of the density.
+/// * `x1` - The lower bound of the event.
+/// * `x2` - The upper bound of the event.
+/// * `num_intervals` - The number of slices of each interval.
+///
+/// # Returns
+///
+/// The approximate probability, or `NaN` if the density integrates to zero over its support, within `utils::numeric::EPSILON`.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::applications::probability_between;
+///
+/// // Standard normal distribution, within one standard deviation of the mean
+/// let probability = probability_between(Box::new(|x: f64| (-x * x / 2.0).exp()), -10.0, 10.0, -1.0, 1.0, 1e3 as u64);
+/// assert!((probability - 0.682689492).abs() < 1e-9);
+/// ```
+pub fn probability_between(
+    density: Function,
+    a: f64,
+    b: f64,
+    x1: f64,
+    x2: f64,
+    num_intervals: u64,
+) -> f64 {
+    let mut integral = Integral::new(density, a, b, num_intervals);
+    let total = integral.simpson_integration_one_third();
+    if approx_eq(total, 0.0, EPSILON) {
+        return f64::NAN;
+    }
+
+    (integral.lower_bound, integral.upper_bound) = (x1, x2);
+    integral.simpson_integration_one_third() / total
+}
+
+/// Computes the area of the surface generated by rotating the graph of the function about the x-axis, over `[a, b]`.
+///
+/// The area is `S = 2π ∫ |f(x)| sqrt(1 + f'(x)^2) dx`, computed with Simpson's 1/3 rule.
+/// The derivative is computed numerically at each point with the central difference of `Derivative`, so the function only needs to be known by its values.
+///
+/// # Arguments
+///
+/// * `f` - The function whose graph is rotated.
+/// * `fprime_step` - The increment of the central difference approximating the derivative.
+/// * `a` - The lower bound of the interval.
+/// * `b` - The upper bound of the interval.
+/// * `n` - The number of slices of the interval.
+///
+/// # Returns
+///
+/// The approximate area of the surface of revolution.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::applications::surface_area_of_revolution_x;
+///
+/// // Sphere of radius 1
+/// let area = surface_area_of_revolution_x(Box::new(|x: f64| (1.0 - x * x).sqrt()), 1e-6, -0.9, 0.9, 1e3 as u64);
+/// assert!((area - 4.0 * std::f64::consts::PI * 0.9).abs() < 1e-6);
+/// ```
+pub fn surface_area_of_revolution_x(f: Function, fprime_step: f64, a: f64, b: f64, n: u64) -> f64 {
+    let f: Rc<dyn Fn(f64) -> f64> = Rc::from(f);
+    let derivative = {
+        let f = Rc::clone(&f);
+        RefCell::new(Derivative::new(Box::new(move |x| f(x)), a, fprime_step))
+    };
+
+    let integrand = move |x: f64| {
+        let mut derivative = derivative.borrow_mut();
+        derivative.x_coordinate = x;
+        let slope = derivative.central_difference();
+        f(x).abs() * (1.0 + slope * slope).sqrt()
+    };
+    2.0 * PI * Integral::new(Box::new(integrand), a, b, n).simpson_integration_one_third()
+}
+
+/// Computes the flux of a one-dimensional field out of `[a, b]`, i.e. the field leaving through the upper end minus the field entering through the lower end: `F(b) - F(a)`.
+///
+/// Together with `integrate_divergence`, it illustrates the divergence theorem in one dimension, `∫ F'(x) dx = F(b) - F(a)` over `[a, b]`,
+/// which is the fundamental theorem of calculus: the sources inside the interval add up to the net flux out of it.
+///
+/// # Arguments
+///
+/// * `field` - The field, as a function of the position.
+/// * `a` - The lower end of the interval.
+/// * `b` - The upper end of the interval.
+///
+/// # Returns
+///
+/// The net flux out of the interval.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::applications::flux_1d;
+/// use rust_math_lib::integrals::Function;
+///
+/// let field: Function = Box::new(|x| x * x);
+/// assert_eq!(flux_1d(&field, 1.0, 3.0), 8.0);
+/// ```
+pub fn flux_1d(field: &Function, a: f64, b: f64) -> f64 {
+    field(b) - field(a)
+}
+
+/// Integrates the divergence of a one-dimensional field over `[a, b]`, i.e. the integral of its derivative, with Simpson's 1/3 rule.
+///
+/// By the divergence theorem, the result matches `flux_1d` for the field, up to the error of the integration.
+///
+/// # Arguments
+///
+/// * `field_derivative` - The derivative of the field, i.e. the density of its sources.
+/// * `a` - The lower end of the interval.
+/// * `b` - The upper end of the interval.
+/// * `n` - The number of slices of the interval.
+///
+/// # Returns
+///
+/// The approximate integral of the divergence.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::applications::{flux_1d, integrate_divergence};
+/// use rust_math_lib::integrals::Function;
+///
+/// let field: Function = Box::new(|x: f64| x.sin());
+/// let divergence: Function = Box::new(|x: f64| x.cos());
+/// let sources = integrate_divergence(&divergence, 0.0, 2.0, 100);
+/// assert!((sources - flux_1d(&field, 0.0, 2.0)).abs() < 1e-9);
+/// ```
+pub fn integrate_divergence(field_derivative: &Function, a: f64, b: f64, n: u64) -> f64 {
+    Integral::simpson_sum(SummationStrategy::Naive, a, b, n.max(1), field_derivative)
+}
+
+/// Computes the area enclosed between two curves over `[a, b]`, `∫ |f(x) - g(x)| dx`.
+///
+/// The points where the curves cross are found first with `roots::find_all_roots`, scanning the interval at `n + 1` points:
+/// each region between two crossings, where one curve stays above the other, is integrated with Simpson's 1/3 rule and its absolute value is added.
+/// Unlike integrating `|f(x) - g(x)|` directly, as `integrals::l1_distance` does, the regions are integrated without the kink of the absolute value at the crossings, so Simpson's rule keeps its accuracy.
+/// Crossings closer than the scan resolution may be missed, as described in `roots::find_all_roots`.
+///
+/// # Arguments
+///
+/// * `f` - The first curve.
+/// * `g` - The second curve.
+/// * `a` - One end of the interval.
+/// * `b` - The other end of the interval.
+/// * `n` - The number of slices of the interval, shared among the regions in proportion to their widths.
+///
+/// # Returns
+///
+/// The approximate area between the curves, which is never negative, whatever the order of the bounds.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::applications::area_between_curves;
+/// use rust_math_lib::integrals::Function;
+///
+/// // Two regions of area 2, on either side of the crossing at π
+/// let f: Function = Box::new(|x: f64| x.sin());
+/// let g: Function = Box::new(|_| 0.0);
+/// let area = area_between_curves(&f, &g, 0.0, 2.0 * std::f64::consts::PI, 100);
+/// assert!((area - 4.0).abs() < 1e-6);
+/// ```
+pub fn area_between_curves(f: &Function, g: &Function, a: f64, b: f64, n: u64) -> f64 {
+    let (a, b) = (a.min(b), a.max(b));
+    let n = n.max(1);
+    let difference = |x: f64| f(x) - g(x);
+
+    let mut crossings = vec![a];
+    crossings.extend(roots::find_all_roots(
+        &difference,
+        a,
+        b,
+        n as usize + 1,
+        0.0,
+    ));
+    crossings.push(b);
+
+    crossings
+        .windows(2)
+        .map(|region| {
+            let share = (n as f64 * (region[1] - region[0]) / (b - a)).round() as u64;
+            Integral::simpson_sum(
+                SummationStrategy::Naive,
+                region[0],
+                region[1],
+                share.max(1),
+                difference,
+            )
+            .abs()
+        })
+        .sum()
+}
+
+// ---- Tests ---- //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_work_done_by_spring() {
+        let (k, d) = (250.0, 0.04); // Spring constant in N/m, displacement in m
+        let work = work_done(Box::new(move |x| -k * x), 0.0, d, 1e3 as u64);
+        assert!((work - (-0.5 * k * d * d)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_probability_between_uniform() {
+        let probability = probability_between(Box::new(|_| 3.0), 2.0, 6.0, 2.5, 3.5, 10);
+        assert!((probability - 0.25).abs() < 1e-12);
+
+        // A density integrating to zero, up to the rounding errors
+        assert!(probability_between(Box::new(|x: f64| x.sin()), -1.0, 1.0, 0.0, 1.0, 10).is_nan());
+    }
+
+    #[test]
+    fn test_surface_area_of_revolution_cylinder() {
+        let (r, h) = (2.0, 3.0);
+        let area = surface_area_of_revolution_x(Box::new(move |_| r), 1e-6, 0.0, h, 10);
+        assert!((area - 2.0 * PI * r * h).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_surface_area_of_revolution_cone() {
+        // Lateral surface of a cone of radius 1 and height 1: π r sqrt(r^2 + h^2)
+        let area = surface_area_of_revolution_x(Box::new(|x| x), 1e-6, 0.0, 1.0, 100);
+        assert!((area - PI * 2f64.sqrt()).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_divergence_theorem_1d() {
+        // Field of a charge density growing exponentially along the line
+        let field: Function = Box::new(|x: f64| x.exp() - 2.0 * x);
+        let divergence: Function = Box::new(|x: f64| x.exp() - 2.0);
+
+        for (a, b) in [(0.0, 1.0), (-2.0, 3.0), (1.0, -1.0)] {
+            let flux = flux_1d(&field, a, b);
+            let sources = integrate_divergence(&divergence, a, b, 1000);
+            assert!(
+                (flux - sources).abs() < 1e-10,
+                "[{}, {}]: {} vs {}",
+                a,
+                b,
+                flux,
+                sources
+            );
+        }
+    }
+
+    #[test]
+    fn test_area_between_curves() {
+        // Between y = x and y = x^2, which meet at both bounds: 1/2 - 1/3
+        let f: Function = Box::new(|x| x);
+        let g: Function = Box::new(|x| x * x);
+        let area = area_between_curves(&f, &g, 0.0, 1.0, 100);
+        assert!((area - 1.0 / 6.0).abs() < 1e-12, "Area: {}", area);
+        assert_eq!(area_between_curves(&g, &f, 1.0, 0.0, 100), area);
+
+        // Crossing at x = 1, inside the interval: 1/6 on [0, 1], plus 5/6 on [1, 2]
+        let area = area_between_curves(&f, &g, 0.0, 2.0, 101);
+        assert!((area - 1.0).abs() < 1e-12, "Area: {}", area);
+    }
+}
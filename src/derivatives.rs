@@ -4,6 +4,12 @@
 //! - `forward_difference`: Uses the forward difference method to approximate the derivative of a function at a specified point.
 //! - `backward_difference`: Uses the backward difference method to approximate the derivative of a function at a specified point.
 //! - `central_difference`: Uses the central difference method to approximate the derivative of a function at a specified point.
+//!
+//! Derivatives of sampled data are estimated with `derivative_from_samples`, using any `DifferentiationMethod`.
+//! Derivatives of functions of several variables are estimated with `partial_derivative`, `gradient` and `mixed_partial`.
+//! The chain rule is verified numerically by comparing `chain_rule_derivative` with `chain_rule_product`.
+
+use crate::interpolation::{self, InterpError};
 
 type Function = Box<dyn Fn(f64) -> f64>; // TODO: GS consider using a trait object instead of a function pointer, or commonise the type definition since it is used in both `integrals` and `derivatives` modules
 
@@ -13,6 +19,32 @@ pub struct Derivative {
     pub x_coordinate: f64,
     pub increment: f64,
     result: f64,
+    last_method: Option<&'static str>, // Name of the method which computed the result
+}
+
+/// The symmetry of a function about `x = 0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Parity {
+    /// `f(-x) = f(x)`, e.g. `cos(x)`.
+    Even,
+    /// `f(-x) = -f(x)`, e.g. `sin(x)`.
+    Odd,
+}
+
+/// The parameters of a derivative, without the function.
+///
+/// Unlike `Derivative`, it is plain data, so it can be generated programmatically, e.g. for a parameter sweep, and paired with a function afterwards.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DerivativeConfig {
+    pub x_coordinate: f64,
+    pub increment: f64,
+}
+
+impl From<(Function, DerivativeConfig)> for Derivative {
+    /// Creates a new derivative of the function with the parameters of the configuration, as `Derivative::new` does.
+    fn from((function, config): (Function, DerivativeConfig)) -> Self {
+        Derivative::new(function, config.x_coordinate, config.increment)
+    }
 }
 
 impl Derivative {
@@ -29,13 +61,166 @@ impl Derivative {
             x_coordinate,
             increment,
             result: 0.0,
+            last_method: None,
         }
     }
 
+    /// Returns the name of the last differentiation method called, e.g. `"central_difference"`, so that the result can be labelled without passing the name around.
+    ///
+    /// A method built on another one, e.g. `step_size_study` on `central_difference`, reports its own name.
+    ///
+    /// # Returns
+    ///
+    /// The name of the method, as written in the code, or `None` if no method was called yet.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::derivatives::Derivative;
+    ///
+    /// let mut derivative = Derivative::new(Box::new(|x| x * x), 2.0, 1e-6);
+    /// assert_eq!(derivative.last_method(), None);
+    /// derivative.central_difference();
+    /// assert_eq!(derivative.last_method(), Some("central_difference"));
+    /// ```
+    pub fn last_method(&self) -> Option<&'static str> {
+        self.last_method
+    }
+
+    /// Sets the direction of the increment, keeping its magnitude: towards larger `x` if `positive`, towards smaller `x` otherwise.
+    ///
+    /// The forward difference then steps in the chosen direction, so the negative direction gives the backward difference without constructing a new struct,
+    /// e.g. to compare the one-sided derivatives of a function at a kink. The central difference does not depend on the direction.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::derivatives::Derivative;
+    ///
+    /// // One-sided derivatives of |x| at 0
+    /// let mut derivative = Derivative::new(Box::new(|x: f64| x.abs()), 0.0, 1e-6);
+    /// assert_eq!(derivative.forward_difference(), 1.0);
+    /// derivative.with_direction(false);
+    /// assert_eq!(derivative.forward_difference(), -1.0);
+    /// ```
+    pub fn with_direction(&mut self, positive: bool) {
+        self.increment = if positive {
+            self.increment.abs()
+        } else {
+            -self.increment.abs()
+        };
+    }
+
     pub fn get_result(&self) -> f64 {
         self.result
     }
 
+    /// Suggests an increment for the central difference, so that its relative error is within `target_rel_error`.
+    ///
+    /// The truncation error of the central difference is about `h^2 * |f'''(x)| / 24`, so the increment is chosen to keep it within a quarter of the target, using pilot estimates of the first and third derivatives.
+    /// The increment is never smaller than `eps^(1/3)` (scaled by the magnitude of `x_coordinate`), where the roundoff error of the difference starts to dominate, nor larger than a tenth of that magnitude.
+    /// The increment, the result and the last method of the struct are not changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `target_rel_error` - The requested relative error of the derivative.
+    ///
+    /// # Returns
+    ///
+    /// The suggested increment.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::derivatives::Derivative;
+    ///
+    /// let mut derivative = Derivative::new(Box::new(|x: f64| x.sin()), 1.0, 1e-6);
+    /// derivative.increment = derivative.suggest_increment(1e-8);
+    /// let result = derivative.central_difference();
+    /// assert!((result - 1.0_f64.cos()).abs() <= 1e-8 * 1.0_f64.cos());
+    /// ```
+    pub fn suggest_increment(&mut self, target_rel_error: f64) -> f64 {
+        let scale = self.x_coordinate.abs().max(1.0);
+        let min_increment = f64::EPSILON.cbrt() * scale;
+        let max_increment = 0.1 * scale;
+
+        let (first, third) = self.pilot_derivatives();
+        let first = first.abs().max(f64::EPSILON);
+        let third = third.abs();
+
+        if third == 0.0 {
+            return max_increment; // No truncation error, e.g. for a quadratic
+        }
+        let suggested = (24.0 * target_rel_error / 4.0 * first / third).sqrt();
+        suggested.clamp(min_increment, max_increment)
+    }
+
+    /// Splits the error of the central difference into its truncation and roundoff components, given the exact derivative.
+    ///
+    /// The truncation error comes from the finite increment, and is estimated as `h^2 * |f'''(x)| / 24` with a pilot estimate of the third derivative, as in `suggest_increment`.
+    /// The roundoff error comes from the cancellation of the two close values of the function, and grows as `eps / h`:
+    /// it is attributed the rest of the total error, i.e. the total error minus the truncation estimate.
+    /// Large increments are dominated by the truncation error, and small ones by the roundoff error.
+    /// The pilot estimate does not change the struct: the central difference is stored as the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `exact` - The exact value of the derivative at `x_coordinate`.
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the estimated truncation error and the total error, i.e. the absolute difference between the central difference and `exact`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::derivatives::Derivative;
+    ///
+    /// let mut derivative = Derivative::new(Box::new(|x: f64| x.sin()), 1.0, 1e-12);
+    /// let (truncation, total) = derivative.error_breakdown(1.0_f64.cos());
+    /// println!("Truncation error: {:e}, roundoff error: {:e}", truncation, total - truncation);
+    /// ```
+    pub fn error_breakdown(&mut self, exact: f64) -> (f64, f64) {
+        let (_, third) = self.pilot_derivatives();
+        let truncation = self.increment.powi(2) * third.abs() / 24.0;
+        let total = (self.central_difference() - exact).abs();
+        self.last_method = Some("error_breakdown");
+
+        (truncation, total)
+    }
+
+    /// Returns pilot estimates of the first and third derivatives, with an increment suited to the third derivative.
+    /// The struct is not changed.
+    fn pilot_derivatives(&self) -> (f64, f64) {
+        let increment = f64::EPSILON.powf(0.2) * self.x_coordinate.abs().max(1.0);
+        let first = stencil_derivative(&self.function, self.x_coordinate, increment, 1, 5);
+        let third = stencil_derivative(&self.function, self.x_coordinate, increment, 3, 5);
+
+        (first, third)
+    }
+
+    /// Checks whether the increment is too small to move away from `x_coordinate` in floating point.
+    ///
+    /// This happens when the increment is below the spacing of the floating-point numbers around `x_coordinate`, e.g. `x_coordinate = 1e16` and `increment = 1e-7`:
+    /// the differences then compare the function with itself, and the derivative silently becomes `0` or `NaN`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if `x_coordinate + increment` or `x_coordinate - increment` rounds to `x_coordinate`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::derivatives::Derivative;
+    ///
+    /// let derivative = Derivative::new(Box::new(|x: f64| x * x), 1e16, 1e-7);
+    /// assert!(derivative.has_step_underflow());
+    /// ```
+    pub fn has_step_underflow(&self) -> bool {
+        self.x_coordinate + self.increment == self.x_coordinate
+            || self.x_coordinate - self.increment == self.x_coordinate
+    }
+
     // TODO: GS add a print result method to print the result of the derivative
 
     // TODO: GS add a copy constructor (`from`) as in `Integrator`
@@ -61,6 +246,7 @@ impl Derivative {
     /// let result = derivative.forward_difference();
     /// ```
     pub fn forward_difference(&mut self) -> f64 {
+        self.last_method = Some("forward_difference");
         self.result = ((self.function)(self.x_coordinate + self.increment)
             - (self.function)(self.x_coordinate))
             / self.increment;
@@ -85,6 +271,7 @@ impl Derivative {
     /// let result = derivative.backward_difference();
     /// /// ```
     pub fn backward_difference(&mut self) -> f64 {
+        self.last_method = Some("backward_difference");
         self.result = ((self.function)(self.x_coordinate)
             - (self.function)(self.x_coordinate - self.increment))
             / self.increment;
@@ -105,12 +292,620 @@ impl Derivative {
     /// let result = derivative.central_difference();
     /// ```
     pub fn central_difference(&mut self) -> f64 {
-        let half_increment = self.increment / 2.0;
-        self.result = ((self.function)(self.x_coordinate + half_increment)
-            - (self.function)(self.x_coordinate - half_increment))
-            / self.increment;
+        self.last_method = Some("central_difference");
+        self.result = central_difference_of(&self.function, self.x_coordinate, self.increment);
         self.result
     }
+
+    /// Performs numerical differentiation using central difference, exploiting the known symmetry of the function.
+    ///
+    /// The symmetry only gives information about the function at `x = 0`, where the stencil is its own mirror image:
+    /// - the derivative of an even function is exactly `0` there, which is returned without evaluating the function, free of any rounding error;
+    /// - the value of an odd function at `-x` is the opposite of its value at `x`, so each central difference needs a single evaluation.
+    ///   The two evaluations are spent on the central differences with increments `h` and `h / 2`, which are combined by Richardson extrapolation into a fourth-order estimate.
+    ///
+    /// Elsewhere, the result is the same as `central_difference`.
+    ///
+    /// # Arguments
+    ///
+    /// * `parity` - The symmetry of the function, which is trusted, not checked.
+    ///
+    /// # Returns
+    ///
+    /// The approximate value of the derivative at the given point.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::derivatives::{Derivative, Parity};
+    ///
+    /// let mut derivative = Derivative::new(Box::new(|x: f64| x.sin()), 0.0, 1e-2);
+    /// let result = derivative.central_difference_symmetric(Parity::Odd);
+    /// assert!((result - 1.0).abs() < 1e-10);
+    /// ```
+    pub fn central_difference_symmetric(&mut self, parity: Parity) -> f64 {
+        if self.x_coordinate != 0.0 {
+            self.central_difference();
+        } else {
+            self.result = match parity {
+                Parity::Even => 0.0,
+                Parity::Odd => {
+                    // (f(h / 2) - f(-h / 2)) / h = 2 f(h / 2) / h, and likewise with h / 2
+                    let coarse = 2.0 * (self.function)(self.increment / 2.0) / self.increment;
+                    let fine = 4.0 * (self.function)(self.increment / 4.0) / self.increment;
+                    (4.0 * fine - coarse) / 3.0
+                }
+            };
+        }
+        self.last_method = Some("central_difference_symmetric");
+        self.result
+    }
+
+    /// Performs numerical differentiation of arbitrary order using the finite difference weights generated by Fornberg's algorithm.
+    ///
+    /// The stencil is made of `num_points` points centred on `x_coordinate` and spaced by `increment`.
+    /// With a single stencil this generalises the forward, backward and central differences: e.g. `order = 1` and `num_points = 5` is the five-point stencil.
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - The order of the derivative.
+    /// * `num_points` - The number of points of the stencil. The more points, the more accurate the result. It is raised to `order + 1` if smaller, the minimum needed for the derivative.
+    ///
+    /// # Returns
+    ///
+    /// The approximate value of the derivative of the given order at `x_coordinate`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::derivatives::Derivative;
+    ///
+    /// // Second derivative of x^3 at x = 2
+    /// let mut derivative = Derivative::new(Box::new(|x| x * x * x), 2.0, 1e-3);
+    /// let result = derivative.fornberg_derivative(2, 3);
+    /// assert!((result - 12.0).abs() < 1e-4);
+    /// ```
+    pub fn fornberg_derivative(&mut self, order: usize, num_points: usize) -> f64 {
+        self.last_method = Some("fornberg_derivative");
+        self.result = stencil_derivative(
+            &self.function,
+            self.x_coordinate,
+            self.increment,
+            order,
+            num_points,
+        );
+        self.result
+    }
+
+    /// Computes the first derivative by differentiating the polynomial interpolating the function around `x_coordinate`.
+    ///
+    /// The function is sampled at `num_terms` points centred on `x_coordinate` and spaced by `increment`, and the interpolating polynomial is differentiated analytically.
+    /// This is the same derivative as `fornberg_derivative(1, num_terms)`, up to rounding, expressed through interpolation instead of finite difference weights:
+    /// its error shrinks as `h^(num_terms - 1)`, e.g. as `h^4` with five points.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_terms` - The number of points of the interpolating polynomial, i.e. its degree plus one. It is raised to `2` if smaller, the minimum for a slope.
+    ///
+    /// # Returns
+    ///
+    /// The approximate value of the first derivative at `x_coordinate`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::derivatives::Derivative;
+    ///
+    /// let mut derivative = Derivative::new(Box::new(|x: f64| x.exp()), 0.0, 1e-2);
+    /// assert!((derivative.taylor_derivative(5) - 1.0).abs() < 1e-9);
+    /// ```
+    pub fn taylor_derivative(&mut self, num_terms: usize) -> f64 {
+        self.last_method = Some("taylor_derivative");
+        let num_terms = num_terms.max(2);
+
+        let centre = (num_terms - 1) as f64 / 2.0;
+        let xs: Vec<f64> = (0..num_terms)
+            .map(|j| self.x_coordinate + (j as f64 - centre) * self.increment.abs())
+            .collect();
+        let ys: Vec<f64> = xs.iter().map(|&x| (self.function)(x)).collect();
+
+        self.result = match interpolation::NewtonPolynomial::new(&xs, &ys) {
+            Ok(polynomial) => polynomial.derivative(self.x_coordinate),
+            Err(_) => f64::NAN, // Increment too small to separate the points
+        };
+        self.result
+    }
+
+    /// Runs each difference method and compares its result with the analytic derivative, to assess their accuracy.
+    ///
+    /// # Arguments
+    ///
+    /// * `analytic` - The exact derivative of the function, evaluated at `x_coordinate`.
+    ///
+    /// # Returns
+    ///
+    /// A `(method, numerical, error)` tuple for the forward, backward and central differences, in this order,
+    /// where `error` is the absolute difference between the numerical and the analytic derivative.
+    /// The result of the struct is the one of the central difference.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::derivatives::Derivative;
+    /// use rust_math_lib::integrals::Function;
+    ///
+    /// let analytic: Function = Box::new(|x| x.exp());
+    /// let mut derivative = Derivative::new(Box::new(|x: f64| x.exp()), 1.0, 1e-5);
+    /// for (method, numerical, error) in derivative.validate_against(&analytic) {
+    ///     println!("{}: {} (error {:e})", method, numerical, error);
+    /// }
+    /// ```
+    pub fn validate_against(&mut self, analytic: &Function) -> Vec<(String, f64, f64)> {
+        let exact = analytic(self.x_coordinate);
+        let results = [
+            ("Forward Difference", self.forward_difference()),
+            ("Backward Difference", self.backward_difference()),
+            ("Central Difference", self.central_difference()),
+        ];
+        self.last_method = Some("validate_against");
+
+        results
+            .iter()
+            .map(|&(name, numerical)| (name.to_string(), numerical, (numerical - exact).abs()))
+            .collect()
+    }
+
+    /// Computes the central difference with each of the given increments, to study how the error depends on the increment.
+    ///
+    /// Plotting the error against the increment gives the classic U-shaped curve: large increments suffer from the truncation error, which shrinks as `h^2`, while small increments suffer from the roundoff error, which grows as `eps / h`.
+    /// The increment of the struct is not changed, and the result is the one of the last increment.
+    ///
+    /// # Arguments
+    ///
+    /// * `steps` - The increments to try.
+    ///
+    /// # Returns
+    ///
+    /// The `(increment, result)` pairs, in the order of `steps`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::derivatives::Derivative;
+    ///
+    /// let mut derivative = Derivative::new(Box::new(|x: f64| x.exp()), 0.0, 1e-6);
+    /// let steps: Vec<f64> = (1..=12).map(|k| 10f64.powi(-k)).collect();
+    /// for (increment, result) in derivative.step_size_study(&steps) {
+    ///     println!("h = {:e}, error = {:e}", increment, (result - 1.0).abs());
+    /// }
+    /// ```
+    pub fn step_size_study(&mut self, steps: &[f64]) -> Vec<(f64, f64)> {
+        let increment = self.increment;
+        let study = steps
+            .iter()
+            .map(|&step| {
+                self.increment = step;
+                (step, self.central_difference())
+            })
+            .collect();
+        self.increment = increment;
+        self.last_method = Some("step_size_study");
+
+        study
+    }
+
+    /// Extrapolates the central difference to a zero increment, by fitting its results at several increments.
+    ///
+    /// The central difference with increment `h` is `D(h) ≈ D0 + C h^2` for a smooth function, where `D0` is the exact derivative.
+    /// The model is fitted to the results at all the increments with `interpolation::polyfit`, as a line in `h^2`, and its intercept `D0` is returned:
+    /// unlike Richardson extrapolation from a single pair of increments, the least-squares fit averages out the roundoff error of the individual results.
+    /// Increments from about `1e-1` to `1e-3` work well: smaller ones are dominated by the roundoff error, which the model does not describe.
+    /// The increment of the struct is not changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `steps` - The increments of the central differences, at least two distinct ones.
+    ///
+    /// # Returns
+    ///
+    /// The extrapolated derivative, or the central difference at the only increment if the increments do not determine the fit, or `NaN` if there are none.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::derivatives::Derivative;
+    ///
+    /// let mut derivative = Derivative::new(Box::new(|x: f64| x.exp()), 0.0, 1e-6);
+    /// let result = derivative.extrapolate_to_zero_step(&[0.1, 0.05, 0.02, 0.01]);
+    /// assert!((result - 1.0).abs() < 1e-8);
+    /// ```
+    pub fn extrapolate_to_zero_step(&mut self, steps: &[f64]) -> f64 {
+        let (squared_steps, results): (Vec<f64>, Vec<f64>) = self
+            .step_size_study(steps)
+            .into_iter()
+            .map(|(step, result)| (step * step, result))
+            .unzip();
+
+        self.result = match interpolation::polyfit(&squared_steps, &results, 1) {
+            Ok(coefficients) => coefficients[0],
+            Err(_) => results.last().copied().unwrap_or(f64::NAN),
+        };
+        self.last_method = Some("extrapolate_to_zero_step");
+        self.result
+    }
+}
+
+/// A finite difference method to estimate derivatives of sampled data.
+///
+/// The method is described by its stencil: the samples used to estimate the derivative at a sample, by their index relative to it.
+/// The weights of the samples are computed from their actual spacing, so the samples do not need to be evenly spaced.
+pub trait DifferentiationMethod {
+    /// Returns the indices of the samples of the stencil, relative to the sample where the derivative is estimated.
+    fn stencil(&self) -> &[isize];
+}
+
+/// The forward difference, using the sample and the next one.
+pub struct ForwardDifference;
+
+/// The backward difference, using the sample and the previous one.
+pub struct BackwardDifference;
+
+/// The central difference, using the sample and its two neighbours. Exact for quadratics.
+pub struct CentralDifference;
+
+impl DifferentiationMethod for ForwardDifference {
+    fn stencil(&self) -> &[isize] {
+        &[0, 1]
+    }
+}
+
+impl DifferentiationMethod for BackwardDifference {
+    fn stencil(&self) -> &[isize] {
+        &[-1, 0]
+    }
+}
+
+impl DifferentiationMethod for CentralDifference {
+    fn stencil(&self) -> &[isize] {
+        &[-1, 0, 1]
+    }
+}
+
+/// Estimates the derivative of sampled data at each sample, with finite differences on possibly unevenly spaced samples.
+///
+/// Near the ends of the data, where the stencil of the method does not fit, it is shifted inwards, giving one-sided differences of the same order.
+///
+/// # Arguments
+///
+/// * `xs` - The abscissae of the samples, strictly increasing.
+/// * `ys` - The values of the function at the abscissae.
+/// * `method` - The finite difference method.
+///
+/// # Returns
+///
+/// The approximate values of the derivative at the abscissae, or an error if there are fewer samples than the stencil of the method, or the abscissae are not strictly increasing.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::derivatives::{CentralDifference, derivative_from_samples};
+///
+/// let xs = [0.0, 0.5, 1.5, 2.0];
+/// let ys: Vec<f64> = xs.iter().map(|x| x * x).collect();
+/// let derivatives = derivative_from_samples(&xs, &ys, &CentralDifference).unwrap();
+/// assert!((derivatives[2] - 3.0).abs() < 1e-12);
+/// ```
+pub fn derivative_from_samples(
+    xs: &[f64],
+    ys: &[f64],
+    method: &dyn DifferentiationMethod,
+) -> Result<Vec<f64>, InterpError> {
+    let stencil = method.stencil();
+    let first = *stencil.iter().min().unwrap_or(&0);
+    let last = *stencil.iter().max().unwrap_or(&0);
+    interpolation::validate_samples(xs, ys, (last - first + 1) as usize)?;
+
+    let derivatives = (0..xs.len() as isize)
+        .map(|i| {
+            // Shift the stencil inwards where it does not fit
+            let shift = (-i - first).max(0) + (xs.len() as isize - 1 - i - last).min(0);
+            let indices: Vec<usize> = stencil.iter().map(|j| (i + j + shift) as usize).collect();
+
+            let offsets: Vec<f64> = indices.iter().map(|&j| xs[j] - xs[i as usize]).collect();
+            let weights = fornberg_weights(&offsets, 1);
+            indices
+                .iter()
+                .zip(&weights)
+                .map(|(&j, weight)| weight * ys[j])
+                .sum()
+        })
+        .collect();
+
+    Ok(derivatives)
+}
+
+/// Performs numerical differentiation of a function of several variables with respect to one of them, using central difference.
+///
+/// # Arguments
+///
+/// * `function` - The function to differentiate, taking the values of the variables as a slice.
+/// * `point` - The point at which to evaluate the derivative.
+/// * `index` - The index of the variable, in `point`, with respect to which the function is differentiated.
+/// * `increment` - The step size for the finite difference approximation.
+///
+/// # Returns
+///
+/// The approximate value of the partial derivative at the given point.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::derivatives::partial_derivative;
+///
+/// // ∂(x * y^2)/∂y at (3, 2)
+/// let result = partial_derivative(&|v: &[f64]| v[0] * v[1] * v[1], &[3.0, 2.0], 1, 1e-6);
+/// assert!((result - 12.0).abs() < 1e-6);
+/// ```
+pub fn partial_derivative(
+    function: &dyn Fn(&[f64]) -> f64,
+    point: &[f64],
+    index: usize,
+    increment: f64,
+) -> f64 {
+    let mut shifted = point.to_vec();
+    shifted[index] = point[index] + increment / 2.0;
+    let forward = function(&shifted);
+    shifted[index] = point[index] - increment / 2.0;
+    let backward = function(&shifted);
+
+    (forward - backward) / increment
+}
+
+/// Performs numerical differentiation of a function of several variables, estimating its gradient with central differences.
+///
+/// # Arguments
+///
+/// * `function` - The function to differentiate, taking the values of the variables as a slice.
+/// * `point` - The point at which to evaluate the gradient.
+/// * `increment` - The step size for the finite difference approximations.
+///
+/// # Returns
+///
+/// The approximate partial derivatives at the given point, in the order of the variables.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::derivatives::gradient;
+///
+/// let result = gradient(&|v: &[f64]| v[0] * v[0] + 3.0 * v[1], &[1.0, 5.0], 1e-6);
+/// assert!((result[0] - 2.0).abs() < 1e-6 && (result[1] - 3.0).abs() < 1e-6);
+/// ```
+pub fn gradient(function: &dyn Fn(&[f64]) -> f64, point: &[f64], increment: f64) -> Vec<f64> {
+    (0..point.len())
+        .map(|index| partial_derivative(function, point, index, increment))
+        .collect()
+}
+
+/// Performs numerical differentiation of a function of several variables twice, with respect to the variables `i` and `j`, i.e. `∂²f/∂x_i∂x_j`.
+///
+/// The mixed partial derivative is computed with the four-point stencil `(f(+h, +h) - f(+h, -h) - f(-h, +h) + f(-h, -h)) / (4 h^2)`, shifting the variables `i` and `j`.
+/// The indices are ordered before evaluating it, so that swapping them gives exactly the same result, as the symmetry of second derivatives requires for a smooth function.
+/// If `i == j`, the stencil would degenerate, so the pure second derivative is computed with the second difference `(f(x + h) - 2 f(x) + f(x - h)) / h^2` instead.
+/// Both are second-order accurate, and are the elements of the Hessian matrix of the function.
+///
+/// # Arguments
+///
+/// * `f` - The function to differentiate, taking the values of the variables as a slice.
+/// * `point` - The point at which to evaluate the derivative.
+/// * `i` - The index of the first variable of differentiation, in `point`.
+/// * `j` - The index of the second variable of differentiation, in `point`.
+/// * `h` - The step size of the finite differences. The roundoff error grows as `eps / h^2`, so it should be larger than for a first derivative, e.g. `1e-4`.
+///
+/// # Returns
+///
+/// The approximate value of the second partial derivative at the given point.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::derivatives::mixed_partial;
+///
+/// // ∂²(x * y^2)/∂x∂y = 2y at (3, 2)
+/// let result = mixed_partial(&|v: &[f64]| v[0] * v[1] * v[1], &[3.0, 2.0], 0, 1, 1e-4);
+/// assert!((result - 4.0).abs() < 1e-6);
+/// ```
+pub fn mixed_partial(f: &dyn Fn(&[f64]) -> f64, point: &[f64], i: usize, j: usize, h: f64) -> f64 {
+    let mut shifted = point.to_vec();
+    if i == j {
+        shifted[i] = point[i] + h;
+        let forward = f(&shifted);
+        shifted[i] = point[i] - h;
+        let backward = f(&shifted);
+        return (forward - 2.0 * f(point) + backward) / (h * h);
+    }
+
+    let (i, j) = (i.min(j), i.max(j));
+    let mut corner = |step_i: f64, step_j: f64| {
+        shifted[i] = point[i] + step_i;
+        shifted[j] = point[j] + step_j;
+        f(&shifted)
+    };
+    (corner(h, h) - corner(h, -h) - corner(-h, h) + corner(-h, -h)) / (4.0 * h * h)
+}
+
+/// Computes the coefficients of the Taylor polynomial of the function around `x0`, i.e. `f^(k)(x0) / k!` for `k = 0..=order`.
+///
+/// Each derivative is computed as in `Derivative::fornberg_derivative`, with the smallest centred stencil of second-order accuracy, e.g. three points for the first two derivatives, five for the next two.
+/// The roundoff error of the derivative of order `k` grows as `eps / h^k`, so high orders need a larger increment than the usual one, e.g. `h = 0.1` for the first ten coefficients.
+///
+/// # Arguments
+///
+/// * `f` - The function to expand.
+/// * `x0` - The point around which the function is expanded.
+/// * `order` - The degree of the Taylor polynomial.
+/// * `h` - The increment of the finite differences.
+///
+/// # Returns
+///
+/// The `order + 1` coefficients, from the constant term up to the term of degree `order`.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::derivatives::taylor_coefficients;
+/// use rust_math_lib::integrals::Function;
+///
+/// let f: Function = Box::new(|x| x.cos());
+/// let coefficients = taylor_coefficients(&f, 0.0, 4, 1e-2);
+/// // Taylor polynomial evaluated at x = 0.1
+/// let approximation: f64 = coefficients.iter().enumerate().map(|(k, c)| c * 0.1_f64.powi(k as i32)).sum();
+/// assert!((approximation - 0.1_f64.cos()).abs() < 1e-6);
+/// ```
+pub fn taylor_coefficients(f: &Function, x0: f64, order: usize, h: f64) -> Vec<f64> {
+    let mut factorial = 1.0;
+    (0..=order)
+        .map(|k| {
+            if k > 0 {
+                factorial *= k as f64;
+            }
+            let num_points = 2 * k.div_ceil(2) + 1;
+            stencil_derivative(f, x0, h, k, num_points) / factorial
+        })
+        .collect()
+}
+
+/// Computes the derivative of the composition `outer(inner(x))` at `x`, differentiating the composition directly.
+///
+/// The derivative is computed with the same central difference as `Derivative::central_difference`, evaluating `outer` at `inner(x ± h / 2)`.
+/// Together with `chain_rule_product`, it verifies the chain rule numerically: both must agree within the truncation error of the central difference.
+///
+/// # Arguments
+///
+/// * `outer` - The outer function of the composition.
+/// * `inner` - The inner function of the composition.
+/// * `x` - The point at which to evaluate the derivative.
+/// * `h` - The increment of the central difference.
+///
+/// # Returns
+///
+/// The approximate value of `d/dx outer(inner(x))`.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::derivatives::chain_rule_derivative;
+/// use rust_math_lib::integrals::Function;
+///
+/// let outer: Function = Box::new(|u| u.exp());
+/// let inner: Function = Box::new(|x| 3.0 * x);
+/// let result = chain_rule_derivative(&outer, &inner, 0.0, 1e-5);
+/// assert!((result - 3.0).abs() < 1e-8);
+/// ```
+pub fn chain_rule_derivative(outer: &Function, inner: &Function, x: f64, h: f64) -> f64 {
+    central_difference_of(&|x| outer(inner(x)), x, h)
+}
+
+/// Computes the derivative of the composition `outer(inner(x))` at `x` with the chain rule, as the product `outer'(inner(x)) * inner'(x)`.
+///
+/// Both factors are computed with the same central difference as `Derivative::central_difference`, with the same increment:
+/// the result can be compared with `chain_rule_derivative` to verify the chain rule numerically.
+///
+/// # Arguments
+///
+/// * `outer` - The outer function of the composition.
+/// * `inner` - The inner function of the composition.
+/// * `x` - The point at which to evaluate the derivative.
+/// * `h` - The increment of the central differences.
+///
+/// # Returns
+///
+/// The approximate value of `outer'(inner(x)) * inner'(x)`.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::derivatives::chain_rule_product;
+/// use rust_math_lib::integrals::Function;
+///
+/// let outer: Function = Box::new(|u| u.exp());
+/// let inner: Function = Box::new(|x| 3.0 * x);
+/// let result = chain_rule_product(&outer, &inner, 0.0, 1e-5);
+/// assert!((result - 3.0).abs() < 1e-8);
+/// ```
+pub fn chain_rule_product(outer: &Function, inner: &Function, x: f64, h: f64) -> f64 {
+    central_difference_of(outer, inner(x), h) * central_difference_of(inner, x, h)
+}
+
+/// Computes the central difference of `function` at `x_coordinate`, evaluating it half an increment on either side, as described in `Derivative::central_difference`.
+fn central_difference_of(function: &dyn Fn(f64) -> f64, x_coordinate: f64, increment: f64) -> f64 {
+    let half_increment = increment / 2.0;
+    (function(x_coordinate + half_increment) - function(x_coordinate - half_increment)) / increment
+}
+
+/// Computes the derivative of order `order` at `x_coordinate` with a centred stencil of `num_points` points spaced by `increment`, as described in `Derivative::fornberg_derivative`.
+fn stencil_derivative(
+    function: &dyn Fn(f64) -> f64,
+    x_coordinate: f64,
+    increment: f64,
+    order: usize,
+    num_points: usize,
+) -> f64 {
+    let num_points = num_points.max(order + 1);
+
+    // Offsets of the stencil points from `x_coordinate`, in units of `increment`
+    let centre = (num_points - 1) as f64 / 2.0;
+    let offsets: Vec<f64> = (0..num_points).map(|j| j as f64 - centre).collect();
+    let weights = fornberg_weights(&offsets, order);
+
+    let sum: f64 = offsets
+        .iter()
+        .zip(&weights)
+        .map(|(offset, weight)| weight * function(x_coordinate + offset * increment))
+        .sum();
+    sum / increment.powi(order as i32)
+}
+
+/// Computes the finite difference weights of the derivative of order `order` at `0`, for a stencil with points at `offsets`.
+///
+/// Implements the recurrence of B. Fornberg, "Generation of Finite Difference Formulas on Arbitrarily Spaced Grids" (1988).
+/// The derivative is approximated by the sum of the weights multiplied by the function values at the offsets, divided by the spacing raised to `order`.
+fn fornberg_weights(offsets: &[f64], order: usize) -> Vec<f64> {
+    let n = offsets.len();
+    // weights[j][k]: weight of the point `j` for the derivative of order `k`
+    let mut weights = vec![vec![0.0; order + 1]; n];
+    weights[0][0] = 1.0;
+
+    let mut c1 = 1.0;
+    let mut c4 = offsets[0];
+    for i in 1..n {
+        let max_order = i.min(order);
+        let mut c2 = 1.0;
+        let c5 = c4;
+        c4 = offsets[i];
+
+        for j in 0..i {
+            let c3 = offsets[i] - offsets[j];
+            c2 *= c3;
+            if j == i - 1 {
+                for k in (1..=max_order).rev() {
+                    weights[i][k] =
+                        c1 * (k as f64 * weights[i - 1][k - 1] - c5 * weights[i - 1][k]) / c2;
+                }
+                weights[i][0] = -c1 * c5 * weights[i - 1][0] / c2;
+            }
+            for k in (1..=max_order).rev() {
+                weights[j][k] = (c4 * weights[j][k] - k as f64 * weights[j][k - 1]) / c3;
+            }
+            weights[j][0] = c4 * weights[j][0] / c3;
+        }
+        c1 = c2;
+    }
+
+    weights
+        .iter()
+        .map(|point_weights| point_weights[order])
+        .collect()
 }
 
 // ---- Tests ---- //
@@ -204,4 +999,384 @@ mod tests {
             1e-6,
         );
     }
+
+    #[test]
+    fn test_fornberg_matches_five_point_stencil() {
+        let function = |x: f64| x.sin() * x.exp();
+        let (x, h) = (0.7, 1e-3);
+        let five_point = (-function(x + 2.0 * h) + 8.0 * function(x + h) - 8.0 * function(x - h)
+            + function(x - 2.0 * h))
+            / (12.0 * h);
+
+        let result = Derivative::new(Box::new(function), x, h).fornberg_derivative(1, 5);
+        assert!(
+            (result - five_point).abs() < 1e-9,
+            "Fornberg: {}, five-point stencil: {}",
+            result,
+            five_point
+        );
+    }
+
+    #[test]
+    fn test_fornberg_high_order_derivative() {
+        // The fourth derivative of sin(x) is sin(x)
+        let result =
+            Derivative::new(Box::new(|x: f64| x.sin()), 1.0, 0.05).fornberg_derivative(4, 9);
+        assert!(
+            (result - 1.0_f64.sin()).abs() < 1e-6,
+            "Result: {}, expected: {}",
+            result,
+            1.0_f64.sin()
+        );
+    }
+
+    #[test]
+    fn test_suggest_increment_meets_target() {
+        for x_coordinate in [0.3, 1.0, 2.5] {
+            for target_rel_error in [1e-4, 1e-6, 1e-8] {
+                let mut derivative =
+                    Derivative::new(Box::new(|x: f64| x.sin()), x_coordinate, 1e-7);
+                derivative.increment = derivative.suggest_increment(target_rel_error);
+                let expected = x_coordinate.cos();
+                let rel_error = ((derivative.central_difference() - expected) / expected).abs();
+
+                assert!(
+                    rel_error <= target_rel_error,
+                    "x = {}, increment = {}: relative error {} above target {}",
+                    x_coordinate,
+                    derivative.increment,
+                    rel_error,
+                    target_rel_error
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_suggest_increment_keeps_state() {
+        let mut derivative = Derivative::new(Box::new(|x: f64| x.sin()), 1.0, 1e-5);
+        let result = derivative.central_difference();
+        derivative.suggest_increment(1e-8);
+        assert_eq!(derivative.result, result);
+        assert_eq!(derivative.last_method(), Some("central_difference"));
+        assert_eq!(derivative.increment, 1e-5);
+    }
+
+    #[test]
+    fn test_step_underflow() {
+        let mut derivative = Derivative::new(Box::new(|x: f64| x * x), 1e16, 1e-7);
+        assert!(derivative.has_step_underflow());
+        assert_eq!(derivative.central_difference(), 0.0); // The failure the check detects
+
+        derivative.increment = 4.0; // Above the spacing of the floating-point numbers around 1e16
+        assert!(!derivative.has_step_underflow());
+    }
+
+    #[test]
+    fn test_derivative_from_samples() {
+        // Unevenly spaced samples of x^2
+        let xs: Vec<f64> = (0..20).map(|i| (i as f64 * 0.1).powf(1.5)).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| x * x).collect();
+
+        let central = derivative_from_samples(&xs, &ys, &CentralDifference).unwrap();
+        for (x, derivative) in xs.iter().zip(&central) {
+            assert!(
+                (derivative - 2.0 * x).abs() < 1e-9,
+                "x = {}: {}",
+                x,
+                derivative
+            );
+        }
+
+        let forward = derivative_from_samples(&xs, &ys, &ForwardDifference).unwrap();
+        for i in 1..xs.len() - 1 {
+            assert!((forward[i] - 2.0 * xs[i]).abs() <= xs[i + 1] - xs[i] + 1e-9);
+        }
+
+        assert_eq!(
+            derivative_from_samples(&xs[..2], &ys[..2], &CentralDifference),
+            Err(InterpError::NotEnoughPoints {
+                required: 3,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_step_size_study() {
+        let steps: Vec<f64> = (0..=14).map(|k| 10f64.powi(-k)).collect();
+        let mut derivative = Derivative::new(Box::new(|x: f64| x.sin()), 1.0, 1e-6);
+        let study = derivative.step_size_study(&steps);
+
+        assert_eq!(study.len(), steps.len());
+        assert!(study.iter().all(|(_, result)| result.is_finite()));
+        assert_eq!(derivative.increment, 1e-6);
+
+        let errors: Vec<f64> = study
+            .iter()
+            .map(|(_, result)| (result - 1.0_f64.cos()).abs())
+            .collect();
+        let smallest = errors[1..errors.len() - 1]
+            .iter()
+            .cloned()
+            .fold(f64::INFINITY, f64::min);
+        assert!(smallest < errors[0] && smallest < errors[errors.len() - 1]);
+    }
+
+    #[test]
+    fn test_gradient() {
+        let function = |v: &[f64]| v[0] * v[0] * v[1] + v[2].sin();
+        let point = [1.5, -2.0, 0.5];
+        let expected = [2.0 * 1.5 * -2.0, 1.5 * 1.5, 0.5_f64.cos()];
+
+        let result = gradient(&function, &point, 1e-6);
+        assert_eq!(result.len(), 3);
+        for (partial, expected) in result.iter().zip(expected) {
+            assert!(
+                (partial - expected).abs() < 1e-6,
+                "{} != {}",
+                partial,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_mixed_partial() {
+        // f(x, y) = x^2 y: ∂²f/∂x∂y = 2x, ∂²f/∂x² = 2y, ∂²f/∂y² = 0
+        let function = |v: &[f64]| v[0] * v[0] * v[1];
+        let point = [1.5, -2.0];
+
+        let mixed = mixed_partial(&function, &point, 0, 1, 1e-4);
+        assert!((mixed - 3.0).abs() < 1e-6, "Mixed: {}", mixed);
+        assert_eq!(mixed_partial(&function, &point, 1, 0, 1e-4), mixed);
+
+        let xx = mixed_partial(&function, &point, 0, 0, 1e-4);
+        assert!((xx + 4.0).abs() < 1e-6, "xx: {}", xx);
+        let yy = mixed_partial(&function, &point, 1, 1, 1e-4);
+        assert!(yy.abs() < 1e-6, "yy: {}", yy);
+    }
+
+    #[test]
+    fn test_derivative_from_config() {
+        for i in 0..5 {
+            let config = DerivativeConfig {
+                x_coordinate: i as f64 * 0.5,
+                increment: 1e-6,
+            };
+            let mut derivative = Derivative::from((Box::new(|x: f64| x.exp()) as Function, config));
+            let expected = config.x_coordinate.exp();
+            assert!((derivative.central_difference() - expected).abs() < 1e-6 * expected);
+        }
+    }
+
+    #[test]
+    fn test_central_difference_symmetric() {
+        // Odd: fourth-order instead of second-order, with the same number of evaluations
+        let mut derivative = Derivative::new(Box::new(|x: f64| x.sin()), 0.0, 1e-2);
+        let plain_error = (derivative.central_difference() - 1.0).abs();
+        let symmetric_error = (derivative.central_difference_symmetric(Parity::Odd) - 1.0).abs();
+        assert!(
+            symmetric_error < plain_error / 1e3,
+            "Symmetric error: {}, plain error: {}",
+            symmetric_error,
+            plain_error
+        );
+
+        // Even: exactly zero, even for a function whose evaluation is not symmetric in floating point
+        let mut derivative = Derivative::new(Box::new(|x: f64| x.cos()), 0.0, 1e-2);
+        assert_eq!(derivative.central_difference_symmetric(Parity::Even), 0.0);
+        let mut derivative =
+            Derivative::new(Box::new(|x: f64| ((x + 1.0) - 1.0).powi(2)), 0.0, 1e-7);
+        assert_ne!(derivative.central_difference(), 0.0);
+        assert_eq!(derivative.central_difference_symmetric(Parity::Even), 0.0);
+
+        // Away from zero, the same as the central difference
+        let mut derivative = Derivative::new(Box::new(|x: f64| x.sin()), 0.5, 1e-6);
+        assert_eq!(
+            derivative.central_difference_symmetric(Parity::Odd),
+            derivative.central_difference()
+        );
+    }
+
+    #[test]
+    fn test_taylor_derivative() {
+        for num_terms in [2, 3, 5, 7] {
+            let mut derivative = Derivative::new(Box::new(|x: f64| x.sin()), 0.7, 1e-2);
+            let taylor = derivative.taylor_derivative(num_terms);
+            let fornberg = derivative.fornberg_derivative(1, num_terms);
+            assert!(
+                (taylor - fornberg).abs() < 1e-9,
+                "Taylor: {}, Fornberg: {}",
+                taylor,
+                fornberg
+            );
+        }
+
+        // Converges to the true derivative as the increment shrinks
+        let errors: Vec<f64> = [1e-1, 5e-2, 2.5e-2]
+            .iter()
+            .map(|&increment| {
+                let mut derivative = Derivative::new(Box::new(|x: f64| x.sin()), 0.7, increment);
+                (derivative.taylor_derivative(5) - 0.7_f64.cos()).abs()
+            })
+            .collect();
+        assert!(errors[1] < errors[0] / 10.0 && errors[2] < errors[1] / 10.0);
+    }
+
+    #[test]
+    fn test_error_breakdown() {
+        let exact = 1.0_f64.cos();
+
+        // Tiny increment: the roundoff error dominates
+        let mut derivative = Derivative::new(Box::new(|x: f64| x.sin()), 1.0, 1e-13);
+        let (truncation, total) = derivative.error_breakdown(exact);
+        assert!(
+            truncation < total / 1e3,
+            "Truncation: {:e}, total: {:e}",
+            truncation,
+            total
+        );
+
+        // Large increment: the truncation error dominates, and accounts for almost all the error
+        let mut derivative = Derivative::new(Box::new(|x: f64| x.sin()), 1.0, 1e-1);
+        let (truncation, total) = derivative.error_breakdown(exact);
+        assert!(
+            (total - truncation).abs() < total / 100.0,
+            "Truncation: {:e}, total: {:e}",
+            truncation,
+            total
+        );
+
+        // The result is the central difference, not the pilot third derivative
+        assert_eq!(derivative.last_method(), Some("error_breakdown"));
+        assert_eq!(derivative.get_result(), derivative.central_difference());
+        assert_eq!((derivative.get_result() - exact).abs(), total);
+    }
+
+    #[test]
+    fn test_last_method() {
+        let mut derivative = Derivative::new(Box::new(|x: f64| x.sin()), 0.5, 1e-5);
+        assert_eq!(derivative.last_method(), None);
+        derivative.forward_difference();
+        assert_eq!(derivative.last_method(), Some("forward_difference"));
+        derivative.fornberg_derivative(1, 5);
+        assert_eq!(derivative.last_method(), Some("fornberg_derivative"));
+
+        // Reported by the outer method, not by the one it is built on
+        derivative.step_size_study(&[1e-3, 1e-4]);
+        assert_eq!(derivative.last_method(), Some("step_size_study"));
+    }
+
+    #[test]
+    fn test_chain_rule() {
+        // d/dx sin(x^2) = 2x cos(x^2)
+        let outer: Function = Box::new(|u| u.sin());
+        let inner: Function = Box::new(|x| x * x);
+        let x: f64 = 0.8;
+        let exact = 2.0 * x * (x * x).cos();
+
+        let direct = chain_rule_derivative(&outer, &inner, x, 1e-5);
+        let product = chain_rule_product(&outer, &inner, x, 1e-5);
+        assert!((direct - exact).abs() < 1e-8, "Direct: {}", direct);
+        assert!((product - exact).abs() < 1e-8, "Product: {}", product);
+        assert!((direct - product).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_taylor_coefficients() {
+        // exp(x) around 0: 1 / k!
+        let f: Function = Box::new(|x| x.exp());
+        let coefficients = taylor_coefficients(&f, 0.0, 6, 0.05);
+        let mut factorial = 1.0;
+        for (k, coefficient) in coefficients.iter().enumerate() {
+            if k > 0 {
+                factorial *= k as f64;
+            }
+            assert!(
+                (coefficient - 1.0 / factorial).abs() < 1e-3 / factorial,
+                "k = {}: {}, expected: {}",
+                k,
+                coefficient,
+                1.0 / factorial
+            );
+        }
+
+        // sin(x) around 0: 0, 1, 0, -1/6, 0, 1/120
+        let f: Function = Box::new(|x| x.sin());
+        let coefficients = taylor_coefficients(&f, 0.0, 5, 0.05);
+        let expected = [0.0, 1.0, 0.0, -1.0 / 6.0, 0.0, 1.0 / 120.0];
+        for (coefficient, expected) in coefficients.iter().zip(expected) {
+            assert!((coefficient - expected).abs() < 1e-3 * expected.abs().max(1e-3));
+        }
+    }
+
+    #[test]
+    fn test_with_direction() {
+        // exp is increasing and convex: both one-sided estimates are positive, and they bracket the derivative
+        let mut derivative = Derivative::new(Box::new(|x: f64| x.exp()), 1.0, 1e-3);
+        let positive = derivative.forward_difference();
+        derivative.with_direction(false);
+        assert!(derivative.increment < 0.0);
+        let negative = derivative.forward_difference();
+        assert!(negative > 0.0 && negative < std::f64::consts::E && std::f64::consts::E < positive);
+
+        // The negative direction is the backward difference, and the magnitude of the increment is kept
+        derivative.with_direction(false);
+        derivative.with_direction(true);
+        assert_eq!(derivative.increment, 1e-3);
+        assert_eq!(derivative.backward_difference(), negative);
+    }
+
+    #[test]
+    fn test_validate_against() {
+        let analytic: Function = Box::new(|x| x.cos());
+        let mut derivative = Derivative::new(Box::new(|x: f64| x.sin()), 0.8, 1e-6);
+        let report = derivative.validate_against(&analytic);
+
+        let names: Vec<&str> = report.iter().map(|(name, _, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "Forward Difference",
+                "Backward Difference",
+                "Central Difference"
+            ]
+        );
+        for (name, numerical, error) in &report {
+            assert!(*error < 1e-6, "{}: error {}", name, error);
+            assert_eq!(*error, (numerical - 0.8_f64.cos()).abs());
+        }
+        assert_eq!(derivative.get_result(), report[2].1);
+    }
+
+    #[test]
+    fn test_extrapolate_to_zero_step() {
+        let x = 1.0_f64;
+        let exact = 1.0 / x.cos().powi(2);
+        let steps = [0.05, 0.04, 0.03, 0.02, 0.01];
+        let mut derivative = Derivative::new(Box::new(|x: f64| x.tan()), x, 1e-6);
+
+        let extrapolated = derivative.extrapolate_to_zero_step(&steps);
+        assert_eq!(derivative.increment, 1e-6);
+        assert_eq!(derivative.get_result(), extrapolated);
+
+        let best_single = derivative
+            .step_size_study(&steps)
+            .iter()
+            .map(|(_, result)| (result - exact).abs())
+            .fold(f64::INFINITY, f64::min);
+        let error = (extrapolated - exact).abs();
+        assert!(
+            error < best_single / 10.0,
+            "Extrapolated error {}, best single step {}",
+            error,
+            best_single
+        );
+
+        // Not enough distinct steps to fit the model
+        let single = derivative.extrapolate_to_zero_step(&[0.01, 0.01]);
+        assert_eq!(single, derivative.step_size_study(&[0.01])[0].1);
+        assert!(derivative.extrapolate_to_zero_step(&[]).is_nan());
+    }
 }
@@ -0,0 +1,68 @@
+//! Exports the results of the calculations in formats suited to reports, beyond the terminal table.
+
+use crate::{CalculationStats, display_result};
+
+/// Formats the statistics of the calculations as a GitHub-flavored Markdown table, with the results right-aligned.
+///
+/// The results are formatted as in the terminal table, with the notation and the unit chosen by the user.
+///
+/// # Arguments
+/// * `stats` - The statistics of the calculations, one row each.
+///
+/// # Returns
+/// The Markdown table, with a trailing newline.
+pub fn to_markdown(stats: &[CalculationStats]) -> String {
+    let mut table = String::from("| Algorithm | Process Time | Result |\n| --- | --- | ---: |\n");
+    for stat in stats {
+        table.push_str(&format!(
+            "| {} | {} | {} |\n",
+            escape_cell(&stat.algorithm),
+            escape_cell(&stat.process_time),
+            escape_cell(&display_result(&stat.result, stat))
+        ));
+    }
+    table
+}
+
+/// Escapes the pipes of a cell, which would otherwise end it.
+fn escape_cell(cell: &str) -> String {
+    cell.replace('|', "\\|")
+}
+
+// ---- Tests ---- //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_math_lib::utils::format::NumberFormat;
+
+    #[test]
+    fn test_to_markdown() {
+        let stats = vec![
+            CalculationStats {
+                algorithm: "Riemann".to_string(),
+                process_time: "1.5ms".to_string(),
+                result: 1.9999999999999998,
+                number_format: NumberFormat::Fixed(4),
+                units: None,
+                order: 1,
+            },
+            CalculationStats {
+                algorithm: "Simpson's 1/3".to_string(),
+                process_time: "4.2ms".to_string(),
+                result: 2.0,
+                number_format: NumberFormat::Fixed(4),
+                units: Some("m|s".to_string()),
+                order: 4,
+            },
+        ];
+
+        let markdown = to_markdown(&stats);
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(lines.len(), 2 + stats.len());
+        assert_eq!(lines[0], "| Algorithm | Process Time | Result |");
+        assert_eq!(lines[1], "| --- | --- | ---: |");
+        assert_eq!(lines[2], "| Riemann | 1.5ms | 2.000 |");
+        assert_eq!(lines[3], "| Simpson's 1/3 | 4.2ms | 2.000 m\\|s |");
+    }
+}
@@ -0,0 +1,301 @@
+//! This module implements an append-only log of the calculations performed, which can be loaded back to replay them.
+//!
+//! Each calculation is described by a `HistoryEntry`, which is written as a single line of a plain text file.
+//! The fields of a line are separated by tabs:
+//! - the timestamp, in seconds since the UNIX epoch;
+//! - the type of calculation (e.g. `Integrals`);
+//! - the method used (e.g. `Riemann`);
+//! - the inputs, as `name=value` pairs separated by semicolons;
+//! - the result.
+
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Name of the history file, created in the home directory of the user.
+pub const HISTORY_FILE_NAME: &str = ".rust_math_history";
+
+/// A single calculation recorded in the history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub timestamp: u64, // Seconds since the UNIX epoch
+    pub calculation: String,
+    pub method: String,
+    pub inputs: Vec<(String, String)>, // Pairs of input name and value, as typed by the user
+    pub result: f64,
+}
+
+impl HistoryEntry {
+    /// Creates a new entry, timestamped with the current time.
+    pub fn new(
+        calculation: &str,
+        method: &str,
+        inputs: Vec<(String, String)>,
+        result: f64,
+    ) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+
+        HistoryEntry {
+            timestamp,
+            calculation: calculation.to_string(),
+            method: method.to_string(),
+            inputs,
+            result,
+        }
+    }
+
+    /// Returns the value of the input called `name`, if the entry has one.
+    pub fn input(&self, name: &str) -> Option<&str> {
+        self.inputs
+            .iter()
+            .find(|(input_name, _)| input_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl fmt::Display for HistoryEntry {
+    /// Formats the entry as a line of the history file, without the trailing newline.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let inputs: Vec<String> = self
+            .inputs
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}",
+            self.timestamp,
+            self.calculation,
+            self.method,
+            inputs.join(";"),
+            self.result
+        )
+    }
+}
+
+impl FromStr for HistoryEntry {
+    type Err = String;
+
+    /// Parses a line of the history file, as formatted by `Display`.
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 5 {
+            return Err(format!("expected 5 fields, found {}", fields.len()));
+        }
+
+        let timestamp = fields[0]
+            .parse::<u64>()
+            .map_err(|e| format!("invalid timestamp '{}': {}", fields[0], e))?;
+        let inputs = if fields[3].is_empty() {
+            vec![]
+        } else {
+            fields[3]
+                .split(';')
+                .map(|pair| match pair.split_once('=') {
+                    Some((name, value)) => Ok((name.to_string(), value.to_string())),
+                    None => Err(format!("invalid input '{}'", pair)),
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        };
+        let result = fields[4]
+            .parse::<f64>()
+            .map_err(|e| format!("invalid result '{}': {}", fields[4], e))?;
+
+        Ok(HistoryEntry {
+            timestamp,
+            calculation: fields[1].to_string(),
+            method: fields[2].to_string(),
+            inputs,
+            result,
+        })
+    }
+}
+
+/// Returns the path of the history file in the home directory of the user, if the home directory is known.
+pub fn default_history_path() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(HISTORY_FILE_NAME))
+}
+
+/// Appends an entry to the history file at `path`, creating the file if it does not exist.
+///
+/// # Arguments
+///
+/// * `entry` - The entry to append.
+/// * `path` - The path of the history file.
+///
+/// # Returns
+///
+/// An error if the file cannot be opened or written.
+pub fn append_history(entry: &HistoryEntry, path: &Path) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", entry)
+}
+
+/// Loads all the entries of the history file at `path`, in the order they were appended.
+///
+/// Malformed lines are skipped, printing a warning to the standard error.
+///
+/// # Arguments
+///
+/// * `path` - The path of the history file.
+///
+/// # Returns
+///
+/// The entries of the history file, or an error if the file cannot be opened or read.
+pub fn load_history(path: &Path) -> io::Result<Vec<HistoryEntry>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut entries = vec![];
+
+    for (line_number, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match line.parse::<HistoryEntry>() {
+            Ok(entry) => entries.push(entry),
+            Err(e) => eprintln!(
+                "Warning: skipping malformed line {} of the history file: {}",
+                line_number + 1,
+                e
+            ),
+        }
+    }
+
+    Ok(entries)
+}
+
+// ---- Tests ---- //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Helper function returning a path in the temporary directory, unique to the calling test.
+    fn temp_history_path(test_name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "rust_math_history_{}_{}",
+            test_name,
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn test_append_history() {
+        let path = temp_history_path("append");
+        let _ = std::fs::remove_file(&path);
+
+        let entries = vec![
+            HistoryEntry::new(
+                "Integrals",
+                "Riemann",
+                vec![
+                    ("function".to_string(), "sin(x)".to_string()),
+                    ("lower_bound".to_string(), "0".to_string()),
+                    ("upper_bound".to_string(), "pi".to_string()),
+                    ("num_intervals".to_string(), "1e7".to_string()),
+                ],
+                2.0,
+            ),
+            HistoryEntry::new(
+                "Derivatives",
+                "Central Difference",
+                vec![
+                    ("function".to_string(), "x^2".to_string()),
+                    ("x_coordinate".to_string(), "2".to_string()),
+                    ("increment".to_string(), "1e-7".to_string()),
+                ],
+                4.0,
+            ),
+        ];
+        for entry in &entries {
+            append_history(entry, &path).unwrap();
+        }
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), entries.len());
+        for (line, entry) in lines.iter().zip(&entries) {
+            assert_eq!(*line, entry.to_string());
+        }
+        assert!(lines[0].ends_with("\tIntegrals\tRiemann\tfunction=sin(x);lower_bound=0;upper_bound=pi;num_intervals=1e7\t2"));
+    }
+
+    #[test]
+    fn test_load_history_round_trip() {
+        let path = temp_history_path("round_trip");
+        let _ = std::fs::remove_file(&path);
+
+        let entries = vec![
+            HistoryEntry::new(
+                "Integrals",
+                "Riemann",
+                vec![
+                    ("function".to_string(), "x^2".to_string()),
+                    ("lower_bound".to_string(), "0".to_string()),
+                    ("upper_bound".to_string(), "3".to_string()),
+                    ("num_intervals".to_string(), "1e6".to_string()),
+                ],
+                8.9999865,
+            ),
+            HistoryEntry::new(
+                "Derivatives",
+                "Forward Difference",
+                vec![
+                    ("function".to_string(), "exp(x)".to_string()),
+                    ("x_coordinate".to_string(), "1".to_string()),
+                    ("increment".to_string(), "1e-8".to_string()),
+                ],
+                std::f64::consts::E,
+            ),
+            HistoryEntry::new("Derivatives", "Central Difference", vec![], f64::NAN),
+        ];
+        for entry in &entries {
+            append_history(entry, &path).unwrap();
+        }
+
+        let loaded = load_history(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.len(), entries.len());
+        assert_eq!(loaded[0], entries[0]);
+        assert_eq!(loaded[1], entries[1]);
+        assert_eq!(loaded[1].input("increment"), Some("1e-8"));
+        assert!(loaded[2].result.is_nan());
+        assert!(loaded[2].inputs.is_empty());
+    }
+
+    #[test]
+    fn test_load_history_skips_malformed_lines() {
+        let path = temp_history_path("malformed");
+        let entry = HistoryEntry::new(
+            "Integrals",
+            "Riemann",
+            vec![("function".to_string(), "sin(x)".to_string())],
+            2.0,
+        );
+        std::fs::write(
+            &path,
+            format!(
+                "{}\nthis line is corrupt\n1700000000\tIntegrals\tRiemann\tfunction=x\tnot_a_number\n{}\n",
+                entry, entry
+            ),
+        )
+        .unwrap();
+
+        let loaded = load_history(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, vec![entry.clone(), entry]);
+    }
+}
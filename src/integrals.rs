@@ -3,25 +3,410 @@
 //! It contains the `Integrator` struct, with two methods which perform numerical integration:
 //! - `riemann_integration`: Uses the Riemann sum method to approximate the integral of a function over a specified interval.
 //! - `simpson_integration_one_third`: Uses Simpson's 1/3 rule to approximate the integral of a function over a specified interval.
+//! - `midpoint_integration`: Samples the function at the middle of each slice, so it also integrates functions singular at the bounds.
+//! - `monte_carlo_integration`: Averages the function over uniformly distributed random points to approximate the integral of a function over a specified interval.
+//!
+//! Complex-valued functions can be integrated with `integrate_complex`.
+//!
+//! All the methods return exactly `0.0` for an interval of zero width, i.e. with bounds equal up to rounding, without evaluating the function.
+//!
+//! The methods on a uniform grid are streaming: the function is evaluated and summed one point at a time, so the memory used does not grow with the number of intervals, even near `MAX_NUM_INTERVALS`.
+//! The only exceptions are the methods which return the samples by design, e.g. `integrate_and_collect`.
+
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::derivatives::Derivative;
+use crate::interpolation::{self, InterpError};
+use crate::roots;
+use crate::utils::complex::Complex;
+use crate::utils::numeric::{
+    ADAPTIVE_TOLERANCE, AGREEMENT_TOLERANCE, CONSISTENCY_TOLERANCE, EPSILON, ROUNDING_TOLERANCE,
+    approx_eq, is_zero_width, rel_eq,
+};
+use crate::utils::random::Rng;
+
+/// A function of a real variable, boxed so that closures can be stored and passed around.
+/// By default the function is real-valued.
+pub type Function<S = f64> = Box<dyn Fn(f64) -> S>;
+
+/// A real function evaluated over many points at once.
+///
+/// Evaluating a batch of points in one call lets an implementation use SIMD instructions, or offload the work to a GPU.
+/// Every closure is a `BatchFunction`, evaluating the points one at a time.
+pub trait BatchFunction {
+    /// Writes the values of the function at the points of `xs` into `out`, which has the same length.
+    fn eval_batch(&self, xs: &[f64], out: &mut [f64]);
+}
+
+impl<F: Fn(f64) -> f64> BatchFunction for F {
+    fn eval_batch(&self, xs: &[f64], out: &mut [f64]) {
+        for (x, y) in xs.iter().zip(out.iter_mut()) {
+            *y = self(*x);
+        }
+    }
+}
+
+/// A sampler of a probability distribution, drawing a point from a generator of uniform numbers in `[0, 1)`.
+pub type Sampler = Box<dyn Fn(&mut dyn FnMut() -> f64) -> f64>;
+
+/// Default cap on the number of intervals, beyond which an integration would take hours.
+pub const MAX_NUM_INTERVALS: u64 = 1e9 as u64;
+
+/// Maximum number of times an interval can be halved by the adaptive methods.
+const MAX_ADAPTIVE_DEPTH: u32 = 50;
+
+/// Factor by which a value of the function must exceed its neighbouring samples to be considered a pole.
+/// Evaluating a function at a pole usually gives a finite value, because the pole is only hit within rounding error, e.g. `tan(pi / 2)` is about `1.6e16` while `tan` is about `1` around it.
+const POLE_JUMP_RATIO: f64 = 1.0 / f64::EPSILON;
+
+/// Number of slices sampled by `integrate_auto` to analyse the function.
+const AUTO_ANALYSIS_SAMPLES: usize = 128;
+
+/// Number of trapezoids each non-smooth slice is split into by `integrate_hybrid`.
+const KINK_SUBDIVISIONS: u64 = 64;
+
+/// Number of steps of the search of a solution on each side of the starting point, in `integrate_implicit`.
+const IMPLICIT_MAX_STEPS: usize = 10000;
+
+/// Maximum number of nodes of the Gauss-Legendre rules tried by `gauss_legendre_adaptive`.
+const MAX_GAUSS_ORDER: usize = 64;
+
+/// Number of cells of the table of the cumulative density, inverted by `integrate_density_adaptive` to place the slices.
+const DENSITY_TABLE_SIZE: usize = 4096;
+
+/// Number of intervals of the coarser of the two pilot runs of `plan_and_integrate`.
+const PLAN_PILOT_INTERVALS: u64 = 8;
+
+/// Maximum number of times `integrate_budgeted` doubles the number of intervals of a panel, from `PLAN_PILOT_INTERVALS`.
+const BUDGET_MAX_REFINEMENTS: u32 = 16;
+
+/// Number of points evaluated at once by `riemann_integration_batched`.
+const BATCH_SIZE: usize = 1024;
+
+/// Maximum number of chunks, i.e. of threads, of `riemann_integration_deterministic_parallel`.
+/// It is a constant rather than the number of cores, so that the result does not depend on the machine.
+const MAX_PARALLEL_CHUNKS: u64 = 64;
+
+/// Number of evaluations of the function timed by `estimate_cost`.
+const COST_BENCHMARK_EVALUATIONS: u32 = 1000;
+
+/// Errors that can occur during numerical integration.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntegrationError {
+    /// Fewer points than the method requires were provided.
+    NotEnoughPoints { required: usize, found: usize },
+    /// The abscissae and the ordinates of the samples have different lengths.
+    LengthMismatch { xs: usize, ys: usize },
+    /// The abscissae of the samples are not strictly increasing at the given index.
+    UnsortedAbscissae(usize),
+    /// The function is not finite at the given points.
+    NonFiniteIntegrand(Vec<f64>),
+    /// The adaptive method hit its cap on the number of subdivisions before meeting the tolerance. Carries the partial result.
+    MaxDepthReached(f64),
+    /// The function vanishes or changes sign near the given point, where a negative power of it has a pole.
+    ZeroCrossing(f64),
+    /// The density of the grid is not positive, or not finite, at the given point.
+    NonPositiveDensity(f64),
+    /// The lower bound is above the upper bound, which strict bounds reject.
+    ReversedBounds { lower_bound: f64, upper_bound: f64 },
+}
+
+impl fmt::Display for IntegrationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IntegrationError::NotEnoughPoints { required, found } => write!(
+                f,
+                "at least {} points are required, but {} were provided",
+                required, found
+            ),
+            IntegrationError::LengthMismatch { xs, ys } => {
+                write!(f, "{} abscissae and {} ordinates were provided", xs, ys)
+            }
+            IntegrationError::UnsortedAbscissae(index) => write!(
+                f,
+                "the abscissae are not strictly increasing at index {}",
+                index
+            ),
+            IntegrationError::NonFiniteIntegrand(points) => {
+                let points: Vec<String> = points.iter().map(|x| x.to_string()).collect();
+                write!(f, "the function is not finite at x = {}", points.join(", "))
+            }
+            IntegrationError::MaxDepthReached(partial) => write!(
+                f,
+                "the maximum subdivision depth was reached before meeting the tolerance, partial result: {}",
+                partial
+            ),
+            IntegrationError::ZeroCrossing(x) => {
+                write!(f, "the function crosses zero near x = {}", x)
+            }
+            IntegrationError::NonPositiveDensity(x) => {
+                write!(f, "the density is not positive at x = {}", x)
+            }
+            IntegrationError::ReversedBounds {
+                lower_bound,
+                upper_bound,
+            } => write!(
+                f,
+                "the lower bound {} is above the upper bound {}",
+                lower_bound, upper_bound
+            ),
+        }
+    }
+}
+
+impl std::error::Error for IntegrationError {}
+
+impl From<InterpError> for IntegrationError {
+    /// Converts an error on the samples, as checked by `interpolation::validate_samples`, into the matching integration error.
+    fn from(error: InterpError) -> Self {
+        match error {
+            InterpError::NotEnoughPoints { required, found } => {
+                IntegrationError::NotEnoughPoints { required, found }
+            }
+            InterpError::LengthMismatch { xs, ys } => IntegrationError::LengthMismatch { xs, ys },
+            InterpError::UnsortedAbscissae(index) => IntegrationError::UnsortedAbscissae(index),
+        }
+    }
+}
+
+/// Errors of the iterative methods, which refine an estimate until it converges, e.g. `Integral::simpson_romberg`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConvergenceError {
+    /// The tolerance was not met within the maximum number of iterations. Carries the partial result and the number of iterations performed.
+    MaxIterationsReached { partial: f64, iterations: usize },
+    /// The integral cannot be computed at all, e.g. because of reversed strict bounds.
+    Integration(IntegrationError),
+}
+
+impl fmt::Display for ConvergenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConvergenceError::MaxIterationsReached {
+                partial,
+                iterations,
+            } => write!(
+                f,
+                "the tolerance was not met after {} iterations, partial result: {}",
+                iterations, partial
+            ),
+            ConvergenceError::Integration(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for ConvergenceError {}
+
+impl From<IntegrationError> for ConvergenceError {
+    fn from(error: IntegrationError) -> Self {
+        ConvergenceError::Integration(error)
+    }
+}
+
+/// The integration methods with a fixed number of evaluations of the function, whose cost can be estimated in advance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IntegrationMethod {
+    Riemann,
+    Simpson,
+    /// Monte Carlo with as many samples as intervals.
+    MonteCarlo,
+}
+
+impl IntegrationMethod {
+    /// Returns the number of evaluations of the function per interval.
+    pub fn evaluations_per_interval(&self) -> u64 {
+        match self {
+            IntegrationMethod::Riemann | IntegrationMethod::MonteCarlo => 1,
+            IntegrationMethod::Simpson => 3, // Both ends and the middle of each slice
+        }
+    }
+}
+
+/// The algorithms accumulating the areas of the slices, trading speed for accuracy on large grids.
+///
+/// Adding many small areas to a large running sum loses their low-order digits, so the rounding error of a naive sum grows with the number of intervals, up to `n * eps` relative.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SummationStrategy {
+    /// Adds each area to the running sum: the fastest, with an error growing as `n`.
+    #[default]
+    Naive,
+    /// Kahan's compensated summation, carrying the digits lost by each addition over to the next one: an error independent of `n`, for about four times the additions.
+    Kahan,
+    /// Adds the areas in pairs, then the pairs in pairs, and so on, keeping the partial sums of similar magnitude: an error growing as `log(n)`, for a little bookkeeping.
+    Pairwise,
+}
+
+/// An estimate of the cost of an integration, before performing it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostEstimate {
+    pub num_intervals: u64,
+    pub time_per_evaluation: Duration, // Measured on a few evaluations of the function
+}
+
+impl CostEstimate {
+    /// Returns the number of evaluations of the function the method will perform.
+    pub fn evaluations(&self, method: IntegrationMethod) -> u64 {
+        method.evaluations_per_interval() * self.num_intervals
+    }
+
+    /// Returns a rough estimate of the time the method will take, from the time of an evaluation of the function.
+    pub fn estimated_time(&self, method: IntegrationMethod) -> Duration {
+        self.time_per_evaluation
+            .mul_f64(self.evaluations(method) as f64)
+    }
+}
+
+/// A value which can be accumulated by the integration methods, such as a number or a vector.
+///
+/// Implementing this trait for a type allows integrating functions with values of that type, e.g. vector- or matrix-valued functions.
+pub trait Summable: Clone {
+    /// Returns the additive identity.
+    fn zero() -> Self;
+    /// Returns the sum of `self` and `other`.
+    fn add(&self, other: &Self) -> Self;
+    /// Returns `self` multiplied by the scalar `factor`.
+    fn scale(&self, factor: f64) -> Self;
+}
+
+impl Summable for f64 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn scale(&self, factor: f64) -> Self {
+        self * factor
+    }
+}
+
+/// Vectors are summed and scaled component-wise.
+impl<S: Summable, const N: usize> Summable for [S; N] {
+    fn zero() -> Self {
+        std::array::from_fn(|_| S::zero())
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        std::array::from_fn(|i| self[i].add(&other[i]))
+    }
+
+    fn scale(&self, factor: f64) -> Self {
+        std::array::from_fn(|i| self[i].scale(factor))
+    }
+}
+
+impl Summable for Complex {
+    fn zero() -> Self {
+        Complex::default()
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        *self + *other
+    }
 
-type Function = Box<dyn Fn(f64) -> f64>;
+    fn scale(&self, factor: f64) -> Self {
+        *self * factor
+    }
+}
 
 /// A struct that provides numerical integration methods.
-pub struct Integral {
-    pub function: Function, // Function to integrate
+///
+/// The function is real-valued by default. Functions with values of any `Summable` type can be integrated with the Riemann sum method.
+pub struct Integral<S = f64> {
+    pub function: Function<S>, // Function to integrate
     pub lower_bound: f64,
     pub upper_bound: f64,
     pub num_intervals: u64,
-    result: f64,
+    result: S,
+    summation: SummationStrategy, // How the areas of the slices are accumulated
+    strict_bounds: bool, // Whether reversed bounds are an error rather than a negated integral
+    last_method: Option<&'static str>, // Name of the method which computed the result
 }
 
-impl Integral {
-    pub fn new(function: Function, lower_bound: f64, upper_bound: f64, num_intervals: u64) -> Self {
+/// The parameters of an integral, without the function.
+///
+/// Unlike `Integral`, it is plain data, so it can be generated programmatically, e.g. for a parameter sweep, and paired with a function afterwards:
+///
+/// ```
+/// use rust_math_lib::integrals::{Integral, IntegralConfig};
+///
+/// let results: Vec<f64> = (1..=3)
+///     .map(|i| IntegralConfig { lower_bound: 0.0, upper_bound: i as f64, num_intervals: 100 })
+///     .map(|config| Integral::from((Box::new(|x: f64| x * x) as Box<dyn Fn(f64) -> f64>, config)))
+///     .map(|mut integral| integral.simpson_integration_one_third())
+///     .collect();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntegralConfig {
+    pub lower_bound: f64,
+    pub upper_bound: f64,
+    pub num_intervals: u64,
+}
+
+impl<S: Summable> From<(Function<S>, IntegralConfig)> for Integral<S> {
+    /// Creates a new integral of the function with the parameters of the configuration, as `Integral::new` does.
+    fn from((function, config): (Function<S>, IntegralConfig)) -> Self {
+        Integral::new(
+            function,
+            config.lower_bound,
+            config.upper_bound,
+            config.num_intervals,
+        )
+    }
+}
+
+impl<S: Summable> Integral<S> {
+    /// Creates a new integral, with the number of intervals capped to `MAX_NUM_INTERVALS`.
+    pub fn new(
+        function: Function<S>,
+        lower_bound: f64,
+        upper_bound: f64,
+        num_intervals: u64,
+    ) -> Self {
+        Self::with_max_intervals(
+            function,
+            lower_bound,
+            upper_bound,
+            num_intervals,
+            MAX_NUM_INTERVALS,
+        )
+    }
+
+    /// Creates a new integral, with the number of intervals capped to `max_intervals`.
+    ///
+    /// A number of intervals above the cap, e.g. `1e18` typed by mistake, would make the integration loop run practically forever:
+    /// it is clamped to the cap, printing a warning to the standard error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let integral = Integral::with_max_intervals(Box::new(|x| x), 0.0, 1.0, 1e18 as u64, 1e6 as u64);
+    /// assert_eq!(integral.num_intervals, 1e6 as u64);
+    /// ```
+    pub fn with_max_intervals(
+        function: Function<S>,
+        lower_bound: f64,
+        upper_bound: f64,
+        num_intervals: u64,
+        max_intervals: u64,
+    ) -> Self {
         // TODO: GS consider returning a Result instead of a struct
-        let num_intervals = if num_intervals > 0 {
+        let num_intervals = if num_intervals > max_intervals {
+            eprintln!(
+                "Warning: {} intervals requested, clamped to {}",
+                num_intervals, max_intervals
+            );
+            max_intervals
+        } else if num_intervals > 0 {
             num_intervals
         } else {
-            1e6 as u64 // Default value for number of intervals
+            1e6_f64.min(max_intervals as f64) as u64 // Default value for number of intervals
         };
 
         Integral {
@@ -29,8 +414,154 @@ impl Integral {
             lower_bound,
             upper_bound,
             num_intervals,
-            result: 0.0,
+            result: S::zero(),
+            summation: SummationStrategy::Naive,
+            strict_bounds: false,
+            last_method: None,
+        }
+    }
+
+    /// Returns the parameters of the integral, without the function.
+    pub fn config(&self) -> IntegralConfig {
+        IntegralConfig {
+            lower_bound: self.lower_bound,
+            upper_bound: self.upper_bound,
+            num_intervals: self.num_intervals,
+        }
+    }
+
+    /// Checks whether two integrals have the same bounds and number of intervals, e.g. to deduplicate or cache the integrals of a batch run.
+    ///
+    /// The functions are not compared, since closures cannot be: two integrals of different functions with the same parameters are equal for this method.
+    /// The bounds are compared as floating-point numbers, so `0.0` and `-0.0` are equal, while a `NaN` bound is equal to nothing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let square = Integral::new(Box::new(|x| x * x), 0.0, 1.0, 100);
+    /// let cube = Integral::new(Box::new(|x| x * x * x), 0.0, 1.0, 100);
+    /// assert!(square.config_eq(&cube));
+    /// ```
+    pub fn config_eq(&self, other: &Integral<S>) -> bool {
+        self.config() == other.config()
+    }
+
+    /// Returns the name of the last integration method called, e.g. `"adaptive_simpson"`, so that the result can be labelled without passing the name around.
+    ///
+    /// A method built on another one, e.g. `mean_value` on `simpson_integration_one_third`, reports its own name.
+    ///
+    /// # Returns
+    ///
+    /// The name of the method, as written in the code, or `None` if no method was called yet.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let mut integral = Integral::new(Box::new(|x| x * x), 0.0, 1.0, 100);
+    /// assert_eq!(integral.last_method(), None);
+    /// integral.simpson_integration_one_third();
+    /// assert_eq!(integral.last_method(), Some("simpson_integration_one_third"));
+    /// ```
+    pub fn last_method(&self) -> Option<&'static str> {
+        self.last_method
+    }
+
+    /// Sets the algorithm accumulating the areas of the slices, naive summation by default.
+    ///
+    /// It applies to all the methods which sum the slices of a uniform grid, e.g. `riemann_integration`, `simpson_integration_one_third`, `midpoint_integration` or `trapezoidal_composite`,
+    /// and their variants gathering more information on the way, e.g. `integrate_with_monitor`, `riemann_integration_batched`, `integrate_and_collect` or `integrate_with_extrema`.
+    /// The adaptive methods already add their panels in pairs, and the Monte Carlo methods are dominated by the sampling error, so they are not affected.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - The summation algorithm.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::{Integral, SummationStrategy};
+    ///
+    /// let mut integral = Integral::new(Box::new(|x: f64| x.cos()), 0.0, 1.0, 1e6 as u64);
+    /// integral.with_summation(SummationStrategy::Kahan);
+    /// assert!((integral.midpoint_integration() - 1f64.sin()).abs() < 1e-12);
+    /// ```
+    pub fn with_summation(&mut self, strategy: SummationStrategy) {
+        self.summation = strategy;
+    }
+
+    /// Sets whether the bounds are strict, permissive by default.
+    ///
+    /// With permissive bounds, a lower bound above the upper bound integrates in the reverse direction, so the result is negated, as in the usual convention `∫[b, a] = -∫[a, b]`.
+    /// With strict bounds, it is taken as a bug of the caller instead: `check_bounds`, and every method returning a `Result`, e.g. `adaptive_simpson`, return `IntegrationError::ReversedBounds`.
+    /// The methods returning a bare value, e.g. `riemann_integration`, `simpson_integration_one_third` or `monte_carlo_integration`, cannot report an error, so they keep integrating in the reverse direction:
+    /// call them through `try_integrate`, which checks the bounds first.
+    ///
+    /// # Arguments
+    ///
+    /// * `strict` - Whether a lower bound above the upper bound is an error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::{Integral, IntegrationError};
+    ///
+    /// let mut integral = Integral::new(Box::new(|x: f64| x.exp()), 1.0, 0.0, 100);
+    /// assert!(integral.check_bounds().is_ok());
+    /// integral.with_strict_bounds(true);
+    /// assert!(matches!(integral.check_bounds(), Err(IntegrationError::ReversedBounds { .. })));
+    /// ```
+    pub fn with_strict_bounds(&mut self, strict: bool) {
+        self.strict_bounds = strict;
+    }
+
+    /// Checks the order of the bounds, which is only an error with strict bounds, as described in `with_strict_bounds`.
+    ///
+    /// # Returns
+    ///
+    /// `IntegrationError::ReversedBounds` if the bounds are strict and the lower bound is above the upper bound.
+    pub fn check_bounds(&self) -> Result<(), IntegrationError> {
+        if self.strict_bounds && self.lower_bound > self.upper_bound {
+            return Err(IntegrationError::ReversedBounds {
+                lower_bound: self.lower_bound,
+                upper_bound: self.upper_bound,
+            });
         }
+        Ok(())
+    }
+
+    /// Calls a method returning a bare value after checking the order of the bounds, so that strict bounds are enforced by every method.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The integration to perform, e.g. `Integral::riemann_integration`, or a closure calling a method with arguments.
+    ///
+    /// # Returns
+    ///
+    /// The result of `method`, or `IntegrationError::ReversedBounds` without calling it if the bounds are strict and reversed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::{Integral, IntegrationError};
+    ///
+    /// let mut integral = Integral::new(Box::new(|x: f64| x * x), 3.0, 0.0, 1e4 as u64);
+    /// assert!((integral.try_integrate(Integral::simpson_integration_one_third).unwrap() + 9.0).abs() < 1e-9);
+    /// integral.with_strict_bounds(true);
+    /// assert!(matches!(
+    ///     integral.try_integrate(|integral| integral.monte_carlo_integration(1000, 42)),
+    ///     Err(IntegrationError::ReversedBounds { .. })
+    /// ));
+    /// ```
+    pub fn try_integrate<T>(
+        &mut self,
+        method: impl FnOnce(&mut Self) -> T,
+    ) -> Result<T, IntegrationError> {
+        self.check_bounds()?;
+        Ok(method(self))
     }
 
     /// Performs numerical integration using the Riemann sum method.
@@ -54,74 +585,3031 @@ impl Integral {
     /// let result = Integral::new(Box::new(|x| x * x), 0.0, 3.0, 1e6 as u64).riemann_integration();
     /// println!("The integral is approximately: {}", result);
     /// ```
-    pub fn riemann_integration(&mut self) -> f64 {
-        let width = (self.upper_bound - self.lower_bound) / self.num_intervals as f64; // Width of each slice of the interval
-
-        for i in 0..self.num_intervals {
-            let x_coordinate = self.lower_bound + i as f64 * width;
-            self.result += (self.function)(x_coordinate) * width; // Infinitesimal area to be accumulated
-        }
-
-        self.result
+    pub fn riemann_integration(&mut self) -> S {
+        self.last_method = Some("riemann_integration");
+        self.result = Self::riemann_sum(
+            self.summation,
+            self.lower_bound,
+            self.upper_bound,
+            self.num_intervals,
+            |x| (self.function)(x),
+        );
+        self.result.clone()
     }
 
-    /// Performs numerical integration using Simpson's 1/3 rule.
-    /// Simpson's 1/3 rule approximates the integrand function with the a quadratic interpolant.
-    pub fn simpson_integration_one_third(&mut self) -> f64 {
-        let width = (self.upper_bound - self.lower_bound) / self.num_intervals as f64; // Width of each slice of the interval
+    /// Sums the areas of the `num_intervals` slices of `[lower_bound, upper_bound]`, sampling `integrand` at the left end of each slice.
+    fn riemann_sum<T: Summable, F: Fn(f64) -> T>(
+        summation: SummationStrategy,
+        lower_bound: f64,
+        upper_bound: f64,
+        num_intervals: u64,
+        integrand: F,
+    ) -> T {
+        if is_zero_width(lower_bound, upper_bound) {
+            return T::zero(); // Zero-width interval, without evaluating the integrand
+        }
 
-        for i in 0..self.num_intervals {
-            let x_coordinate = self.lower_bound + i as f64 * width;
-            let x_next = x_coordinate + width;
-            let x_mid = (x_coordinate + x_next) / 2.0;
+        let width = (upper_bound - lower_bound) / num_intervals as f64; // Width of each slice of the interval
+        let mut sum = Accumulator::new(summation);
 
-            // Simpson's rule: f(a) + 4f(m) + f(b)
-            self.result += (self.function)(x_coordinate)
-                + 4.0 * (self.function)(x_mid)
-                + (self.function)(x_next);
+        for i in 0..num_intervals {
+            let x_coordinate = lower_bound + i as f64 * width;
+            sum.add(integrand(x_coordinate).scale(width)); // Infinitesimal area to be accumulated
         }
 
-        self.result *= width / 6.0; // Last step can be factored out of the integral, because it is constant
-        self.result
+        sum.total()
     }
 }
 
-// ---- Tests ---- //
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::utils::colours::*;
-
-    /// Helper function to test integration with common logic.
-    fn test_integration(
-        function: fn(f64) -> f64,
-        lower_bound: f64,
-        upper_bound: f64,
-        num_intervals: u32,
-        expected: f64,
-        error_tolerance: f64,
-    ) {
-        let mut integral = Integral::new(
-            Box::new(function),
-            lower_bound,
-            upper_bound,
-            num_intervals as u64,
+impl Integral {
+    /// Computes the weighted moment of order `k` of the function, i.e. the integral of `x^k * f(x)` over the interval.
+    ///
+    /// For `k = 0` this is the plain integral of the function. If the function is a probability density, the moment of order `k` is the `k`-th raw statistical moment.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The order of the moment.
+    ///
+    /// # Returns
+    ///
+    /// The approximate value of the moment, computed with the Riemann sum method.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// // Mean of the uniform distribution over [0, 1]
+    /// let mean = Integral::new(Box::new(|_| 1.0), 0.0, 1.0, 1e6 as u64).weighted_moment(1);
+    /// assert!((mean - 0.5).abs() < 1e-5);
+    /// ```
+    pub fn weighted_moment(&mut self, k: u32) -> f64 {
+        self.last_method = Some("weighted_moment");
+        self.result = Self::riemann_sum(
+            self.summation,
+            self.lower_bound,
+            self.upper_bound,
+            self.num_intervals,
+            |x| x.powi(k as i32) * (self.function)(x),
         );
-        let results_vec = vec![
-            ("Riemann", integral.riemann_integration()),
-            ("Simpson's 1/3", integral.simpson_integration_one_third()),
-        ];
+        self.result
+    }
 
-        for (method_name, result) in results_vec {
-            let delta = (result - expected).abs();
-            println!(
-                "Method: {}\n  {}Result{}:    {}\n  {}Expected{}:  {}\n  {}Tolerance{}: {}\n  {}Delta{}:     {}",
-                method_name,
-                CYAN,
-                RESET,
-                result,
-                YELLOW,
+    /// Performs numerical integration after a change of variables `x = x(u)`.
+    ///
+    /// The integral of `f(x)` over `[lower_bound, upper_bound]` is computed as the integral of `f(x(u)) * dx/du` over `u_bounds`.
+    /// A suitable substitution concentrates the sampling points where they are needed, e.g. `x = u^2` clusters them near an integrable singularity at `x = 0`.
+    /// The `u` interval is sampled at the midpoint of each slice, so the endpoints are never evaluated.
+    ///
+    /// # Arguments
+    ///
+    /// * `u_of_x` - The inverse transformation, which must map `lower_bound` and `upper_bound` onto `u_bounds`. It is only used to check the consistency of the arguments in debug builds.
+    /// * `x_of_u` - The transformation from the new variable `u` to `x`.
+    /// * `dx_du` - The derivative of `x_of_u`.
+    /// * `u_bounds` - The bounds of the integral in the new variable `u`.
+    ///
+    /// # Returns
+    ///
+    /// The approximate value of the integral.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::{Function, Integral};
+    ///
+    /// // Integral of 1 / sqrt(x) over [0, 1], singular at x = 0, with the substitution x = u^2
+    /// let u_of_x: Function = Box::new(|x| x.sqrt());
+    /// let x_of_u: Function = Box::new(|u| u * u);
+    /// let dx_du: Function = Box::new(|u| 2.0 * u);
+    /// let result = Integral::new(Box::new(|x| 1.0 / x.sqrt()), 0.0, 1.0, 1e4 as u64)
+    ///     .integrate_with_substitution(&u_of_x, &x_of_u, &dx_du, (0.0, 1.0));
+    /// assert!((result - 2.0).abs() < 1e-9);
+    /// ```
+    pub fn integrate_with_substitution(
+        &mut self,
+        u_of_x: &Function,
+        x_of_u: &Function,
+        dx_du: &Function,
+        u_bounds: (f64, f64),
+    ) -> f64 {
+        self.last_method = Some("integrate_with_substitution");
+        debug_assert!(
+            approx_eq(u_of_x(self.lower_bound), u_bounds.0, CONSISTENCY_TOLERANCE)
+                && approx_eq(u_of_x(self.upper_bound), u_bounds.1, CONSISTENCY_TOLERANCE),
+            "The substitution does not map the integral bounds onto `u_bounds`"
+        );
+
+        self.result = Self::midpoint_sum(
+            self.summation,
+            u_bounds.0,
+            u_bounds.1,
+            self.num_intervals,
+            |u| (self.function)(x_of_u(u)) * dx_du(u),
+        );
+        self.result
+    }
+
+    /// Performs numerical integration using the midpoint rule, which samples the function at the middle of each slice.
+    ///
+    /// The function is never evaluated at the bounds, so it may be singular there, as long as it is integrable:
+    /// e.g. `ln(x)` or `1 / sqrt(x)` over `[0, 1]`, where the Riemann sum and Simpson's rule would evaluate the function at `x = 0` and return an infinite or `NaN` result.
+    /// The convergence is slower near an endpoint singularity than for a smooth function, so use many intervals.
+    ///
+    /// # Returns
+    ///
+    /// The approximate value of the integral.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let result = Integral::new(Box::new(|x: f64| 1.0 / x.sqrt()), 0.0, 1.0, 1e6 as u64).midpoint_integration();
+    /// assert!((result - 2.0).abs() < 1e-2);
+    /// ```
+    pub fn midpoint_integration(&mut self) -> f64 {
+        self.last_method = Some("midpoint_integration");
+        self.result = Self::midpoint_sum(
+            self.summation,
+            self.lower_bound,
+            self.upper_bound,
+            self.num_intervals,
+            |x| (self.function)(x),
+        );
+        self.result
+    }
+
+    /// Performs numerical integration using Simpson's 1/3 rule.
+    /// Simpson's 1/3 rule approximates the integrand function with the a quadratic interpolant.
+    ///
+    /// Each slice is integrated on its own, with the quadratic through its ends and its middle point,
+    /// so `num_intervals` may be odd: unlike the textbook composite rule, which pairs up slices, there is no last unpaired panel to fall back to the trapezoidal rule for.
+    /// For sampled data, where the middle points are not available, `simpson_irregular` pairs up the slices and does fall back to the trapezoidal rule on the last one.
+    pub fn simpson_integration_one_third(&mut self) -> f64 {
+        self.last_method = Some("simpson_integration_one_third");
+        self.result = Self::simpson_sum(
+            self.summation,
+            self.lower_bound,
+            self.upper_bound,
+            self.num_intervals,
+            |x| (self.function)(x),
+        );
+        self.result
+    }
+
+    /// Performs numerical integration using the composite trapezoidal rule.
+    /// The trapezoidal rule approximates the integrand function with a straight line on each slice.
+    ///
+    /// The areas of the slices are summed in the form `h * (f0 / 2 + f1 + ... + f(n-1) + fn / 2)`, so each point shared by two slices is evaluated once:
+    /// `num_intervals + 1` evaluations in total, about half as many as integrating the slices one by one.
+    ///
+    /// # Returns
+    ///
+    /// The approximate value of the integral.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let result = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 1.0, 1e4 as u64).trapezoidal_composite();
+    /// assert!((result - (std::f64::consts::E - 1.0)).abs() < 1e-8);
+    /// ```
+    pub fn trapezoidal_composite(&mut self) -> f64 {
+        self.last_method = Some("trapezoidal_composite");
+        self.result = Self::trapezoidal_sum(
+            self.summation,
+            self.lower_bound,
+            self.upper_bound,
+            self.num_intervals,
+            |x| (self.function)(x),
+        );
+        self.result
+    }
+
+    /// Performs numerical integration using the Riemann sum method, reporting the progress to a monitor which can stop it early.
+    ///
+    /// After each slice, `monitor` is called with the index of the slice and the running sum, i.e. the integral from the lower bound to the end of the slice.
+    /// The slices are added with the summation strategy of the integral, so the whole sum is the one of `riemann_integration`.
+    /// If it returns `false`, the integration stops there, e.g. because the user asked to cancel it, or because an external convergence rule is met.
+    ///
+    /// # Arguments
+    ///
+    /// * `monitor` - The callback receiving the index of the slice just summed and the running sum, and returning whether to go on.
+    ///
+    /// # Returns
+    ///
+    /// The running sum when the integration stopped: the whole integral if the monitor never stopped it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// // Stop once the running sum exceeds 1
+    /// let mut integral = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 2.0, 1e4 as u64);
+    /// let partial = integral.integrate_with_monitor(&mut |_, sum| sum <= 1.0);
+    /// assert!(partial > 1.0 && partial < 1.001);
+    /// ```
+    pub fn integrate_with_monitor(&mut self, monitor: &mut dyn FnMut(u64, f64) -> bool) -> f64 {
+        self.last_method = Some("integrate_with_monitor");
+        if is_zero_width(self.lower_bound, self.upper_bound) {
+            self.result = 0.0; // Zero-width interval, without evaluating the function
+            return self.result;
+        }
+
+        let width = (self.upper_bound - self.lower_bound) / self.num_intervals as f64; // Width of each slice of the interval
+        let mut sum = Accumulator::new(self.summation);
+
+        for i in 0..self.num_intervals {
+            sum.add((self.function)(self.lower_bound + i as f64 * width) * width);
+            if !monitor(i, sum.total()) {
+                break;
+            }
+        }
+
+        self.result = sum.total();
+        self.result
+    }
+
+    /// Performs numerical integration using the Riemann sum method, stopping early once the running sum has stabilised.
+    ///
+    /// The integration stops as soon as the last `window` slices changed the running sum by less than `rel_tolerance` relatively, and the running sum is returned.
+    /// This saves most of the work for an integrand decaying to zero over a long interval, e.g. `exp(-x)` over `[0, 100]`, whose tail adds nothing measurable.
+    /// It is only valid for such decaying integrands: the slices after the stop are assumed to be negligible, so a function which is small for a while and then grows again, e.g. with a late peak, is silently truncated.
+    /// The stop is checked with `integrate_with_monitor`.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - The number of slices over which the change of the running sum is measured, at least 1.
+    /// * `rel_tolerance` - The relative change of the running sum over the window below which the integration stops.
+    ///
+    /// # Returns
+    ///
+    /// The running sum when the integration stopped: the whole Riemann sum if the running sum never stabilised.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// // Stops around x = 35, where the tail is below 1e-15
+    /// let result = Integral::new(Box::new(|x: f64| (-x).exp()), 0.0, 1000.0, 1e7 as u64).riemann_early_exit(100, 1e-15);
+    /// assert!((result - 1.0).abs() < 1e-3);
+    /// ```
+    pub fn riemann_early_exit(&mut self, window: usize, rel_tolerance: f64) -> f64 {
+        let window = window.max(1);
+        let mut recent = VecDeque::with_capacity(window); // Running sums before the slices of the window
+        recent.push_back(0.0);
+
+        let result = self.integrate_with_monitor(&mut |_, sum| {
+            if recent.len() > window {
+                let before_window = recent.pop_front().unwrap_or(0.0);
+                if (sum - before_window).abs() < rel_tolerance * sum.abs() {
+                    return false;
+                }
+            }
+            recent.push_back(sum);
+            true
+        });
+        self.last_method = Some("riemann_early_exit");
+        result
+    }
+
+    /// Performs numerical integration using the Riemann sum method, evaluating `f` over batches of points instead of one point at a time.
+    ///
+    /// The function of the integral is ignored: `f` is the batched counterpart of it, e.g. a vectorised implementation of the same formula.
+    /// The points, the order of the sum and the summation strategy are the ones of `riemann_integration`, so the result is the same, bit for bit, when `f` agrees with the function.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The function to integrate, evaluated `BATCH_SIZE` points at a time.
+    ///
+    /// # Returns
+    ///
+    /// The approximate value of the integral.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let mut integral = Integral::new(Box::new(|x| x * x), 0.0, 3.0, 1e4 as u64);
+    /// let result = integral.riemann_integration_batched(&|x: f64| x * x);
+    /// assert_eq!(result, integral.riemann_integration());
+    /// ```
+    pub fn riemann_integration_batched(&mut self, f: &dyn BatchFunction) -> f64 {
+        self.last_method = Some("riemann_integration_batched");
+        if is_zero_width(self.lower_bound, self.upper_bound) {
+            self.result = 0.0; // Zero-width interval, without evaluating the function
+            return self.result;
+        }
+
+        let width = (self.upper_bound - self.lower_bound) / self.num_intervals as f64; // Width of each slice of the interval
+        let mut xs = Vec::with_capacity(BATCH_SIZE);
+        let mut values = vec![0.0; BATCH_SIZE];
+        let mut sum = Accumulator::new(self.summation);
+
+        let mut first = 0;
+        while first < self.num_intervals {
+            let last = (first + BATCH_SIZE as u64).min(self.num_intervals);
+            xs.clear();
+            xs.extend((first..last).map(|i| self.lower_bound + i as f64 * width));
+            let values = &mut values[..xs.len()];
+            f.eval_batch(&xs, values);
+
+            for value in values.iter() {
+                sum.add(value * width);
+            }
+            first = last;
+        }
+
+        self.result = sum.total();
+        self.result
+    }
+
+    /// Performs numerical integration using Simpson's 1/3 rule on slices of varying width, concentrated where a density is large.
+    ///
+    /// The slices are placed so that each holds the same share of the integral of the density: the bounds of the slices are the points where the cumulative density reaches `k / num_intervals` of its total.
+    /// A density peaked where the function varies fast spends the intervals there, without specifying the slices by hand.
+    /// The error of Simpson's rule on a slice grows as its width to the fifth power times the fourth derivative of the function, so the ideal density is about `|f''''(x)|^(1/5)`:
+    /// in practice, a softened version of the shape of the function, e.g. its square root for a peak, since the function itself would starve the rest of the interval.
+    /// The cumulative density is tabulated with the trapezoidal rule over `DENSITY_TABLE_SIZE` cells and inverted by linear interpolation, so features of the density narrower than a cell are smoothed out:
+    /// this only affects where the slices are placed, not the validity of the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `density` - The density of the slices, positive over the interval. It does not need to be normalised.
+    ///
+    /// # Returns
+    ///
+    /// The approximate value of the integral, or an error if the density is not positive, or not finite, at a point of its table.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::{Function, Integral};
+    ///
+    /// // More slices near the steep end of the exponential: the fifth root of its fourth derivative is exp(2x), up to a constant factor
+    /// let density: Function = Box::new(|x: f64| (2.0 * x).exp());
+    /// let result = Integral::new(Box::new(|x: f64| (10.0 * x).exp()), 0.0, 1.0, 20).integrate_density_adaptive(&density).unwrap();
+    /// assert!((result - (10f64.exp() - 1.0) / 10.0).abs() < 1e-2);
+    /// ```
+    pub fn integrate_density_adaptive(
+        &mut self,
+        density: &Function,
+    ) -> Result<f64, IntegrationError> {
+        self.last_method = Some("integrate_density_adaptive");
+        self.check_bounds()?;
+        let (a, b, n) = (self.lower_bound, self.upper_bound, self.num_intervals);
+        if is_zero_width(a, b) {
+            self.result = 0.0;
+            return Ok(self.result);
+        }
+
+        // Table of the cumulative density at the points `a + i * cell_width`
+        let cell_width = (b - a) / DENSITY_TABLE_SIZE as f64;
+        let mut cumulative = Vec::with_capacity(DENSITY_TABLE_SIZE + 1);
+        let mut previous = 0.0;
+        for i in 0..=DENSITY_TABLE_SIZE {
+            let x_coordinate = a + i as f64 * cell_width;
+            let value = density(x_coordinate);
+            if !(value > 0.0 && value.is_finite()) {
+                return Err(IntegrationError::NonPositiveDensity(x_coordinate));
+            }
+            let area = if i == 0 {
+                0.0
+            } else {
+                (previous + value) / 2.0 * cell_width
+            };
+            cumulative.push(cumulative.last().copied().unwrap_or(0.0) + area);
+            previous = value;
+        }
+        let total = cumulative[DENSITY_TABLE_SIZE];
+
+        // Walk the table and the slices together, since both are sorted
+        let mut cell = 0;
+        let mut slice_start = a;
+        let mut sum = 0.0;
+        for k in 1..=n {
+            let slice_end = if k == n {
+                b
+            } else {
+                let target = total * k as f64 / n as f64;
+                while cell < DENSITY_TABLE_SIZE - 1 && cumulative[cell + 1] < target {
+                    cell += 1;
+                }
+                let fraction =
+                    (target - cumulative[cell]) / (cumulative[cell + 1] - cumulative[cell]);
+                a + (cell as f64 + fraction.clamp(0.0, 1.0)) * cell_width
+            };
+            sum += Self::simpson_sum(self.summation, slice_start, slice_end, 1, |x| {
+                (self.function)(x)
+            });
+            slice_start = slice_end;
+        }
+
+        self.result = sum;
+        Ok(self.result)
+    }
+
+    /// Performs numerical integration using Simpson's 1/3 rule, ignoring the orientation of the interval.
+    ///
+    /// The function is integrated from the smaller to the larger bound, whatever their order, and the magnitude of the result is returned:
+    /// this is for users who only care about the size of the integral, e.g. an area under a curve known to keep its sign.
+    /// The other methods keep the signed convention, where swapping the bounds flips the sign of the result.
+    ///
+    /// # Returns
+    ///
+    /// The absolute value of the approximate integral.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let result = Integral::new(Box::new(|x: f64| x * x), 3.0, 0.0, 10).integrate_absolute();
+    /// assert!((result - 9.0).abs() < 1e-12);
+    /// ```
+    pub fn integrate_absolute(&mut self) -> f64 {
+        self.last_method = Some("integrate_absolute");
+        let lower_bound = self.lower_bound.min(self.upper_bound);
+        let upper_bound = self.lower_bound.max(self.upper_bound);
+        self.result = Self::simpson_sum(
+            self.summation,
+            lower_bound,
+            upper_bound,
+            self.num_intervals,
+            |x| (self.function)(x),
+        )
+        .abs();
+        self.result
+    }
+
+    /// Performs numerical integration using Simpson's 1/3 rule, clamping the values of the function to `[min, max]`.
+    ///
+    /// This computes the integral of `clamp(f(x), min, max)`, not of the function: a different quantity, e.g. the output of a model saturating at `max`, or a signal with its spurious spikes cut off.
+    /// The clamped function has kinks where it meets the limits, so the error of Simpson's rule only shrinks as `h^2` unless the kinks fall on the ends of the slices.
+    /// If `min` is greater than `max`, the limits are swapped.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The lower limit of the values of the function.
+    /// * `max` - The upper limit of the values of the function.
+    ///
+    /// # Returns
+    ///
+    /// The approximate integral of the clamped function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// // Half-wave rectified sine
+    /// let result = Integral::new(Box::new(|x: f64| x.sin()), 0.0, 2.0 * std::f64::consts::PI, 100).integrate_clamped(0.0, f64::INFINITY);
+    /// assert!((result - 2.0).abs() < 1e-6);
+    /// ```
+    pub fn integrate_clamped(&mut self, min: f64, max: f64) -> f64 {
+        self.last_method = Some("integrate_clamped");
+        let (min, max) = (min.min(max), min.max(max));
+        self.result = Self::simpson_sum(
+            self.summation,
+            self.lower_bound,
+            self.upper_bound,
+            self.num_intervals,
+            |x| (self.function)(x).clamp(min, max),
+        );
+        self.result
+    }
+
+    /// Performs numerical integration using Simpson's 1/3 rule, looking up the values of the function in a cache owned by the caller.
+    ///
+    /// This is meant for integrands backed by expensive lookups, e.g. files or network services: the values computed are stored in `cache`,
+    /// so repeated integrations over the same points, e.g. with the same bounds and number of intervals, reuse them instead of calling the function again.
+    /// The caller decides how long the cache lives, and can share it across several integrals of the same function.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - The values of the function already computed, keyed on the bit pattern of `x` (`x.to_bits()`).
+    ///   It must only contain values of this integral's function.
+    ///
+    /// # Returns
+    ///
+    /// The approximate value of the integral.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let mut cache = HashMap::new();
+    /// let mut integral = Integral::new(Box::new(|x: f64| x * x), 0.0, 3.0, 10);
+    /// let result = integral.integrate_with_shared_cache(&mut cache);
+    /// assert!((result - 9.0).abs() < 1e-12);
+    /// assert!(cache.len() >= 21);
+    /// ```
+    pub fn integrate_with_shared_cache(&mut self, cache: &mut HashMap<u64, f64>) -> f64 {
+        self.last_method = Some("integrate_with_shared_cache");
+        let cache = RefCell::new(cache);
+        self.result = Self::simpson_sum(
+            self.summation,
+            self.lower_bound,
+            self.upper_bound,
+            self.num_intervals,
+            |x| {
+                *cache
+                    .borrow_mut()
+                    .entry(x.to_bits())
+                    .or_insert_with(|| (self.function)(x))
+            },
+        );
+        self.result
+    }
+
+    /// Performs numerical integration using Simpson's 1/3 rule, collecting the sampled values of the function.
+    ///
+    /// This gives both the result and the data to plot the function, without evaluating it twice.
+    /// The function is sampled at the ends and at the middle of each slice, so `2 * num_intervals + 1` samples are returned:
+    /// each sample takes 16 bytes, e.g. 32 MB for a million intervals, so keep the number of intervals moderate.
+    ///
+    /// # Returns
+    ///
+    /// The approximate value of the integral, and the `(x, f(x))` pairs sampled, sorted by `x` from the lower to the upper bound.
+    /// No samples are returned for an interval of zero width.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let (result, samples) = Integral::new(Box::new(|x: f64| x * x), 0.0, 3.0, 10).integrate_and_collect();
+    /// assert!((result - 9.0).abs() < 1e-12);
+    /// assert_eq!(samples.len(), 21);
+    /// ```
+    pub fn integrate_and_collect(&mut self) -> (f64, Vec<(f64, f64)>) {
+        self.last_method = Some("integrate_and_collect");
+        if is_zero_width(self.lower_bound, self.upper_bound) {
+            self.result = 0.0;
+            return (self.result, vec![]);
+        }
+
+        let num_samples = 2 * self.num_intervals + 1;
+        let half_width = (self.upper_bound - self.lower_bound) / (num_samples - 1) as f64;
+        let samples: Vec<(f64, f64)> = (0..num_samples)
+            .map(|i| {
+                let x_coordinate = if i == num_samples - 1 {
+                    self.upper_bound // Avoid rounding errors on the last sample
+                } else {
+                    self.lower_bound + i as f64 * half_width
+                };
+                (x_coordinate, (self.function)(x_coordinate))
+            })
+            .collect();
+
+        // Simpson's weights: 1 at the bounds, 4 at the middle of each slice and 2 at the shared ends of the slices
+        let mut sum = Accumulator::new(self.summation);
+        for (i, &(_, y)) in samples.iter().enumerate() {
+            sum.add(match i {
+                0 => y,
+                _ if i as u64 == num_samples - 1 => y,
+                _ if i % 2 == 1 => 4.0 * y,
+                _ => 2.0 * y,
+            });
+        }
+
+        self.result = sum.total() * half_width / 3.0;
+        (self.result, samples)
+    }
+
+    /// Estimates the error of the Riemann sum with the current number of intervals, when the exact value of the integral is not known.
+    ///
+    /// A Simpson's 1/3 estimate with `reference_intervals` intervals stands in for the exact value, so `reference_intervals` should be much larger than `num_intervals`.
+    ///
+    /// # Arguments
+    ///
+    /// * `reference_intervals` - The number of intervals of the reference estimate.
+    ///
+    /// # Returns
+    ///
+    /// The absolute difference between the Riemann sum and the reference estimate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let error = Integral::new(Box::new(|x| x.exp()), 0.0, 1.0, 1e3 as u64).reference_error(1e5 as u64);
+    /// assert!(error < 1e-3);
+    /// ```
+    pub fn reference_error(&mut self, reference_intervals: u64) -> f64 {
+        let reference = Self::simpson_sum(
+            self.summation,
+            self.lower_bound,
+            self.upper_bound,
+            reference_intervals.max(1),
+            |x| (self.function)(x),
+        );
+
+        let error = (self.riemann_integration() - reference).abs();
+        self.last_method = Some("reference_error");
+        error
+    }
+
+    /// Traces the convergence of Simpson's 1/3 rule as the number of intervals is doubled, when the exact value of the integral is not known.
+    ///
+    /// Starting from the current number of intervals, the number is doubled `levels` times, and each estimate is compared with the previous one.
+    /// The difference between two levels is a proxy for the error of the coarser one, so plotting the trace shows the convergence of the method.
+    /// The finest level uses `num_intervals * 2^levels` intervals, so keep the number of intervals small.
+    ///
+    /// # Arguments
+    ///
+    /// * `levels` - The number of doublings.
+    ///
+    /// # Returns
+    ///
+    /// The absolute differences between each level and the previous one, from the coarsest to the finest. The result of the struct is the finest estimate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let trace = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 1.0, 4).refinement_error_trace(5);
+    /// assert_eq!(trace.len(), 5);
+    /// assert!(trace[4] < 1e-9);
+    /// ```
+    pub fn refinement_error_trace(&mut self, levels: usize) -> Vec<f64> {
+        let mut num_intervals = self.num_intervals;
+        let mut previous = self.simpson_integration_one_third();
+        let mut trace = Vec::with_capacity(levels);
+
+        for _ in 0..levels {
+            num_intervals *= 2;
+            let estimate = Self::simpson_sum(
+                self.summation,
+                self.lower_bound,
+                self.upper_bound,
+                num_intervals,
+                |x| (self.function)(x),
+            );
+            trace.push((estimate - previous).abs());
+            previous = estimate;
+        }
+
+        self.result = previous;
+        self.last_method = Some("refinement_error_trace");
+        trace
+    }
+
+    /// Performs numerical integration using the adaptive Simpson's method, and reports the grid it used.
+    ///
+    /// The interval is recursively halved until Simpson's 1/3 rule on each half agrees with the rule on the whole panel within the tolerance, so the grid is denser where the function is harder to integrate.
+    /// The number of intervals is not used.
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - The requested absolute accuracy of the result.
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the approximate value of the integral and the sorted endpoints of the panels of the final grid, bounds included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let (result, grid) = Integral::new(Box::new(|x| x.exp()), 0.0, 1.0, 1).adaptive_simpson_with_grid(1e-9);
+    /// assert!((result - (std::f64::consts::E - 1.0)).abs() < 1e-9);
+    /// assert_eq!(grid.first(), Some(&0.0));
+    /// assert_eq!(grid.last(), Some(&1.0));
+    /// ```
+    pub fn adaptive_simpson_with_grid(&mut self, tolerance: f64) -> (f64, Vec<f64>) {
+        self.last_method = Some("adaptive_simpson_with_grid");
+        let (result, trace) = self.run_adaptive_simpson(
+            (self.lower_bound, self.upper_bound),
+            tolerance,
+            MAX_ADAPTIVE_DEPTH,
+        );
+        self.result = result;
+
+        let mut grid = vec![self.lower_bound];
+        grid.extend(trace.panels.iter().map(|&(_, end, _)| end));
+        grid.sort_by(f64::total_cmp);
+        (self.result, grid)
+    }
+
+    /// Performs numerical integration using the adaptive Simpson's method, and reports the effort spent on each region of the interval.
+    ///
+    /// The panels are halved as in `adaptive_simpson_with_grid`, each with half the tolerance of its parent, so the deeper a panel, the harder the function was to integrate there:
+    /// a sharp feature, e.g. a cusp or a narrow peak, shows up as a cluster of deep panels around it, which helps diagnose an integrand that is slow or inaccurate to integrate.
+    /// The number of intervals is not used.
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - The requested absolute accuracy of the result.
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the approximate value of the integral and the accepted panels as `(start, end, depth)`, sorted by their start, where `depth` is the number of times the interval was halved to obtain the panel.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let (result, effort) = Integral::new(Box::new(|x: f64| x.abs().sqrt()), -1.0, 1.0, 1).adaptive_simpson_with_effort(1e-8);
+    /// assert!((result - 4.0 / 3.0).abs() < 1e-7);
+    /// let (start, end, depth) = effort.iter().max_by_key(|panel| panel.2).unwrap();
+    /// println!("Deepest panel: [{}, {}], halved {} times", start, end, depth);
+    /// ```
+    pub fn adaptive_simpson_with_effort(
+        &mut self,
+        tolerance: f64,
+    ) -> (f64, Vec<(f64, f64, usize)>) {
+        self.last_method = Some("adaptive_simpson_with_effort");
+        let (result, trace) = self.run_adaptive_simpson(
+            (self.lower_bound, self.upper_bound),
+            tolerance,
+            MAX_ADAPTIVE_DEPTH,
+        );
+        self.result = result;
+
+        let mut effort: Vec<(f64, f64, usize)> = trace
+            .panels
+            .iter()
+            .map(|&(start, end, depth)| (start, end, depth as usize))
+            .collect();
+        effort.sort_by(|a, b| a.0.total_cmp(&b.0));
+        (self.result, effort)
+    }
+
+    /// Performs numerical integration using the adaptive Simpson's method, with a cap on the number of subdivisions.
+    ///
+    /// The interval is recursively halved until Simpson's 1/3 rule on each half agrees with the rule on the whole panel within the tolerance.
+    /// A panel cannot be halved more than `max_depth` times: if the cap is hit, the tolerance may not be met, and an error carrying the partial result is returned, so that the caller can decide whether to accept it.
+    /// The number of intervals is not used.
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - The requested absolute accuracy of the result.
+    /// * `max_depth` - The maximum number of times a panel can be halved.
+    ///
+    /// # Returns
+    ///
+    /// The approximate value of the integral, or `IntegrationError::MaxDepthReached` with the partial result if the cap was hit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::{Integral, IntegrationError};
+    ///
+    /// let mut integral = Integral::new(Box::new(|x: f64| x.sqrt()), 0.0, 1.0, 1);
+    /// match integral.adaptive_simpson(1e-9, 5) {
+    ///     Ok(result) => println!("The integral is approximately: {}", result),
+    ///     Err(IntegrationError::MaxDepthReached(partial)) => println!("Inaccurate result: {}", partial),
+    ///     Err(e) => println!("Error: {}", e),
+    /// }
+    /// ```
+    pub fn adaptive_simpson(
+        &mut self,
+        tolerance: f64,
+        max_depth: u32,
+    ) -> Result<f64, IntegrationError> {
+        self.last_method = Some("adaptive_simpson");
+        self.check_bounds()?;
+        let (result, trace) =
+            self.run_adaptive_simpson((self.lower_bound, self.upper_bound), tolerance, max_depth);
+        self.result = result;
+
+        if trace.max_depth_reached {
+            Err(IntegrationError::MaxDepthReached(self.result))
+        } else {
+            Ok(self.result)
+        }
+    }
+
+    /// Performs numerical integration using Simpson's 1/3 rule on panels subdivided where the function is strongly curved.
+    ///
+    /// The second derivative `f''` is computed at the ends and the middle of each panel with `Derivative::fornberg_derivative` on a three-point stencil, i.e. `(f(x + h) - 2 f(x) + f(x - h)) / h^2`.
+    /// The error of Simpson's rule on a panel of width `w` is about `w^5 |f''''| / 2880`, and the second difference of these curvatures gives `f''''`, so the error is about `w^3 |f''(a) - 2 f''(m) + f''(b)| / 720`:
+    /// a panel is accepted once it is within its share of the tolerance, proportional to its width, otherwise it is halved.
+    /// Unlike `adaptive_simpson`, which compares the estimates on the whole panel and on its halves, the decision rests on the curvature, which causes the error,
+    /// so the panels are small where the curvature is large and changes quickly, e.g. at a rounded corner, and large where the function is nearly straight.
+    /// The panels start as the slices of the uniform grid with the number of intervals of the integral, which must be fine enough for the samples of `|f''|` to see every sharp feature:
+    /// a narrow peak between the points of the starting grid can be missed altogether.
+    /// A panel is also accepted after `MAX_ADAPTIVE_DEPTH` halvings, e.g. at a discontinuity.
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - The requested absolute accuracy of the result.
+    /// * `h` - The increment of the second differences. The roundoff error grows as `eps / h^2`, so it should be larger than for a first derivative, e.g. `1e-4`.
+    ///
+    /// # Returns
+    ///
+    /// The approximate value of the integral.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let result = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 1.0, 4).integrate_curvature_adaptive(1e-8, 1e-4);
+    /// assert!((result - (std::f64::consts::E - 1.0)).abs() < 1e-8);
+    /// ```
+    pub fn integrate_curvature_adaptive(&mut self, tolerance: f64, h: f64) -> f64 {
+        self.last_method = Some("integrate_curvature_adaptive");
+        let (a, b) = (self.lower_bound, self.upper_bound);
+        if is_zero_width(a, b) {
+            self.result = 0.0;
+            return self.result;
+        }
+
+        // The function is lent to the derivative for the duration of the integration
+        let function = std::mem::replace(&mut self.function, Box::new(|_| f64::NAN));
+        let mut derivative = Derivative::new(function, a, h);
+
+        let n = self.num_intervals;
+        let grid_width = (b - a) / n as f64;
+        // Panels left to integrate, the leftmost last
+        let mut panels: Vec<(f64, f64, u32)> = (0..n)
+            .rev()
+            .map(|i| {
+                let end = if i == n - 1 {
+                    b
+                } else {
+                    a + (i + 1) as f64 * grid_width
+                };
+                (a + i as f64 * grid_width, end, 0)
+            })
+            .collect();
+
+        let mut sum = 0.0;
+        while let Some((start, end, depth)) = panels.pop() {
+            let mid = (start + end) / 2.0;
+            let width = end - start;
+            let [c_start, c_mid, c_end] = [start, mid, end].map(|x| {
+                derivative.x_coordinate = x;
+                derivative.fornberg_derivative(2, 3)
+            });
+
+            // Simpson's error w^5 |f''''| / 2880, with f'''' from the second difference of the curvature
+            let error_bound = width.abs().powi(3) * (c_start - 2.0 * c_mid + c_end).abs() / 720.0;
+            if error_bound <= tolerance.abs() * (width / (b - a)) || depth >= MAX_ADAPTIVE_DEPTH {
+                let f = &derivative.function;
+                sum += width / 6.0 * (f(start) + 4.0 * f(mid) + f(end));
+            } else {
+                panels.push((mid, end, depth + 1));
+                panels.push((start, mid, depth + 1));
+            }
+        }
+
+        self.function = derivative.function;
+        self.result = sum;
+        self.result
+    }
+
+    /// Performs numerical integration using Gauss-Legendre rules of increasing order, until two successive estimates agree.
+    ///
+    /// The Gauss-Legendre rule with `n` nodes integrates exactly the polynomials of degree up to `2n - 1`, so a smooth function usually converges with a handful of nodes.
+    /// Rules with 2, 3, 4, ... nodes are applied to the whole interval, until the estimate differs from the previous one by less than `tolerance`, or `MAX_GAUSS_ORDER` nodes are reached.
+    /// The number of intervals is not used.
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - The largest accepted difference between successive estimates.
+    ///
+    /// # Returns
+    ///
+    /// The last estimate, and the number of nodes of the rule which gave it. The number of nodes is `MAX_GAUSS_ORDER` if the tolerance was not met.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let (result, order) = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 1.0, 1).gauss_legendre_adaptive(1e-12);
+    /// assert!((result - (std::f64::consts::E - 1.0)).abs() < 1e-12);
+    /// assert!(order < 10);
+    /// ```
+    pub fn gauss_legendre_adaptive(&mut self, tolerance: f64) -> (f64, usize) {
+        self.last_method = Some("gauss_legendre_adaptive");
+        let half_width = (self.upper_bound - self.lower_bound) / 2.0;
+        let centre = (self.lower_bound + self.upper_bound) / 2.0;
+        let estimate = |order: usize| {
+            half_width
+                * gauss_legendre_rule(order)
+                    .iter()
+                    .map(|(node, weight)| weight * (self.function)(centre + half_width * node))
+                    .sum::<f64>()
+        };
+
+        let mut previous = estimate(1);
+        let mut order = 2;
+        let mut result = estimate(order);
+        while (result - previous).abs() >= tolerance && order < MAX_GAUSS_ORDER {
+            previous = result;
+            order += 1;
+            result = estimate(order);
+        }
+
+        self.result = result;
+        (self.result, order)
+    }
+
+    /// Performs numerical integration using Simpson's 1/3 rule with Richardson extrapolation, doubling the number of intervals until the estimated error is within the tolerance.
+    ///
+    /// The error of Simpson's rule shrinks as `h^4` for a smooth function, so doubling the number of intervals divides it by 16:
+    /// the difference between two successive estimates `S(h)` and `S(h/2)` is 15 times the error of the finer one, which is estimated as `|S(h/2) - S(h)| / 15`.
+    /// Once it is within the tolerance, the extrapolated value `S(h/2) + (S(h/2) - S(h)) / 15` is returned, which is usually even more accurate.
+    /// The first estimate uses the number of intervals of the integral.
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - The requested absolute accuracy of the result.
+    /// * `max_iters` - The maximum number of times the number of intervals is doubled.
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the approximate value of the integral and the number of iterations performed,
+    /// or `ConvergenceError::MaxIterationsReached` with the partial result if the tolerance was not met within `max_iters` iterations, or before the number of intervals exceeded `MAX_NUM_INTERVALS`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let (result, iterations) = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 1.0, 4).simpson_romberg(1e-12, 20).unwrap();
+    /// assert!((result - (std::f64::consts::E - 1.0)).abs() < 1e-12);
+    /// println!("Converged in {} iterations", iterations);
+    /// ```
+    pub fn simpson_romberg(
+        &mut self,
+        tolerance: f64,
+        max_iters: usize,
+    ) -> Result<(f64, usize), ConvergenceError> {
+        self.last_method = Some("simpson_romberg");
+        self.check_bounds()?;
+        let (a, b) = (self.lower_bound, self.upper_bound);
+        let mut num_intervals = self.num_intervals;
+        let mut previous =
+            Self::simpson_sum(self.summation, a, b, num_intervals, |x| (self.function)(x));
+
+        let mut iterations = 0;
+        while iterations < max_iters && num_intervals <= MAX_NUM_INTERVALS / 2 {
+            iterations += 1;
+            num_intervals *= 2;
+            let current =
+                Self::simpson_sum(self.summation, a, b, num_intervals, |x| (self.function)(x));
+            let correction = (current - previous) / 15.0;
+            self.result = current + correction;
+            if correction.abs() <= tolerance {
+                return Ok((self.result, iterations));
+            }
+            previous = current;
+        }
+
+        self.result = previous;
+        Err(ConvergenceError::MaxIterationsReached {
+            partial: self.result,
+            iterations,
+        })
+    }
+
+    /// Performs numerical integration using Simpson's 1/3 rule, with the number of intervals planned to meet a target error.
+    ///
+    /// The error of Simpson's rule is about `C / n^4` for `n` intervals and a smooth function.
+    /// Two cheap pilot runs, with 8 and 16 intervals, estimate the coefficient `C` from their difference, which gives the smallest `n` meeting the target:
+    /// a single run at that resolution then neither wastes evaluations nor falls short, unlike guessing the number of intervals.
+    /// The planned number is doubled in safety before rounding up, as the pilot runs may be too coarse to show the asymptotic behaviour, and capped to `MAX_NUM_INTERVALS`.
+    /// The number of intervals of the integral is not used.
+    ///
+    /// # Arguments
+    ///
+    /// * `target_abs_error` - The requested absolute accuracy of the result.
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the approximate value of the integral and the number of intervals used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let (result, num_intervals) = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 1.0, 1).plan_and_integrate(1e-10);
+    /// assert!((result - (std::f64::consts::E - 1.0)).abs() < 1e-10);
+    /// println!("Planned {} intervals", num_intervals);
+    /// ```
+    pub fn plan_and_integrate(&mut self, target_abs_error: f64) -> (f64, u64) {
+        self.last_method = Some("plan_and_integrate");
+        let (a, b) = (self.lower_bound, self.upper_bound);
+        let coarse = Self::simpson_sum(self.summation, a, b, PLAN_PILOT_INTERVALS, |x| {
+            (self.function)(x)
+        });
+        let fine = Self::simpson_sum(self.summation, a, b, 2 * PLAN_PILOT_INTERVALS, |x| {
+            (self.function)(x)
+        });
+
+        // S(n) - S(2n) = C / n^4 - C / (16 n^4), with n the pilot intervals
+        let coefficient =
+            (coarse - fine).abs() * 16.0 / 15.0 * (PLAN_PILOT_INTERVALS as f64).powi(4);
+        let planned = (2.0 * coefficient / target_abs_error.abs())
+            .powf(0.25)
+            .ceil();
+        if coefficient == 0.0 || planned <= (2 * PLAN_PILOT_INTERVALS) as f64 {
+            self.result = fine; // The finer pilot run already meets the target, e.g. for a cubic
+            return (self.result, 2 * PLAN_PILOT_INTERVALS);
+        }
+
+        let num_intervals = (planned as u64).min(MAX_NUM_INTERVALS);
+        self.result =
+            Self::simpson_sum(self.summation, a, b, num_intervals, |x| (self.function)(x));
+        (self.result, num_intervals)
+    }
+
+    /// Performs numerical integration using Simpson's 1/3 rule on panels refined independently, sharing out an absolute error budget among them.
+    ///
+    /// The interval is split into `subintervals` panels of equal width, and the integral of `|f|` over each one is roughly estimated with `PLAN_PILOT_INTERVALS` intervals.
+    /// Each panel receives a share of the budget proportional to that magnitude, or an equal share if the function is zero everywhere, so that the relative accuracy is about the same everywhere.
+    /// The intervals of each panel are then doubled until the error, estimated as in `simpson_romberg`, is within its share:
+    /// the estimated errors add up to at most the budget, so the global accuracy is predictable, while the evaluations are spent only where the function needs them.
+    /// A panel stops after `BUDGET_MAX_REFINEMENTS` doublings even if its share is not met, e.g. near a singularity, so its achieved error may then exceed its share.
+    /// The number of intervals of the integral is not used.
+    ///
+    /// # Arguments
+    ///
+    /// * `total_abs_budget` - The requested absolute accuracy of the whole integral.
+    /// * `subintervals` - The number of panels, at least 1.
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the approximate value of the integral and the estimated error achieved on each panel, from the lower bound to the upper bound.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let (result, errors) = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 4.0, 1).integrate_budgeted(1e-9, 4);
+    /// assert!((result - (4f64.exp() - 1.0)).abs() < 1e-9);
+    /// assert!(errors.iter().sum::<f64>() <= 1e-9);
+    /// ```
+    pub fn integrate_budgeted(
+        &mut self,
+        total_abs_budget: f64,
+        subintervals: usize,
+    ) -> (f64, Vec<f64>) {
+        self.last_method = Some("integrate_budgeted");
+        let subintervals = subintervals.max(1);
+        let width = (self.upper_bound - self.lower_bound) / subintervals as f64;
+        let panels: Vec<(f64, f64)> = (0..subintervals)
+            .map(|k| {
+                let end = if k == subintervals - 1 {
+                    self.upper_bound
+                } else {
+                    self.lower_bound + (k + 1) as f64 * width
+                };
+                (self.lower_bound + k as f64 * width, end)
+            })
+            .collect();
+
+        let magnitudes: Vec<f64> = panels
+            .iter()
+            .map(|&(start, end)| {
+                Self::simpson_sum(self.summation, start, end, PLAN_PILOT_INTERVALS, |x| {
+                    (self.function)(x).abs()
+                })
+                .abs()
+            })
+            .collect();
+        let total_magnitude: f64 = magnitudes.iter().sum();
+
+        let mut sum = 0.0;
+        let mut errors = Vec::with_capacity(subintervals);
+        for (&(start, end), magnitude) in panels.iter().zip(magnitudes) {
+            let share = if total_magnitude > 0.0 {
+                total_abs_budget.abs() * magnitude / total_magnitude
+            } else {
+                total_abs_budget.abs() / subintervals as f64
+            };
+
+            let mut num_intervals = PLAN_PILOT_INTERVALS;
+            let mut previous = Self::simpson_sum(self.summation, start, end, num_intervals, |x| {
+                (self.function)(x)
+            });
+            let mut error = f64::INFINITY;
+            for _ in 0..BUDGET_MAX_REFINEMENTS {
+                num_intervals *= 2;
+                let current = Self::simpson_sum(self.summation, start, end, num_intervals, |x| {
+                    (self.function)(x)
+                });
+                error = (current - previous).abs() / 15.0;
+                previous = current;
+                if error <= share {
+                    break;
+                }
+            }
+
+            sum += previous;
+            errors.push(error);
+        }
+
+        self.result = sum;
+        (self.result, errors)
+    }
+
+    /// Estimates the cost of integrating the function with the current number of intervals, without performing the integration.
+    ///
+    /// The function is timed on a thousand evaluations at the middle of the interval, so the time estimate is rough: functions whose cost depends on `x` are not accounted for.
+    /// This is meant as a dry run, to confirm a long integration before committing to it.
+    ///
+    /// # Returns
+    ///
+    /// The cost estimate, which gives the number of evaluations and the estimated time of each method.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::{Integral, IntegrationMethod};
+    ///
+    /// let cost = Integral::new(Box::new(|x: f64| x.sin()), 0.0, 1.0, 1e7 as u64).estimate_cost();
+    /// assert_eq!(cost.evaluations(IntegrationMethod::Simpson), 3e7 as u64);
+    /// println!("Simpson's 1/3 rule will take about {:?}", cost.estimated_time(IntegrationMethod::Simpson));
+    /// ```
+    pub fn estimate_cost(&self) -> CostEstimate {
+        let x_mid = (self.lower_bound + self.upper_bound) / 2.0;
+        let start = Instant::now();
+        for _ in 0..COST_BENCHMARK_EVALUATIONS {
+            std::hint::black_box((self.function)(std::hint::black_box(x_mid)));
+        }
+
+        CostEstimate {
+            num_intervals: self.num_intervals,
+            time_per_evaluation: start.elapsed() / COST_BENCHMARK_EVALUATIONS,
+        }
+    }
+
+    /// Performs numerical integration using the Riemann sum method, keeping track of the smallest and largest sampled values of the function.
+    ///
+    /// The extrema are gathered during the integration, so no additional evaluation of the function is needed.
+    /// They are the extrema of the samples, so they can miss narrow features of the function between them.
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the approximate value of the integral, the minimum and the maximum sampled values of the function.
+    /// The extrema are `NaN` if the interval has zero width, as the function is not sampled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let (result, min, max) = Integral::new(Box::new(|x| x * x), 0.0, 3.0, 1e6 as u64).integrate_with_extrema();
+    /// println!("The integral is approximately {}, with values between {} and {}", result, min, max);
+    /// ```
+    pub fn integrate_with_extrema(&mut self) -> (f64, f64, f64) {
+        self.last_method = Some("integrate_with_extrema");
+        if is_zero_width(self.lower_bound, self.upper_bound) {
+            self.result = 0.0;
+            return (self.result, f64::NAN, f64::NAN); // Nothing is sampled
+        }
+
+        let width = (self.upper_bound - self.lower_bound) / self.num_intervals as f64; // Width of each slice of the interval
+        let mut sum = Accumulator::new(self.summation);
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+
+        for i in 0..self.num_intervals {
+            let x_coordinate = self.lower_bound + i as f64 * width;
+            let value = (self.function)(x_coordinate);
+            sum.add(value * width);
+            min = min.min(value);
+            max = max.max(value);
+        }
+
+        self.result = sum.total();
+        (self.result, min, max)
+    }
+
+    /// Performs numerical integration using the composite trapezoidal rule, estimating the largest slope of the function at the same time.
+    ///
+    /// The slope is estimated at each point of the grid with the central difference `(f(x + h / 2) - f(x - h / 2)) / h`, as in `Derivative::central_difference`,
+    /// and the largest magnitude is an estimate of the Lipschitz constant of the function over the interval, e.g. for an a priori bound on the error of another method.
+    /// The estimate is the largest of the sampled slopes, so it can miss a steeper feature between the points of the grid, and is a lower bound of the true constant.
+    ///
+    /// Each central difference costs two more evaluations of the function, which lie up to `h / 2` beyond the bounds at the ends of the interval:
+    /// `3 * (num_intervals + 1)` evaluations in total, three times as many as `trapezoidal_composite`.
+    ///
+    /// # Arguments
+    ///
+    /// * `h` - The increment of the central differences.
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the approximate value of the integral and the largest magnitude of the sampled slopes. The slope is `0` if the interval has zero width, as the function is not sampled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let (result, lipschitz) = Integral::new(Box::new(|x| x * x), 0.0, 3.0, 1000).integrate_with_lipschitz(1e-5);
+    /// assert!((result - 9.0).abs() < 1e-4);
+    /// assert!((lipschitz - 6.0).abs() < 1e-6);
+    /// ```
+    pub fn integrate_with_lipschitz(&mut self, h: f64) -> (f64, f64) {
+        self.last_method = Some("integrate_with_lipschitz");
+        let max_slope = std::cell::Cell::new(0.0_f64);
+        self.result = Self::trapezoidal_sum(
+            self.summation,
+            self.lower_bound,
+            self.upper_bound,
+            self.num_intervals,
+            |x| {
+                let slope = ((self.function)(x + h / 2.0) - (self.function)(x - h / 2.0)) / h;
+                max_slope.set(max_slope.get().max(slope.abs()));
+                (self.function)(x)
+            },
+        );
+        (self.result, max_slope.get())
+    }
+
+    /// Performs numerical integration using Simpson's 1/3 rule, keeping track of the region which contributes the most to the integral.
+    ///
+    /// The interval is split into `bins` regions of equal width, each integrated with its share of the intervals, and the region with the largest integral in absolute value is reported:
+    /// the last region also takes the remainder of the division of the intervals, so that all of them are used, and each region gets at least one interval.
+    /// it shows where the "mass" of the integral lies, e.g. around a peak.
+    /// Only the running sum and the best region so far are kept, so the memory used does not grow with the number of bins.
+    ///
+    /// # Arguments
+    ///
+    /// * `bins` - The number of regions, at least 1.
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the approximate value of the integral and the bounds of the dominant region, from the side of the lower bound to the side of the upper bound.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let (result, (start, end)) = Integral::new(Box::new(|x: f64| x.powi(4)), 0.0, 1.0, 1000).integrate_with_dominant_region(4);
+    /// assert!((result - 0.2).abs() < 1e-12);
+    /// assert_eq!((start, end), (0.75, 1.0));
+    /// ```
+    pub fn integrate_with_dominant_region(&mut self, bins: usize) -> (f64, (f64, f64)) {
+        self.last_method = Some("integrate_with_dominant_region");
+        let bins = bins.max(1) as u64;
+        let (intervals_per_bin, remainder) = (self.num_intervals / bins, self.num_intervals % bins);
+        let (a, b) = (self.lower_bound, self.upper_bound);
+        let bin_width = (b - a) / bins as f64;
+
+        let mut sum = 0.0;
+        let mut dominant = (f64::NEG_INFINITY, (a, b)); // Absolute contribution and bounds
+        for bin in 0..bins {
+            let start = a + bin as f64 * bin_width;
+            let (end, num_intervals) = if bin == bins - 1 {
+                (b, intervals_per_bin + remainder)
+            } else {
+                (start + bin_width, intervals_per_bin)
+            };
+            let contribution =
+                Self::simpson_sum(self.summation, start, end, num_intervals.max(1), |x| {
+                    (self.function)(x)
+                });
+            sum += contribution;
+            if contribution.abs() > dominant.0 {
+                dominant = (contribution.abs(), (start, end));
+            }
+        }
+
+        self.result = sum;
+        (self.result, dominant.1)
+    }
+
+    /// Performs numerical integration using the Monte Carlo method.
+    ///
+    /// The function is averaged over `samples` points uniformly distributed in the interval, and the average is multiplied by the width of the interval.
+    /// The error decreases as `1 / sqrt(samples)`, regardless of the smoothness of the function.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - The number of random points.
+    /// * `seed` - The seed of the pseudo-random number generator. The same seed always gives the same result.
+    ///
+    /// # Returns
+    ///
+    /// The approximate value of the integral.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let result = Integral::new(Box::new(|x| x * x), 0.0, 3.0, 1).monte_carlo_integration(1e6 as u64, 42);
+    /// assert!((result - 9.0).abs() < 0.1);
+    /// ```
+    pub fn monte_carlo_integration(&mut self, samples: u64, seed: u64) -> f64 {
+        self.last_method = Some("monte_carlo_integration");
+        if is_zero_width(self.lower_bound, self.upper_bound) {
+            self.result = 0.0;
+            return self.result;
+        }
+
+        let samples = samples.max(1);
+        let mut sum = 0.0;
+        self.for_each_random_sample(samples, seed, |value| sum += value);
+
+        self.result = (self.upper_bound - self.lower_bound) * sum / samples as f64;
+        self.result
+    }
+
+    /// Evaluates the function at `samples` points uniformly distributed in the interval, drawn from a generator seeded with `seed`, and passes each value to `visit`.
+    fn for_each_random_sample(&self, samples: u64, seed: u64, mut visit: impl FnMut(f64)) {
+        let width = self.upper_bound - self.lower_bound;
+        let mut rng = Rng::new(seed);
+
+        for _ in 0..samples {
+            visit((self.function)(self.lower_bound + rng.next_f64() * width));
+        }
+    }
+
+    /// Performs numerical integration using the Monte Carlo method with antithetic sampling.
+    ///
+    /// Each random point `x` is paired with its mirror image `a + b - x`, and the function is averaged over the pairs.
+    /// For monotonic functions the two values of a pair err in opposite directions, so the variance is lower than with independent points.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - The number of evaluations of the function, i.e. twice the number of pairs. At least two pairs are used, to estimate the standard error.
+    /// * `seed` - The seed of the pseudo-random number generator. The same seed always gives the same result.
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the approximate value of the integral and its standard error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let (result, standard_error) = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 1.0, 1).monte_carlo_antithetic(1e5 as u64, 42);
+    /// assert!((result - (std::f64::consts::E - 1.0)).abs() < 5.0 * standard_error);
+    /// ```
+    pub fn monte_carlo_antithetic(&mut self, samples: u64, seed: u64) -> (f64, f64) {
+        self.last_method = Some("monte_carlo_antithetic");
+        if is_zero_width(self.lower_bound, self.upper_bound) {
+            self.result = 0.0;
+            return (self.result, 0.0);
+        }
+
+        let pairs = (samples / 2).max(2);
+        let width = self.upper_bound - self.lower_bound;
+        let mut rng = Rng::new(seed);
+        let (mut sum, mut sum_squares) = (0.0, 0.0);
+
+        for _ in 0..pairs {
+            let offset = rng.next_f64() * width;
+            let value = ((self.function)(self.lower_bound + offset)
+                + (self.function)(self.upper_bound - offset))
+                / 2.0;
+            sum += value;
+            sum_squares += value * value;
+        }
+
+        let mean = sum / pairs as f64;
+        let variance = ((sum_squares - pairs as f64 * mean * mean) / (pairs - 1) as f64).max(0.0);
+        self.result = width * mean;
+        (self.result, width * (variance / pairs as f64).sqrt())
+    }
+
+    /// Performs numerical integration using the Monte Carlo method, with a confidence interval of the result.
+    ///
+    /// The random points are the ones of `monte_carlo_integration` with the same seed, so the estimate is the same.
+    /// The interval is `estimate ± z * standard_error`, where the standard error is estimated from the sample variance, accumulated with Welford's algorithm so that a large mean does not cancel it out,
+    /// and `z` is the quantile of the normal distribution for the confidence level, e.g. about `1.96` for `0.95`.
+    /// The normal approximation is justified by the central limit theorem, and is accurate for more than a few dozen samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - The number of random points. At least two points are used, to estimate the standard error.
+    /// * `seed` - The seed of the pseudo-random number generator. The same seed always gives the same result.
+    /// * `confidence` - The probability that the interval contains the exact value, strictly between `0` and `1`.
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the approximate value of the integral, and the lower and upper ends of the confidence interval.
+    /// The ends are `NaN` if `confidence` is not strictly between `0` and `1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let (result, (low, high)) = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 1.0, 1).monte_carlo_confidence(1e5 as u64, 42, 0.99);
+    /// assert!(low < result && result < high);
+    /// ```
+    pub fn monte_carlo_confidence(
+        &mut self,
+        samples: u64,
+        seed: u64,
+        confidence: f64,
+    ) -> (f64, (f64, f64)) {
+        self.last_method = Some("monte_carlo_confidence");
+        let z = normal_quantile((1.0 + confidence) / 2.0);
+        if is_zero_width(self.lower_bound, self.upper_bound) {
+            self.result = 0.0;
+            return (self.result, (0.0 * z, 0.0 * z)); // Exact, unless the confidence is invalid
+        }
+
+        let samples = samples.max(2);
+        let width = self.upper_bound - self.lower_bound;
+        let mut sum = 0.0; // Summed as in `monte_carlo_integration`, for the same estimate
+        let (mut count, mut mean, mut squared_deviations) = (0.0, 0.0, 0.0);
+        self.for_each_random_sample(samples, seed, |value| {
+            sum += value;
+            count += 1.0;
+            let delta = value - mean;
+            mean += delta / count;
+            squared_deviations += delta * (value - mean);
+        });
+
+        let variance = squared_deviations / (samples - 1) as f64;
+        let half_width = z * width.abs() * (variance / samples as f64).sqrt();
+        self.result = width * sum / samples as f64;
+        (
+            self.result,
+            (self.result - half_width, self.result + half_width),
+        )
+    }
+
+    /// Computes the sensitivity of the integral to a uniform scaling of the function, `(I[f * (1 + ε)] - I[f]) / ε`, using Simpson's 1/3 rule.
+    ///
+    /// It is used in error propagation: a relative error `ε` on the function changes the integral by about `ε` times the sensitivity.
+    /// Since integration is linear the sensitivity equals the integral, up to rounding errors, so it is also a self-consistency check of the method.
+    ///
+    /// # Arguments
+    ///
+    /// * `epsilon` - The relative scaling of the function. Too small values amplify the rounding errors, e.g. `1e-6` is a good compromise.
+    ///
+    /// # Returns
+    ///
+    /// The approximate sensitivity of the integral.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let sensitivity = Integral::new(Box::new(|x: f64| x.sin()), 0.0, std::f64::consts::PI, 1e3 as u64).sensitivity_to_scaling(1e-6);
+    /// assert!((sensitivity - 2.0).abs() < 1e-6);
+    /// ```
+    pub fn sensitivity_to_scaling(&mut self, epsilon: f64) -> f64 {
+        self.last_method = Some("sensitivity_to_scaling");
+        let (a, b, n) = (self.lower_bound, self.upper_bound, self.num_intervals);
+        let integral = Self::simpson_sum(self.summation, a, b, n, |x| (self.function)(x));
+        let scaled = Self::simpson_sum(self.summation, a, b, n, |x| {
+            (self.function)(x) * (1.0 + epsilon)
+        });
+
+        self.result = (scaled - integral) / epsilon;
+        self.result
+    }
+
+    /// Checks that the function can be evaluated over the interval, before committing to a long integration.
+    ///
+    /// The function is evaluated at `samples` evenly spaced points, bounds included.
+    /// A value is rejected if it is not finite, or if it exceeds the values at the neighbouring samples (or `1`, if they are smaller) by so large a factor that it can only come from a pole hit within rounding error.
+    /// Large values of a function that grows smoothly, e.g. `exp(x)` on `[0, 40]`, are accepted.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - The number of points to check. At least the two bounds are always checked.
+    ///
+    /// # Returns
+    ///
+    /// An error listing the points where the function is not finite, if any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let integral = Integral::new(Box::new(|x| x.ln()), 0.0, 1.0, 1e6 as u64);
+    /// assert!(integral.validate_integrand(11).is_err()); // ln(0) is not finite
+    /// ```
+    pub fn validate_integrand(&self, samples: usize) -> Result<(), IntegrationError> {
+        self.check_bounds()?;
+        let samples = samples.max(2);
+        let step = (self.upper_bound - self.lower_bound) / (samples - 1) as f64;
+
+        let points: Vec<f64> = (0..samples)
+            .map(|i| self.lower_bound + i as f64 * step)
+            .collect();
+        let values: Vec<f64> = points.iter().map(|&x| (self.function)(x)).collect();
+
+        let bad_points: Vec<f64> = (0..samples)
+            .filter(|&i| {
+                let value = values[i];
+                if !value.is_finite() {
+                    return true;
+                }
+                let left = if i > 0 { values[i - 1].abs() } else { 0.0 };
+                let right = values.get(i + 1).map_or(0.0, |v| v.abs());
+                value.abs() > POLE_JUMP_RATIO * left.max(right).max(1.0)
+            })
+            .map(|i| points[i])
+            .collect();
+
+        if bad_points.is_empty() {
+            Ok(())
+        } else {
+            Err(IntegrationError::NonFiniteIntegrand(bad_points))
+        }
+    }
+
+    /// Locates the jump discontinuities and poles of the function over the interval.
+    ///
+    /// The interval is split into `samples` slices, and each slice where the function changes is bisected towards the largest change.
+    /// Where the function is continuous the change shrinks with the width of the slice, so a slice is reported only if the change survives the bisection.
+    /// Changes within `utils::numeric::ROUNDING_TOLERANCE` of the values of the function, relatively, are attributed to rounding errors, and ignored, however small the values.
+    /// A pole is located like a jump when it lies inside a slice; a slice with an end where the function is not finite, e.g. on the pole itself, is skipped: use `validate_integrand` to find those points.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - The number of slices to check. A discontinuity is missed if it is hidden within a slice, e.g. a jump which comes back.
+    ///
+    /// # Returns
+    ///
+    /// The approximate locations of the discontinuities, sorted from the lower to the upper bound.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let integral = Integral::new(Box::new(|x: f64| x.floor()), 0.0, 3.5, 1e6 as u64);
+    /// let discontinuities = integral.detect_discontinuities(10);
+    /// assert_eq!(discontinuities.len(), 3);
+    /// assert!((discontinuities[1] - 2.0).abs() < 1e-9);
+    /// ```
+    pub fn detect_discontinuities(&self, samples: usize) -> Vec<f64> {
+        let samples = samples.max(1);
+        let step = (self.upper_bound - self.lower_bound) / samples as f64;
+        let mut discontinuities = vec![];
+
+        for i in 0..samples {
+            // Ends of the slice in increasing order, also with reversed bounds
+            let (start, end) = (
+                self.lower_bound + i as f64 * step,
+                self.lower_bound + (i + 1) as f64 * step,
+            );
+            let (mut a, mut b) = (start.min(end), start.max(end));
+            let (mut fa, mut fb) = ((self.function)(a), (self.function)(b));
+            let initial_jump = (fb - fa).abs();
+            if rel_eq(fa, fb, ROUNDING_TOLERANCE) || !initial_jump.is_finite() {
+                continue; // No change beyond rounding errors
+            }
+
+            // Halve the slice until the change is located within rounding error, or it vanishes
+            while a < (a + b) / 2.0 && (a + b) / 2.0 < b {
+                let m = (a + b) / 2.0;
+                let fm = (self.function)(m);
+                if (fm - fa).abs() >= (fb - fm).abs() {
+                    (b, fb) = (m, fm);
+                } else {
+                    (a, fa) = (m, fm);
+                }
+                if (fb - fa).abs() < 0.5 * initial_jump * ((b - a) / step.abs()).sqrt() {
+                    break; // Shrinking with the slice: the function is continuous here
+                }
+            }
+
+            if (fb - fa).abs() >= 0.5 * initial_jump * ((b - a) / step.abs()).sqrt() {
+                discontinuities.push((a + b) / 2.0);
+            }
+        }
+
+        discontinuities
+    }
+
+    /// Performs numerical integration with a method chosen by analysing the function, as a smart default for non-experts.
+    ///
+    /// - Functions with discontinuities are integrated piecewise, with the adaptive Simpson's method on each piece between discontinuities.
+    /// - Smooth functions, for which Simpson's 1/3 rule already agrees on a coarse grid and on a grid twice as fine, are integrated with Simpson's 1/3 rule and the number of intervals.
+    /// - Other functions, e.g. with narrow spikes, are integrated with the adaptive Simpson's method.
+    ///
+    /// The adaptive methods do not use the number of intervals, and their partial result is kept if they hit their cap on the number of subdivisions.
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the approximate value of the integral and the name of the chosen method.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let (result, method) = Integral::new(Box::new(|x: f64| x.sin()), 0.0, std::f64::consts::PI, 1e3 as u64).integrate_auto();
+    /// println!("The integral is approximately {}, computed with {}", result, method);
+    /// ```
+    pub fn integrate_auto(&mut self) -> (f64, &'static str) {
+        self.last_method = Some("integrate_auto");
+        let (a, b) = (self.lower_bound, self.upper_bound);
+        if is_zero_width(a, b) {
+            self.result = 0.0;
+            return (self.result, "Simpson's 1/3");
+        }
+
+        let discontinuities = self.detect_discontinuities(AUTO_ANALYSIS_SAMPLES);
+        if !discontinuities.is_empty() {
+            let mut breakpoints = vec![a];
+            breakpoints.extend(discontinuities);
+            breakpoints.push(b);
+
+            self.result = breakpoints
+                .windows(2)
+                .map(|piece| {
+                    self.run_adaptive_simpson(
+                        (piece[0], piece[1]),
+                        ADAPTIVE_TOLERANCE,
+                        MAX_ADAPTIVE_DEPTH,
+                    )
+                    .0
+                })
+                .sum();
+            return (self.result, "Piecewise Adaptive Simpson");
+        }
+
+        let coarse = Self::simpson_sum(
+            self.summation,
+            a,
+            b,
+            AUTO_ANALYSIS_SAMPLES as u64 / 2,
+            |x| (self.function)(x),
+        );
+        let fine = Self::simpson_sum(self.summation, a, b, AUTO_ANALYSIS_SAMPLES as u64, |x| {
+            (self.function)(x)
+        });
+        if approx_eq(fine, coarse, AGREEMENT_TOLERANCE) {
+            let result = self.simpson_integration_one_third();
+            self.last_method = Some("integrate_auto");
+            (result, "Simpson's 1/3")
+        } else {
+            self.result = self
+                .run_adaptive_simpson((a, b), ADAPTIVE_TOLERANCE, MAX_ADAPTIVE_DEPTH)
+                .0;
+            (self.result, "Adaptive Simpson")
+        }
+    }
+
+    /// Performs numerical integration using Simpson's 1/3 rule on the smooth slices of the interval, and the trapezoidal rule on the slices with a kink or a jump.
+    ///
+    /// Simpson's rule relies on the function being smooth, and loses its accuracy on a slice where it is not.
+    /// The heuristic flags a slice as non-smooth if:
+    /// - the slopes of the function on its two halves, from the values at the ends and at the middle of the slice, differ by more than `kink_tolerance`, i.e. the function has a kink;
+    /// - or it contains a discontinuity found by `detect_discontinuities`.
+    ///
+    /// The slopes of a smooth function differ by about `|f''| * width / 2`, where `width` is the width of a slice, so the tolerance should be above that.
+    /// Each non-smooth slice is split into 64 trapezoids, which are robust to the kink.
+    ///
+    /// # Arguments
+    ///
+    /// * `kink_tolerance` - The largest change of slope within a slice considered smooth.
+    ///
+    /// # Returns
+    ///
+    /// The approximate value of the integral.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let result = Integral::new(Box::new(|x: f64| (x - 0.25).abs()), 0.0, 1.0, 10).integrate_hybrid(1e-3);
+    /// assert!((result - 0.3125).abs() < 1e-4);
+    /// ```
+    pub fn integrate_hybrid(&mut self, kink_tolerance: f64) -> f64 {
+        self.last_method = Some("integrate_hybrid");
+        let (a, b) = (self.lower_bound, self.upper_bound);
+        if is_zero_width(a, b) {
+            self.result = 0.0;
+            return self.result;
+        }
+
+        let discontinuities = self.detect_discontinuities(AUTO_ANALYSIS_SAMPLES);
+        let width = (b - a) / self.num_intervals as f64; // Width of each slice of the interval, negative with reversed bounds
+        let mut sum = Accumulator::new(self.summation);
+
+        for i in 0..self.num_intervals {
+            let x_coordinate = a + i as f64 * width;
+            let x_next = x_coordinate + width;
+            let x_mid = (x_coordinate + x_next) / 2.0;
+            let (fa, fm, fb) = (
+                (self.function)(x_coordinate),
+                (self.function)(x_mid),
+                (self.function)(x_next),
+            );
+
+            let slope_change = ((fb - fm) - (fm - fa)).abs() / (width / 2.0).abs();
+            let has_discontinuity = discontinuities
+                .iter()
+                .any(|&d| x_coordinate.min(x_next) <= d && d <= x_coordinate.max(x_next));
+            sum.add(if slope_change > kink_tolerance || has_discontinuity {
+                Self::trapezoidal_sum(
+                    self.summation,
+                    x_coordinate,
+                    x_next,
+                    KINK_SUBDIVISIONS,
+                    |x| (self.function)(x),
+                )
+            } else {
+                width / 6.0 * (fa + 4.0 * fm + fb)
+            });
+        }
+
+        self.result = sum.total();
+        self.result
+    }
+
+    /// Performs numerical integration of an integer power of the function, `∫ f(x)^p dx`, using Simpson's 1/3 rule.
+    ///
+    /// For `p = 0` the result is the width of the interval, without evaluating the function.
+    /// For negative powers the function is first checked at the points used by Simpson's rule, since `f(x)^p` has a pole wherever `f` vanishes:
+    /// if the function is zero, or changes sign between two consecutive points, an error is returned instead of a meaningless result.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The power.
+    ///
+    /// # Returns
+    ///
+    /// The approximate value of the integral, or an error if `p` is negative and the function crosses zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// // ∫ 1 / x^2 dx over [1, 2]
+    /// let result = Integral::new(Box::new(|x| x), 1.0, 2.0, 1e3 as u64).integrate_power(-2).unwrap();
+    /// assert!((result - 0.5).abs() < 1e-12);
+    /// ```
+    pub fn integrate_power(&mut self, p: i32) -> Result<f64, IntegrationError> {
+        self.last_method = Some("integrate_power");
+        self.check_bounds()?;
+        let (a, b, n) = (self.lower_bound, self.upper_bound, self.num_intervals);
+        if p == 0 {
+            self.result = b - a;
+            return Ok(self.result);
+        }
+
+        if p < 0 {
+            self.check_no_zero_crossing()?;
+        }
+
+        self.result = Self::simpson_sum(self.summation, a, b, n, |x| (self.function)(x).powi(p));
+        Ok(self.result)
+    }
+
+    /// Computes the arithmetic mean value of the function over the interval, `(1 / (b - a)) ∫ f(x) dx`, using Simpson's 1/3 rule.
+    ///
+    /// # Returns
+    ///
+    /// The arithmetic mean value, or `NaN` for an interval of zero width.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let mean = Integral::new(Box::new(|x| x * x), 0.0, 3.0, 1e3 as u64).mean_value();
+    /// assert!((mean - 3.0).abs() < 1e-12);
+    /// ```
+    pub fn mean_value(&mut self) -> f64 {
+        let (a, b) = (self.lower_bound, self.upper_bound);
+        self.result = self.simpson_integration_one_third() / (b - a);
+        self.last_method = Some("mean_value");
+        self.result
+    }
+
+    /// Computes the geometric mean value of the function over the interval, `exp((1 / (b - a)) ∫ ln|f(x)| dx)`, using Simpson's 1/3 rule.
+    ///
+    /// The geometric mean is the natural average of quantities which combine by multiplication, e.g. growth rates.
+    /// It is never larger than the arithmetic mean of `|f|`, with equality for a constant function.
+    /// The logarithm has a pole wherever the function vanishes, so the function is first checked as in `integrate_power` with a negative power.
+    ///
+    /// # Returns
+    ///
+    /// The geometric mean value of `|f|`, or an error if the function crosses zero. `NaN` for an interval of zero width.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let mean = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 2.0, 1e3 as u64).geometric_mean_value().unwrap();
+    /// assert!((mean - 1f64.exp()).abs() < 1e-12);
+    /// ```
+    pub fn geometric_mean_value(&mut self) -> Result<f64, IntegrationError> {
+        self.last_method = Some("geometric_mean_value");
+        self.check_bounds()?;
+        self.check_no_zero_crossing()?;
+
+        let (a, b, n) = (self.lower_bound, self.upper_bound, self.num_intervals);
+        let log_integral =
+            Self::simpson_sum(self.summation, a, b, n, |x| (self.function)(x).abs().ln());
+        self.result = (log_integral / (b - a)).exp();
+        Ok(self.result)
+    }
+
+    /// Computes the harmonic mean value of the function over the interval, `(b - a) / ∫ 1 / f(x) dx`, using Simpson's 1/3 rule.
+    ///
+    /// The harmonic mean is the natural average of rates, e.g. the average speed over a path, given the speed at each point.
+    /// For a positive function it is never larger than the geometric mean, with equality for a constant function.
+    /// The reciprocal has a pole wherever the function vanishes, so the function is first checked as in `integrate_power` with a negative power.
+    ///
+    /// # Returns
+    ///
+    /// The harmonic mean value, or an error if the function crosses zero. `NaN` for an interval of zero width.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// let mean = Integral::new(Box::new(|x| x), 1.0, 2.0, 1e3 as u64).harmonic_mean_value().unwrap();
+    /// assert!((mean - 1.0 / 2f64.ln()).abs() < 1e-12);
+    /// ```
+    pub fn harmonic_mean_value(&mut self) -> Result<f64, IntegrationError> {
+        let (a, b) = (self.lower_bound, self.upper_bound);
+        let reciprocal_integral = self.integrate_power(-1)?;
+        self.result = (b - a) / reciprocal_integral;
+        self.last_method = Some("harmonic_mean_value");
+        Ok(self.result)
+    }
+
+    /// Computes the root-mean-square value of the function over the interval, `sqrt((1 / (b - a)) ∫ f(x)^2 dx)`, using Simpson's 1/3 rule.
+    ///
+    /// The RMS value of an alternating current or voltage is the constant value which would dissipate the same power in a resistor, e.g. `1 / sqrt(2)` of the amplitude for a sine wave.
+    ///
+    /// # Returns
+    ///
+    /// The RMS value, or `NaN` for an interval of zero width.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// // Sawtooth wave of amplitude 1
+    /// let rms = Integral::new(Box::new(|x| x), -1.0, 1.0, 1e3 as u64).rms_value();
+    /// assert!((rms - 1.0 / 3f64.sqrt()).abs() < 1e-12);
+    /// ```
+    pub fn rms_value(&mut self) -> f64 {
+        self.last_method = Some("rms_value");
+        let (a, b, n) = (self.lower_bound, self.upper_bound, self.num_intervals);
+        let square_integral =
+            Self::simpson_sum(self.summation, a, b, n, |x| (self.function)(x).powi(2));
+        self.result = (square_integral / (b - a)).sqrt();
+        self.result
+    }
+
+    /// Computes the total variation of the function over the interval, `∫ |f'(x)| dx`, using Simpson's 1/3 rule.
+    ///
+    /// The total variation is the distance travelled up and down by the graph of the function, e.g. `4` for a sine over a period:
+    /// it measures how much the function oscillates, which bounds the error of the quadrature rules for functions of bounded variation.
+    /// The derivative is computed at each point with `Derivative::central_difference`, so the function only needs to be known by its values.
+    /// Where the derivative changes sign, the absolute value has a kink, which lowers the accuracy of Simpson's rule: use more intervals for a function with many extrema.
+    ///
+    /// # Arguments
+    ///
+    /// * `h` - The increment of the central differences.
+    ///
+    /// # Returns
+    ///
+    /// The approximate total variation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// // A monotonic function varies by the difference of its values at the bounds
+    /// let variation = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 1.0, 100).total_variation(1e-5);
+    /// assert!((variation - (std::f64::consts::E - 1.0)).abs() < 1e-9);
+    /// ```
+    pub fn total_variation(&mut self, h: f64) -> f64 {
+        self.last_method = Some("total_variation");
+        let (a, b, n) = (self.lower_bound, self.upper_bound, self.num_intervals);
+
+        // The function is lent to the derivative for the duration of the integration
+        let function = std::mem::replace(&mut self.function, Box::new(|_| f64::NAN));
+        let derivative = RefCell::new(Derivative::new(function, a, h));
+        self.result = Self::simpson_sum(self.summation, a, b, n, |x| {
+            let mut derivative = derivative.borrow_mut();
+            derivative.x_coordinate = x;
+            derivative.central_difference().abs()
+        });
+
+        self.function = derivative.into_inner().function;
+        self.result
+    }
+
+    /// Computes the centroid of the region between the function and the x-axis, over the interval.
+    ///
+    /// The coordinates of the centroid are `x̄ = ∫ x f(x) dx / A` and `ȳ = ∫ f(x)^2 / 2 dx / A`, where `A = ∫ f(x) dx` is the signed area, all computed with Simpson's 1/3 rule.
+    /// Regions below the x-axis count as negative area.
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the coordinates `(x̄, ȳ)` of the centroid, or `(NaN, NaN)` if the signed area is zero within `utils::numeric::EPSILON`, because the centroid is undefined.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// // Triangle with vertices (0, 0), (1, 0) and (1, 1)
+    /// let (x, y) = Integral::new(Box::new(|x| x), 0.0, 1.0, 1e3 as u64).centroid();
+    /// assert!((x - 2.0 / 3.0).abs() < 1e-9);
+    /// assert!((y - 1.0 / 3.0).abs() < 1e-9);
+    /// ```
+    pub fn centroid(&mut self) -> (f64, f64) {
+        self.last_method = Some("centroid");
+        let (a, b, n) = (self.lower_bound, self.upper_bound, self.num_intervals);
+        let area = Self::simpson_sum(self.summation, a, b, n, |x| (self.function)(x));
+        if approx_eq(area, 0.0, EPSILON) {
+            return (f64::NAN, f64::NAN);
+        }
+
+        let moment_y = Self::simpson_sum(self.summation, a, b, n, |x| x * (self.function)(x));
+        let moment_x = Self::simpson_sum(self.summation, a, b, n, |x| {
+            (self.function)(x).powi(2) / 2.0
+        });
+        self.result = area;
+        (moment_y / area, moment_x / area)
+    }
+
+    /// Computes the volume of the solid generated by rotating the graph of the function about the x-axis, using the disk method.
+    ///
+    /// The volume is `π ∫ f(x)^2 dx` over the interval, computed with Simpson's 1/3 rule.
+    ///
+    /// # Returns
+    ///
+    /// The approximate volume of the solid of revolution.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::integrals::Integral;
+    ///
+    /// // Sphere of radius 1
+    /// let volume = Integral::new(Box::new(|x: f64| (1.0 - x * x).sqrt()), -1.0, 1.0, 1e3 as u64)
+    ///     .volume_of_revolution_x();
+    /// assert!((volume - 4.0 / 3.0 * std::f64::consts::PI).abs() < 1e-9);
+    /// ```
+    pub fn volume_of_revolution_x(&mut self) -> f64 {
+        self.last_method = Some("volume_of_revolution_x");
+        self.result = std::f64::consts::PI
+            * Self::simpson_sum(
+                self.summation,
+                self.lower_bound,
+                self.upper_bound,
+                self.num_intervals,
+                |x| (self.function)(x).powi(2),
+            );
+        self.result
+    }
+
+    /// Checks that the function neither vanishes nor changes sign between two consecutive points used by Simpson's rule.
+    fn check_no_zero_crossing(&self) -> Result<(), IntegrationError> {
+        let (a, b, n) = (self.lower_bound, self.upper_bound, self.num_intervals);
+        if is_zero_width(a, b) {
+            return Ok(()); // The function is not evaluated
+        }
+
+        let half_width = (b - a) / (2 * n) as f64;
+        let mut previous = (self.function)(a);
+        for i in 0..=2 * n {
+            let x_coordinate = a + i as f64 * half_width;
+            let value = (self.function)(x_coordinate);
+            if value == 0.0 || value.signum() != previous.signum() {
+                return Err(IntegrationError::ZeroCrossing(x_coordinate));
+            }
+            previous = value;
+        }
+
+        Ok(())
+    }
+
+    /// Sums the areas of the `num_intervals` slices of `[lower_bound, upper_bound]`, approximating `integrand` with a quadratic interpolant on each slice.
+    pub(crate) fn simpson_sum<T: Summable, F: Fn(f64) -> T>(
+        summation: SummationStrategy,
+        lower_bound: f64,
+        upper_bound: f64,
+        num_intervals: u64,
+        integrand: F,
+    ) -> T {
+        if is_zero_width(lower_bound, upper_bound) {
+            return T::zero(); // Zero-width interval, without evaluating the integrand
+        }
+
+        let width = (upper_bound - lower_bound) / num_intervals as f64; // Width of each slice of the interval
+        let mut sum = Accumulator::new(summation);
+
+        for i in 0..num_intervals {
+            let x_coordinate = lower_bound + i as f64 * width;
+            let x_next = x_coordinate + width;
+            let x_mid = (x_coordinate + x_next) / 2.0;
+
+            // Simpson's rule: f(a) + 4f(m) + f(b)
+            sum.add(
+                integrand(x_coordinate)
+                    .add(&integrand(x_mid).scale(4.0))
+                    .add(&integrand(x_next)),
+            );
+        }
+
+        sum.total().scale(width / 6.0) // Last step can be factored out of the integral, because it is constant
+    }
+
+    /// Sums the areas of the `num_intervals` slices of `[lower_bound, upper_bound]`, sampling `integrand` at the middle of each slice.
+    fn midpoint_sum<F: Fn(f64) -> f64>(
+        summation: SummationStrategy,
+        lower_bound: f64,
+        upper_bound: f64,
+        num_intervals: u64,
+        integrand: F,
+    ) -> f64 {
+        if is_zero_width(lower_bound, upper_bound) {
+            return 0.0; // Zero-width interval, without evaluating the integrand
+        }
+
+        let width = (upper_bound - lower_bound) / num_intervals as f64; // Width of each slice of the interval
+        let mut sum = Accumulator::new(summation);
+
+        for i in 0..num_intervals {
+            let x_coordinate = lower_bound + (i as f64 + 0.5) * width;
+            sum.add(integrand(x_coordinate) * width);
+        }
+
+        sum.total()
+    }
+
+    /// Sums the areas of the `num_intervals` slices of `[lower_bound, upper_bound]`, approximating `integrand` with a straight line on each slice.
+    fn trapezoidal_sum<F: Fn(f64) -> f64>(
+        summation: SummationStrategy,
+        lower_bound: f64,
+        upper_bound: f64,
+        num_intervals: u64,
+        integrand: F,
+    ) -> f64 {
+        if is_zero_width(lower_bound, upper_bound) {
+            return 0.0; // Zero-width interval, without evaluating the integrand
+        }
+
+        let width = (upper_bound - lower_bound) / num_intervals as f64; // Width of each slice of the interval
+        let mut sum = Accumulator::new(summation);
+        sum.add((integrand(lower_bound) + integrand(upper_bound)) / 2.0);
+
+        for i in 1..num_intervals {
+            sum.add(integrand(lower_bound + i as f64 * width));
+        }
+
+        sum.total() * width
+    }
+
+    /// Runs the adaptive Simpson's method over `[a, b]`, tracing the panels it accepted.
+    fn run_adaptive_simpson(
+        &self,
+        (a, b): (f64, f64),
+        tolerance: f64,
+        max_depth: u32,
+    ) -> (f64, AdaptiveTrace) {
+        let mut trace = AdaptiveTrace {
+            max_depth,
+            panels: vec![],
+            max_depth_reached: false,
+        };
+        if is_zero_width(a, b) {
+            return (0.0, trace);
+        }
+
+        let m = (a + b) / 2.0;
+        let (fa, fm, fb) = ((self.function)(a), (self.function)(m), (self.function)(b));
+        let whole = (b - a) / 6.0 * (fa + 4.0 * fm + fb);
+        let result = Self::adaptive_simpson_step(
+            &|x| (self.function)(x),
+            (a, b),
+            (fa, fm, fb),
+            whole,
+            tolerance,
+            0,
+            &mut trace,
+        );
+
+        (result, trace)
+    }
+
+    /// Recursive step of the adaptive Simpson's method on the panel `[a, b]`, which was obtained by halving the interval `depth` times.
+    ///
+    /// `values` holds the integrand evaluated at `a`, at the middle of the panel and at `b`, and `whole` is Simpson's rule on the panel.
+    /// Each accepted panel is recorded in `trace`.
+    fn adaptive_simpson_step<F: Fn(f64) -> f64>(
+        integrand: &F,
+        (a, b): (f64, f64),
+        (fa, fm, fb): (f64, f64, f64),
+        whole: f64,
+        tolerance: f64,
+        depth: u32,
+        trace: &mut AdaptiveTrace,
+    ) -> f64 {
+        let m = (a + b) / 2.0;
+        let (left_mid, right_mid) = ((a + m) / 2.0, (m + b) / 2.0);
+        let (f_left_mid, f_right_mid) = (integrand(left_mid), integrand(right_mid));
+        let left = (m - a) / 6.0 * (fa + 4.0 * f_left_mid + fm);
+        let right = (b - m) / 6.0 * (fm + 4.0 * f_right_mid + fb);
+        let delta = left + right - whole;
+
+        let converged = delta.abs() <= 15.0 * tolerance;
+        if converged || depth >= trace.max_depth {
+            trace.max_depth_reached |= !converged;
+            trace.panels.push((a, b, depth));
+            return left + right + delta / 15.0; // Richardson extrapolation of the two estimates
+        }
+
+        Self::adaptive_simpson_step(
+            integrand,
+            (a, m),
+            (fa, f_left_mid, fm),
+            left,
+            tolerance / 2.0,
+            depth + 1,
+            trace,
+        ) + Self::adaptive_simpson_step(
+            integrand,
+            (m, b),
+            (fm, f_right_mid, fb),
+            right,
+            tolerance / 2.0,
+            depth + 1,
+            trace,
+        )
+    }
+}
+
+/// Record of a run of the adaptive Simpson's method.
+struct AdaptiveTrace {
+    max_depth: u32,               // Maximum number of times a panel can be halved
+    panels: Vec<(f64, f64, u32)>, // Accepted panels, with the number of times the interval was halved to obtain them
+    max_depth_reached: bool,      // Whether a panel was accepted without meeting the tolerance
+}
+
+/// Running sum of the areas of the slices, with a `SummationStrategy`.
+///
+/// The pairwise summation is streaming: the partial sums waiting for a partner are kept on a stack of at most `log2(n)` entries, like the digits of a binary counter.
+struct Accumulator<T> {
+    strategy: SummationStrategy,
+    sum: T,
+    compensation: T, // Digits lost by the last addition, for Kahan's summation
+    partials: Vec<(u64, T)>, // Number of areas and partial sum, from the largest to the smallest, for the pairwise summation
+}
+
+impl<T: Summable> Accumulator<T> {
+    fn new(strategy: SummationStrategy) -> Self {
+        Accumulator {
+            strategy,
+            sum: T::zero(),
+            compensation: T::zero(),
+            partials: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, value: T) {
+        match self.strategy {
+            SummationStrategy::Naive => self.sum = self.sum.add(&value),
+            SummationStrategy::Kahan => {
+                let corrected = value.add(&self.compensation.scale(-1.0));
+                let sum = self.sum.add(&corrected);
+                // What was actually added, minus what should have been
+                self.compensation = sum.add(&self.sum.scale(-1.0)).add(&corrected.scale(-1.0));
+                self.sum = sum;
+            }
+            SummationStrategy::Pairwise => {
+                let (mut count, mut value) = (1, value);
+                while self.partials.last().is_some_and(|&(top, _)| top == count) {
+                    let (_, partial) = self.partials.pop().unwrap();
+                    value = partial.add(&value);
+                    count *= 2;
+                }
+                self.partials.push((count, value));
+            }
+        }
+    }
+
+    fn total(&self) -> T {
+        match self.strategy {
+            SummationStrategy::Naive | SummationStrategy::Kahan => self.sum.clone(),
+            SummationStrategy::Pairwise => self
+                .partials
+                .iter()
+                .rev()
+                .fold(T::zero(), |sum, (_, partial)| sum.add(partial)),
+        }
+    }
+}
+
+/// Performs numerical integration of sampled data with Simpson's 1/3 rule generalised to unequally spaced points.
+///
+/// Each pair of consecutive slices is integrated exactly with the quadratic through its three points.
+/// If the number of slices is odd, the last slice is integrated with the trapezoidal rule.
+///
+/// # Arguments
+///
+/// * `xs` - The abscissae of the samples, strictly increasing.
+/// * `ys` - The values of the function at the abscissae.
+///
+/// # Returns
+///
+/// The approximate value of the integral over `[xs[0], xs[xs.len() - 1]]`, or an error if there are fewer than three samples, or the abscissae are not strictly increasing.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::integrals::simpson_irregular;
+///
+/// // Exact for a quadratic
+/// let xs = [0.0, 0.5, 2.0];
+/// let ys: Vec<f64> = xs.iter().map(|x| x * x).collect();
+/// let result = simpson_irregular(&xs, &ys).unwrap();
+/// assert!((result - 8.0 / 3.0).abs() < 1e-12);
+/// ```
+pub fn simpson_irregular(xs: &[f64], ys: &[f64]) -> Result<f64, IntegrationError> {
+    interpolation::validate_samples(xs, ys, 3)?;
+
+    let num_slices = xs.len() - 1;
+    let mut sum = 0.0;
+
+    for i in (0..num_slices - 1).step_by(2) {
+        let h0 = xs[i + 1] - xs[i];
+        let h1 = xs[i + 2] - xs[i + 1];
+        sum += (h0 + h1) / 6.0
+            * ((2.0 - h1 / h0) * ys[i]
+                + (h0 + h1).powi(2) / (h0 * h1) * ys[i + 1]
+                + (2.0 - h0 / h1) * ys[i + 2]);
+    }
+
+    // Leftover slice
+    if num_slices % 2 == 1 {
+        let last = num_slices - 1;
+        sum += (xs[last + 1] - xs[last]) * (ys[last] + ys[last + 1]) / 2.0;
+    }
+
+    Ok(sum)
+}
+
+/// Integrates exactly the piecewise-linear function through the control points, as the sum of the trapezoids between consecutive points.
+///
+/// Unlike sampling a closure, the result has no discretisation error, e.g. for a profile or a signal defined by its vertices:
+/// only the rounding error of the sum remains.
+///
+/// # Arguments
+///
+/// * `points` - The `(x, y)` control points, with strictly increasing abscissae.
+///
+/// # Returns
+///
+/// The integral over `[x0, xn]`, or an error if there are fewer than two points, or the abscissae are not strictly increasing.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::integrals::piecewise_linear_integral;
+///
+/// let result = piecewise_linear_integral(&[(0.0, 1.0), (2.0, 3.0), (3.0, 0.0)]).unwrap();
+/// assert_eq!(result, 5.5);
+/// ```
+pub fn piecewise_linear_integral(points: &[(f64, f64)]) -> Result<f64, IntegrationError> {
+    let (xs, ys): (Vec<f64>, Vec<f64>) = points.iter().copied().unzip();
+    interpolation::validate_samples(&xs, &ys, 2)?;
+
+    Ok(points
+        .windows(2)
+        .map(|pair| (pair[1].0 - pair[0].0) * (pair[0].1 + pair[1].1) / 2.0)
+        .sum())
+}
+
+/// Reconstructs sampled data from samples of its derivative, by cumulative integration with the trapezoidal rule.
+///
+/// This is the discrete inverse of `derivatives::derivative_from_samples`.
+///
+/// # Arguments
+///
+/// * `xs` - The abscissae of the samples, strictly increasing.
+/// * `dydx` - The values of the derivative at the abscissae.
+/// * `y0` - The value of the data at `xs[0]`.
+///
+/// # Returns
+///
+/// The reconstructed values of the data at the abscissae, or an error if there are no samples, or the abscissae are not strictly increasing.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::integrals::reconstruct_from_derivative;
+///
+/// let ys = reconstruct_from_derivative(&[0.0, 1.0, 3.0], &[1.0, 1.0, 1.0], 2.0).unwrap();
+/// assert_eq!(ys, vec![2.0, 3.0, 5.0]);
+/// ```
+pub fn reconstruct_from_derivative(
+    xs: &[f64],
+    dydx: &[f64],
+    y0: f64,
+) -> Result<Vec<f64>, IntegrationError> {
+    interpolation::validate_samples(xs, dydx, 1)?;
+
+    let mut ys = Vec::with_capacity(xs.len());
+    ys.push(y0);
+    for i in 1..xs.len() {
+        ys.push(ys[i - 1] + (xs[i] - xs[i - 1]) * (dydx[i - 1] + dydx[i]) / 2.0);
+    }
+
+    Ok(ys)
+}
+
+/// Computes the area swept by a curve given in polar coordinates, `(1/2) ∫ r(θ)^2 dθ`, using Simpson's 1/3 rule.
+///
+/// Over a whole turn of a closed curve, this is the area the curve encloses.
+///
+/// # Arguments
+///
+/// * `r` - The distance from the origin as a function of the angle.
+/// * `theta_start` - The starting angle, in radians.
+/// * `theta_end` - The ending angle, in radians.
+/// * `num_intervals` - The number of slices of the range of angles.
+///
+/// # Returns
+///
+/// The approximate area.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::integrals::polar_area;
+///
+/// // Quarter of a disk of radius 2
+/// let area = polar_area(&|_| 2.0, 0.0, std::f64::consts::FRAC_PI_2, 100);
+/// assert!((area - std::f64::consts::PI).abs() < 1e-12);
+/// ```
+pub fn polar_area(
+    r: &dyn Fn(f64) -> f64,
+    theta_start: f64,
+    theta_end: f64,
+    num_intervals: u64,
+) -> f64 {
+    Integral::simpson_sum(
+        SummationStrategy::Naive,
+        theta_start,
+        theta_end,
+        num_intervals,
+        |theta| r(theta).powi(2) / 2.0,
+    )
+}
+
+/// Computes the line integral of a scalar field along a path, `∫ field(path(t)) * |path'(t)| dt`, using Simpson's 1/3 rule.
+///
+/// The speed `|path'(t)|` is obtained numerically with the central difference, so the path only needs to be given as a function of its parameter.
+/// With the constant field `1`, the result is the length of the path.
+///
+/// # Arguments
+///
+/// * `field` - The scalar field, as a function of the coordinates `(x, y)`.
+/// * `path` - The path, as a function of the parameter `t` returning the coordinates `(x, y)`.
+/// * `t0` - The starting value of the parameter.
+/// * `t1` - The ending value of the parameter.
+/// * `num_intervals` - The number of slices of the range of the parameter.
+/// * `increment` - The step size for the central difference of the path.
+///
+/// # Returns
+///
+/// The approximate value of the line integral.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::integrals::line_integral;
+///
+/// // Length of the unit circle
+/// let length = line_integral(&|_, _| 1.0, &|t: f64| (t.cos(), t.sin()), 0.0, 2.0 * std::f64::consts::PI, 1e3 as u64, 1e-6);
+/// assert!((length - 2.0 * std::f64::consts::PI).abs() < 1e-6);
+/// ```
+pub fn line_integral(
+    field: &dyn Fn(f64, f64) -> f64,
+    path: &dyn Fn(f64) -> (f64, f64),
+    t0: f64,
+    t1: f64,
+    num_intervals: u64,
+    increment: f64,
+) -> f64 {
+    Integral::simpson_sum(SummationStrategy::Naive, t0, t1, num_intervals, |t| {
+        let (x_forward, y_forward) = path(t + increment / 2.0);
+        let (x_backward, y_backward) = path(t - increment / 2.0);
+        let speed = (x_forward - x_backward).hypot(y_forward - y_backward) / increment;
+
+        let (x, y) = path(t);
+        field(x, y) * speed
+    })
+}
+
+/// Computes the L1 distance between two functions over `[a, b]`, `∫ |f(x) - g(x)| dx`, using Simpson's 1/3 rule.
+///
+/// It measures how far an approximation is from a reference function, over the whole interval.
+/// Where the functions cross, the absolute value has a kink, which lowers the accuracy of Simpson's rule: use more intervals if they cross often.
+///
+/// # Arguments
+///
+/// * `f` - The first function.
+/// * `g` - The second function.
+/// * `a` - The lower bound of the interval.
+/// * `b` - The upper bound of the interval.
+/// * `num_intervals` - The number of slices of the interval.
+///
+/// # Returns
+///
+/// The approximate L1 distance.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::integrals::{Function, l1_distance};
+///
+/// // Error of the Taylor polynomial of degree 3 of sin(x) around 0
+/// let f: Function = Box::new(|x| x.sin());
+/// let g: Function = Box::new(|x| x - x.powi(3) / 6.0);
+/// let distance = l1_distance(&f, &g, 0.0, 1.0, 1e3 as u64);
+/// // sin(x) is above the polynomial over [0, 1]: ∫ sin(x) - x + x^3 / 6 dx = 1 - cos(1) - 1/2 + 1/24 ≈ 1.364e-3
+/// let exact = 1.0 - 1f64.cos() - 0.5 + 1.0 / 24.0;
+/// assert!((distance - exact).abs() < 1e-9);
+/// ```
+pub fn l1_distance(f: &Function, g: &Function, a: f64, b: f64, num_intervals: u64) -> f64 {
+    Integral::simpson_sum(SummationStrategy::Naive, a, b, num_intervals, |x| {
+        (f(x) - g(x)).abs()
+    })
+}
+
+/// Computes the convolution of two functions at a point, `(f * g)(t) = ∫ f(τ) g(t - τ) dτ` over `[a, b]`, using Simpson's 1/3 rule.
+///
+/// The convolution is the response of a linear time-invariant system with impulse response `g` to the signal `f`, central to signal processing.
+/// The interval `[a, b]` should cover the support of `f`, where it is not zero, so that the integral over it is the integral over the whole real line.
+///
+/// # Arguments
+///
+/// * `f` - The first function, integrated over `[a, b]`.
+/// * `g` - The second function, reflected and shifted by `t`.
+/// * `t` - The point where the convolution is computed.
+/// * `a` - The lower limit of the integration.
+/// * `b` - The upper limit of the integration.
+/// * `n` - The number of slices of the interval.
+///
+/// # Returns
+///
+/// The approximate value of the convolution at `t`.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::integrals::{Function, convolution_at};
+///
+/// // Smoothing a ramp with a moving average over a unit window: the ramp shifted by half the window
+/// let f: Function = Box::new(|x| x);
+/// let g: Function = Box::new(|x| if (0.0..1.0).contains(&x) { 1.0 } else { 0.0 });
+/// let result = convolution_at(&f, &g, 2.0, 1.0, 2.0, 1e3 as u64);
+/// assert!((result - 1.5).abs() < 1e-3);
+/// ```
+pub fn convolution_at(f: &Function, g: &Function, t: f64, a: f64, b: f64, n: u64) -> f64 {
+    Integral::simpson_sum(SummationStrategy::Naive, a, b, n, |tau| f(tau) * g(t - tau))
+}
+
+/// Computes the derivative of the integral of `f` from `lower` with respect to its upper limit, at `upper`.
+///
+/// The integral is computed with Simpson's 1/3 rule up to `upper + h / 2` and up to `upper - h / 2`, and differentiated with the central difference.
+/// By the Fundamental Theorem of Calculus the result is `f(upper)` for a continuous function, so comparing the two checks the integration and the differentiation against each other.
+///
+/// # Arguments
+///
+/// * `f` - The function to integrate.
+/// * `lower` - The lower limit of the integral.
+/// * `upper` - The upper limit of the integral, where the derivative is computed.
+/// * `n` - The number of slices of each integral.
+/// * `h` - The increment of the central difference. Too small an increment is dominated by the error of the integrals, divided by `h`.
+///
+/// # Returns
+///
+/// The approximate value of the derivative.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::integrals::{Function, derivative_of_integral_at};
+///
+/// let f: Function = Box::new(|x| x.cos());
+/// let result = derivative_of_integral_at(&f, 0.0, 1.0, 1e3 as u64, 1e-3);
+/// assert!((result - 1f64.cos()).abs() < 1e-6);
+/// ```
+pub fn derivative_of_integral_at(f: &Function, lower: f64, upper: f64, n: u64, h: f64) -> f64 {
+    let integral_to = |b: f64| Integral::simpson_sum(SummationStrategy::Naive, lower, b, n, f);
+    (integral_to(upper + h / 2.0) - integral_to(upper - h / 2.0)) / h
+}
+
+/// Performs numerical integration using the Monte Carlo method with importance sampling.
+///
+/// The points are drawn from a proposal distribution instead of uniformly, and the function is weighted by `f(x) / pdf(x)`.
+/// The closer the proposal is to the shape of `|f|`, the lower the variance: a proposal peaked where the function is peaked can reduce the error dramatically.
+/// The integral is over the support of the proposal, so the proposal must not vanish where the function does not.
+///
+/// # Arguments
+///
+/// * `f` - The function to integrate.
+/// * `sampler` - The sampler of the proposal distribution, drawing a point from a generator of uniform numbers in `[0, 1)`.
+/// * `pdf` - The probability density function of the proposal distribution.
+/// * `samples` - The number of random points. At least two points are used, to estimate the standard error.
+/// * `seed` - The seed of the pseudo-random number generator. The same seed always gives the same result.
+///
+/// # Returns
+///
+/// A tuple with the approximate value of the integral and its standard error.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::integrals::{Function, monte_carlo_importance, ramp_proposal};
+///
+/// let f: Function = Box::new(|x| x.powi(4));
+/// let (sampler, pdf) = ramp_proposal(0.0, 1.0);
+/// let (result, standard_error) = monte_carlo_importance(&f, &sampler, &pdf, 1e5 as u64, 42);
+/// assert!((result - 0.2).abs() < 5.0 * standard_error);
+/// ```
+pub fn monte_carlo_importance(
+    f: &Function,
+    sampler: &dyn Fn(&mut dyn FnMut() -> f64) -> f64,
+    pdf: &Function,
+    samples: u64,
+    seed: u64,
+) -> (f64, f64) {
+    let samples = samples.max(2);
+    let mut rng = Rng::new(seed);
+    let mut uniform = || rng.next_f64();
+    let (mut sum, mut sum_squares) = (0.0, 0.0);
+
+    for _ in 0..samples {
+        let x = sampler(&mut uniform);
+        let weight = f(x) / pdf(x);
+        sum += weight;
+        sum_squares += weight * weight;
+    }
+
+    let mean = sum / samples as f64;
+    let variance = ((sum_squares - samples as f64 * mean * mean) / (samples - 1) as f64).max(0.0);
+    (mean, (variance / samples as f64).sqrt())
+}
+
+/// Returns a proposal distribution for `monte_carlo_importance` over `[a, b]`, with a density rising linearly from `0` at `a` to `2 / (b - a)` at `b`.
+///
+/// It suits functions which grow towards the upper bound. Swap the bounds for functions which grow towards the lower bound.
+///
+/// # Returns
+///
+/// A tuple with the sampler of the distribution and its probability density function.
+pub fn ramp_proposal(a: f64, b: f64) -> (Sampler, Function) {
+    let width = b - a;
+    let sampler: Sampler = Box::new(move |uniform| a + width * (1.0 - uniform()).sqrt()); // Inverse of the cumulative distribution, never exactly `a`
+    let pdf: Function = Box::new(move |x| 2.0 * (x - a) / (width * width.abs()));
+    (sampler, pdf)
+}
+
+/// Performs numerical integration of a complex-valued function of a real variable, using Simpson's 1/3 rule.
+///
+/// The real and imaginary parts are summed together, so the function is evaluated once per point of the `num_intervals` slices.
+///
+/// # Arguments
+///
+/// * `function` - The function to integrate.
+/// * `lower_bound` - The lower bound of the interval.
+/// * `upper_bound` - The upper bound of the interval.
+/// * `num_intervals` - The number of slices of the interval.
+///
+/// # Returns
+///
+/// The approximate value of the integral.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::integrals::integrate_complex;
+/// use rust_math_lib::utils::complex::Complex;
+///
+/// // Fourier coefficient of a cosine
+/// let k = 3.0;
+/// let result = integrate_complex(&|x| Complex::from_angle(-k * x) * (k * x).cos(), 0.0, 2.0 * std::f64::consts::PI, 1e3 as u64);
+/// assert!((result - Complex::new(std::f64::consts::PI, 0.0)).abs() < 1e-9);
+/// ```
+pub fn integrate_complex(
+    function: &dyn Fn(f64) -> Complex,
+    lower_bound: f64,
+    upper_bound: f64,
+    num_intervals: u64,
+) -> Complex {
+    Integral::simpson_sum(
+        SummationStrategy::Naive,
+        lower_bound,
+        upper_bound,
+        num_intervals,
+        function,
+    )
+}
+
+/// Performs numerical integration of a curve defined implicitly by `g(x, y) = 0`, i.e. of the function `y(x)` solving the equation.
+///
+/// The function is integrated with the midpoint rule, so it is never solved at the bounds, where implicit curves often have vertical tangents, e.g. a circle.
+/// At each midpoint, the equation is solved for `y` with `roots::find_bracket` and `roots::bisection`, starting from the solution at the previous midpoint, or from `y = 0` at the first one.
+/// The search steps away from the starting point by `h`, upward first, so the solution followed is the one nearest to `y = 0` at the first midpoint, preferring the upper branch at equal distance,
+/// and then the branch continuing from it: `h` must be smaller than the distance between the branches, e.g. between the upper and lower halves of a circle.
+///
+/// # Arguments
+///
+/// * `g` - The function defining the curve.
+/// * `a` - The lower limit of the integration.
+/// * `b` - The upper limit of the integration.
+/// * `n` - The number of slices of the interval.
+/// * `h` - The step of the search of the solution.
+///
+/// # Returns
+///
+/// The approximate value of the integral, or `NaN` if the equation has no solution within `10000 * h` of the starting point at some midpoint.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::integrals::integrate_implicit;
+///
+/// // Area under the line x + y = 2, over [0, 2]
+/// let result = integrate_implicit(&|x, y| x + y - 2.0, 0.0, 2.0, 100, 0.1);
+/// assert!((result - 2.0).abs() < 1e-9);
+/// ```
+pub fn integrate_implicit(g: &dyn Fn(f64, f64) -> f64, a: f64, b: f64, n: u64, h: f64) -> f64 {
+    let previous = std::cell::Cell::new(0.0);
+    Integral::midpoint_sum(SummationStrategy::Naive, a, b, n.max(1), |x| {
+        let function = |y| g(x, y);
+        let y = roots::find_bracket(&function, previous.get(), h, IMPLICIT_MAX_STEPS)
+            .and_then(|(low, high)| roots::bisection(&function, low, high, 0.0))
+            .unwrap_or(f64::NAN);
+        if y.is_finite() {
+            previous.set(y);
+        }
+        y
+    })
+}
+
+/// Computes a quantile of a distribution given by its density, i.e. the `x` where the integral of the density from `a` to `x` is the fraction `q` of its integral over `[a, b]`.
+///
+/// The density need not be normalised, but must not be negative, so that its cumulative integral increases with `x`.
+/// The interval is split into `n` slices, integrated with Simpson's 1/3 rule in two streaming passes:
+/// the first one sums the whole integral, the second one stops at the slice where the cumulative integral reaches the fraction `q` of it.
+/// The quantile is then found within that slice with `roots::bisection`, down to the rounding error.
+///
+/// # Arguments
+///
+/// * `density` - The density of the distribution.
+/// * `a` - The lower bound of the support of the distribution.
+/// * `b` - The upper bound of the support of the distribution.
+/// * `q` - The fraction of the distribution below the quantile, between `0` and `1`, e.g. `0.5` for the median.
+/// * `n` - The number of slices of the interval.
+///
+/// # Returns
+///
+/// The approximate quantile, or `RootError::InvalidQuantile` if `q` is not between `0` and `1`, or `RootError::NonPositiveMass` if the integral of the density is not positive.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::integrals::{Function, quantile};
+///
+/// // Median of the exponential distribution, ln(2)
+/// let density: Function = Box::new(|x| (-x).exp());
+/// let median = quantile(&density, 0.0, 50.0, 0.5, 1e4 as u64).unwrap();
+/// assert!((median - 2f64.ln()).abs() < 1e-9);
+/// ```
+pub fn quantile(
+    density: &Function,
+    a: f64,
+    b: f64,
+    q: f64,
+    n: u64,
+) -> Result<f64, roots::RootError> {
+    let n = n.max(1);
+    let width = (b - a) / n as f64;
+    let slice = |i: u64| (a + i as f64 * width, a + (i + 1) as f64 * width);
+    let slice_integral = |(start, end): (f64, f64)| {
+        Integral::simpson_sum(SummationStrategy::Naive, start, end, 1, density)
+    };
+
+    // Same summation as in the search below, so that the last slice reaches the total exactly
+    let total: f64 = (0..n).map(|i| slice_integral(slice(i))).sum();
+    if !(0.0..=1.0).contains(&q) {
+        return Err(roots::RootError::InvalidQuantile(q));
+    }
+    if total.is_nan() || total <= 0.0 {
+        return Err(roots::RootError::NonPositiveMass(total));
+    }
+
+    let target = q * total;
+    let mut cumulative = 0.0;
+    for i in 0..n {
+        let (start, end) = slice(i);
+        let next = cumulative + slice_integral((start, end));
+        if next >= target || i == n - 1 {
+            let residual = |x: f64| cumulative + slice_integral((start, x)) - target;
+            return roots::bisection(&residual, start, end, 0.0);
+        }
+        cumulative = next;
+    }
+
+    unreachable!("The last slice always ends the search")
+}
+
+/// Performs numerical integration using the Riemann sum method, splitting the slices into chunks summed in parallel threads.
+///
+/// The partial sums of the chunks are combined in a fixed order, from left to right, once all the threads have finished:
+/// the result is bit-identical from run to run, whatever the scheduling of the threads, which keeps tests and reports reproducible.
+/// It may still differ from `Integral::riemann_integration` by rounding, because the slices are summed in a different grouping, except with a single chunk.
+///
+/// This is a free function, rather than a method of `Integral`, because the function is shared between threads, so it must be `Sync`, which a `Function` is not.
+///
+/// # Arguments
+///
+/// * `function` - The function to integrate.
+/// * `lower_bound` - The lower limit of the integration.
+/// * `upper_bound` - The upper limit of the integration.
+/// * `num_intervals` - The number of slices of the interval.
+/// * `chunks` - The number of chunks, i.e. of threads. It is clamped between `1` and the smaller of `num_intervals` and `MAX_PARALLEL_CHUNKS` (64).
+///
+/// # Returns
+///
+/// The approximate value of the integral.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::integrals::riemann_integration_deterministic_parallel;
+///
+/// let result = riemann_integration_deterministic_parallel(&|x| x * x, 0.0, 3.0, 1e6 as u64, 4);
+/// assert!((result - 9.0).abs() < 1e-4);
+/// ```
+pub fn riemann_integration_deterministic_parallel(
+    function: &(dyn Fn(f64) -> f64 + Sync),
+    lower_bound: f64,
+    upper_bound: f64,
+    num_intervals: u64,
+    chunks: usize,
+) -> f64 {
+    if is_zero_width(lower_bound, upper_bound) {
+        return 0.0; // Zero-width interval, without evaluating the function
+    }
+
+    let num_intervals = num_intervals.max(1);
+    let chunks = (chunks as u64).clamp(1, num_intervals.min(MAX_PARALLEL_CHUNKS));
+    let width = (upper_bound - lower_bound) / num_intervals as f64; // Width of each slice of the interval
+
+    let partial_sums: Vec<f64> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..chunks)
+            .map(|chunk| {
+                let (first, last) = chunk_bounds(chunk, chunks, num_intervals);
+                scope.spawn(move || {
+                    let mut sum = 0.0;
+                    for i in first..last {
+                        sum += function(lower_bound + i as f64 * width) * width;
+                    }
+                    sum
+                })
+            })
+            .collect();
+
+        // Joined in the order the chunks were spawned, not in the order they finish
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("A thread of the integration panicked"))
+            .collect()
+    });
+
+    partial_sums.iter().fold(0.0, |sum, partial| sum + partial)
+}
+
+/// Returns the first and past-the-end slices of the chunk `chunk` of `num_intervals` slices split into `chunks` chunks.
+/// The first `num_intervals % chunks` chunks get one more slice, so the bounds never overflow, unlike `chunk * num_intervals / chunks`.
+fn chunk_bounds(chunk: u64, chunks: u64, num_intervals: u64) -> (u64, u64) {
+    let (chunk_size, remainder) = (num_intervals / chunks, num_intervals % chunks);
+    let start = |chunk: u64| chunk * chunk_size + chunk.min(remainder);
+    (start(chunk), start(chunk + 1))
+}
+
+/// Performs numerical integration over several regions concurrently, using Simpson's 1/3 rule.
+///
+/// The regions are split into consecutive groups, one per available core, each integrated in its own thread, so the number of threads stays bounded however many regions there are.
+/// Only available with the `parallel` feature.
+///
+/// The results are returned per region, without summing them, so that the caller can inspect the contribution of each region, or combine them as it sees fit,
+/// e.g. regions of a larger interval processed in chunks, or the pieces of a distributed computation.
+/// Each result is the same as integrating the region on its own with `Integral::simpson_integration_one_third`.
+///
+/// As `riemann_integration_deterministic_parallel`, this is a free function because the function is shared between threads, so it must be `Sync`.
+///
+/// # Arguments
+///
+/// * `function` - The function to integrate.
+/// * `regions` - The regions, as `(start, end, num_intervals)` tuples. A region with zero intervals is integrated with one.
+///
+/// # Returns
+///
+/// The approximate values of the integrals over the regions, in the order of `regions`.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::integrals::integrate_regions_parallel;
+///
+/// let results = integrate_regions_parallel(&|x| x * x, &[(0.0, 1.0, 10), (1.0, 3.0, 10)]);
+/// assert!((results[0] - 1.0 / 3.0).abs() < 1e-12);
+/// assert!((results.iter().sum::<f64>() - 9.0).abs() < 1e-12);
+/// ```
+#[cfg(feature = "parallel")]
+pub fn integrate_regions_parallel(
+    function: &(dyn Fn(f64) -> f64 + Sync),
+    regions: &[(f64, f64, u64)],
+) -> Vec<f64> {
+    let workers = std::thread::available_parallelism().map_or(1, |cores| cores.get());
+    let group_size = regions.len().div_ceil(workers).max(1);
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = regions
+            .chunks(group_size)
+            .map(|group| {
+                scope.spawn(move || {
+                    group
+                        .iter()
+                        .map(|&(start, end, num_intervals)| {
+                            Integral::simpson_sum(
+                                SummationStrategy::Naive,
+                                start,
+                                end,
+                                num_intervals.max(1),
+                                function,
+                            )
+                        })
+                        .collect::<Vec<f64>>()
+                })
+            })
+            .collect();
+
+        // Joined in the order of the groups, so the results keep the order of the regions
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("A thread of the integration panicked"))
+            .collect()
+    })
+}
+
+/// Computes the quantile of the standard normal distribution at probability `p`, i.e. the `z` such that `P(Z ≤ z) = p`.
+///
+/// Uses the rational approximation of P. J. Acklam, with a relative error below `1.2e-9`.
+/// Returns `NaN` if `p` is not strictly between `0` and `1`.
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    if !(p > 0.0 && p < 1.0) {
+        return f64::NAN;
+    }
+
+    // Tails, where the quantile grows as sqrt(-2 ln p)
+    let tail = |p: f64| {
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < P_LOW {
+        return tail(p);
+    }
+    if p > 1.0 - P_LOW {
+        return -tail(1.0 - p);
+    }
+
+    let q = p - 0.5;
+    let r = q * q;
+    (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+        / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+}
+
+/// Computes the nodes and weights of the Gauss-Legendre rule with `order` nodes, on `[-1, 1]`.
+///
+/// The nodes are the roots of the Legendre polynomial of degree `order`, found with Newton's method from Chebyshev-like initial guesses.
+fn gauss_legendre_rule(order: usize) -> Vec<(f64, f64)> {
+    (0..order)
+        .map(|i| {
+            let mut x = (std::f64::consts::PI * (i as f64 + 0.75) / (order as f64 + 0.5)).cos();
+            let mut derivative = 0.0;
+
+            for _ in 0..100 {
+                // Legendre polynomials of degree `order` and `order - 1` at `x`, by their three-term recurrence
+                let (mut p, mut p_previous) = (1.0, 0.0);
+                for k in 1..=order {
+                    let p_next =
+                        ((2 * k - 1) as f64 * x * p - (k - 1) as f64 * p_previous) / k as f64;
+                    p_previous = p;
+                    p = p_next;
+                }
+                derivative = order as f64 * (x * p - p_previous) / (x * x - 1.0);
+
+                let step = p / derivative;
+                x -= step;
+                if step.abs() <= f64::EPSILON {
+                    break;
+                }
+            }
+
+            (x, 2.0 / ((1.0 - x * x) * derivative * derivative))
+        })
+        .collect()
+}
+
+// ---- Tests ---- //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::colours::*;
+
+    /// Helper function to test integration with common logic.
+    fn test_integration(
+        function: fn(f64) -> f64,
+        lower_bound: f64,
+        upper_bound: f64,
+        num_intervals: u32,
+        expected: f64,
+        error_tolerance: f64,
+    ) {
+        let mut integral = Integral::new(
+            Box::new(function),
+            lower_bound,
+            upper_bound,
+            num_intervals as u64,
+        );
+        let results_vec = vec![
+            ("Riemann", integral.riemann_integration()),
+            ("Simpson's 1/3", integral.simpson_integration_one_third()),
+        ];
+
+        for (method_name, result) in results_vec {
+            let delta = (result - expected).abs();
+            println!(
+                "Method: {}\n  {}Result{}:    {}\n  {}Expected{}:  {}\n  {}Tolerance{}: {}\n  {}Delta{}:     {}",
+                method_name,
+                CYAN,
+                RESET,
+                result,
+                YELLOW,
                 RESET,
                 expected,
                 GREEN,
@@ -133,63 +3621,1630 @@ mod tests {
             );
 
             assert!(
-                delta < error_tolerance,
-                "Test failed for {} method",
-                method_name
+                delta < error_tolerance,
+                "Test failed for {} method",
+                method_name
+            );
+        }
+    }
+
+    #[test]
+    fn test_integrate_square() {
+        test_integration(|x| x * x, 0.0, 3.0, 1e7 as u32, 9.0, 1e-5);
+    }
+
+    #[test]
+    fn test_integrate_sine() {
+        test_integration(
+            |x| x.sin(),
+            0.0,
+            std::f64::consts::PI,
+            1e7 as u32,
+            2.0,
+            1e-5,
+        );
+    }
+
+    #[test]
+    fn test_integrate_sine_reverse() {
+        test_integration(
+            |x| x.sin(),
+            std::f64::consts::PI,
+            0.0,
+            1e7 as u32,
+            -2.0,
+            1e-5,
+        );
+    }
+
+    #[test]
+    fn test_integrate_custom_function_1() {
+        test_integration(
+            |x| ((x + 2.0).powi(2) + 3.0 * (4.0 * x).sin()) / (x + 4.0),
+            -1.0,
+            3.0,
+            1e7 as u32,
+            7.15492507558,
+            1e-5,
+        );
+    }
+
+    #[test]
+    fn test_integrate_custom_function_2() {
+        test_integration(
+            |x| ((0.5 * x + 3.0).ln() / (-x).cos()).sqrt(),
+            -1.5,
+            1.4,
+            1e7 as u32,
+            4.00152756063,
+            1e-5,
+        );
+    }
+
+    #[test]
+    fn test_weighted_moments_uniform_density() {
+        let mut integral = Integral::new(Box::new(|_| 1.0), 0.0, 1.0, 1e6 as u64);
+
+        // The k-th raw moment of the uniform density over [0, 1] is 1 / (k + 1)
+        for k in 0..=2 {
+            let expected = 1.0 / (k as f64 + 1.0);
+            let result = integral.weighted_moment(k);
+            assert!(
+                (result - expected).abs() < 1e-5,
+                "Moment of order {} is {}, expected {}",
+                k,
+                result,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_integrate_with_linear_substitution() {
+        // Integral of x^2 over [1, 3] with x = 2u + 1, u in [0, 1]
+        let u_of_x: Function = Box::new(|x| (x - 1.0) / 2.0);
+        let x_of_u: Function = Box::new(|u| 2.0 * u + 1.0);
+        let dx_du: Function = Box::new(|_| 2.0);
+        let result = Integral::new(Box::new(|x| x * x), 1.0, 3.0, 1e5 as u64)
+            .integrate_with_substitution(&u_of_x, &x_of_u, &dx_du, (0.0, 1.0));
+
+        assert!((result - 26.0 / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_integrate_with_sqrt_substitution() {
+        // Integral of 1 / sqrt(x) over [0, 1], singular at x = 0, with x = u^2, u in [0, 1]
+        let u_of_x: Function = Box::new(|x| x.sqrt());
+        let x_of_u: Function = Box::new(|u| u * u);
+        let dx_du: Function = Box::new(|u| 2.0 * u);
+        let result = Integral::new(Box::new(|x| 1.0 / x.sqrt()), 0.0, 1.0, 1e3 as u64)
+            .integrate_with_substitution(&u_of_x, &x_of_u, &dx_du, (0.0, 1.0));
+
+        assert!(result.is_finite());
+        assert!((result - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_reference_error_decreases_with_intervals() {
+        let errors: Vec<f64> = [1e2, 1e3, 1e4]
+            .iter()
+            .map(|&num_intervals| {
+                Integral::new(Box::new(|x| x.exp()), 0.0, 1.0, num_intervals as u64)
+                    .reference_error(1e6 as u64)
+            })
+            .collect();
+
+        assert!(
+            errors.windows(2).all(|pair| pair[1] < pair[0]),
+            "Reference errors do not decrease: {:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_adaptive_simpson_grid_denser_near_peak() {
+        let peak = 0.3;
+        let (result, grid) = Integral::new(
+            Box::new(move |x: f64| (-1000.0 * (x - peak).powi(2)).exp()),
+            0.0,
+            1.0,
+            1,
+        )
+        .adaptive_simpson_with_grid(1e-10);
+
+        let expected = (std::f64::consts::PI / 1000.0).sqrt();
+        assert!((result - expected).abs() < 1e-8);
+        assert!(grid.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // Count the grid points in two regions of equal width, around the peak and where the function is flat
+        let count_in =
+            |start: f64, end: f64| grid.iter().filter(|&&x| x >= start && x <= end).count();
+        let near_peak = count_in(peak - 0.1, peak + 0.1);
+        let flat = count_in(0.7, 0.9);
+        assert!(
+            near_peak > 4 * flat,
+            "Grid points near the peak: {}, in the flat region: {}",
+            near_peak,
+            flat
+        );
+    }
+
+    #[test]
+    fn test_integrate_with_extrema_sine() {
+        let (result, min, max) =
+            Integral::new(Box::new(|x| x.sin()), 0.0, std::f64::consts::PI, 1e6 as u64)
+                .integrate_with_extrema();
+
+        assert!((result - 2.0).abs() < 1e-5);
+        assert!(min.abs() < 1e-9, "Minimum: {}", min);
+        assert!((max - 1.0).abs() < 1e-9, "Maximum: {}", max);
+    }
+
+    #[test]
+    fn test_monte_carlo_integration() {
+        let mut integral = Integral::new(Box::new(|x| x.sin()), 0.0, std::f64::consts::PI, 1);
+        let result = integral.monte_carlo_integration(1e6 as u64, 2024);
+
+        assert!((result - 2.0).abs() < 1e-2, "Result: {}", result);
+        // The same seed gives the same result
+        assert_eq!(result, integral.monte_carlo_integration(1e6 as u64, 2024));
+    }
+
+    /// Helper function returning irregularly spaced samples of `sin` over [0, pi].
+    fn irregular_sine_samples(num_points: usize) -> (Vec<f64>, Vec<f64>) {
+        let xs: Vec<f64> = (0..num_points)
+            .map(|i| std::f64::consts::PI * (i as f64 / (num_points - 1) as f64).powf(1.5))
+            .collect();
+        let ys = xs.iter().map(|x| x.sin()).collect();
+        (xs, ys)
+    }
+
+    #[test]
+    fn test_simpson_irregular_beats_trapezoidal() {
+        for num_points in [21, 22] {
+            let (xs, ys) = irregular_sine_samples(num_points);
+            let trapezoidal: f64 = (0..xs.len() - 1)
+                .map(|i| (xs[i + 1] - xs[i]) * (ys[i] + ys[i + 1]) / 2.0)
+                .sum();
+            let simpson = simpson_irregular(&xs, &ys).unwrap();
+
+            let trapezoidal_error = (trapezoidal - 2.0).abs();
+            let simpson_error = (simpson - 2.0).abs();
+            assert!(
+                simpson_error < trapezoidal_error / 10.0,
+                "{} points: Simpson error {}, trapezoidal error {}",
+                num_points,
+                simpson_error,
+                trapezoidal_error
+            );
+        }
+    }
+
+    #[test]
+    fn test_simpson_irregular_errors() {
+        assert_eq!(
+            simpson_irregular(&[0.0, 1.0], &[0.0, 1.0]),
+            Err(IntegrationError::NotEnoughPoints {
+                required: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            simpson_irregular(&[0.0, 1.0, 2.0], &[0.0, 1.0]),
+            Err(IntegrationError::LengthMismatch { xs: 3, ys: 2 })
+        );
+        assert_eq!(
+            simpson_irregular(&[0.0, 2.0, 1.0], &[0.0, 1.0, 2.0]),
+            Err(IntegrationError::UnsortedAbscissae(2))
+        );
+    }
+
+    #[test]
+    fn test_validate_integrand_clean_function() {
+        let integral = Integral::new(Box::new(|x| x.sin()), 0.0, std::f64::consts::PI, 1e6 as u64);
+        assert_eq!(integral.validate_integrand(101), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_integrand_tangent_pole() {
+        let integral = Integral::new(Box::new(|x| x.tan()), 0.0, std::f64::consts::PI, 1e6 as u64);
+        assert_eq!(
+            integral.validate_integrand(5),
+            Err(IntegrationError::NonFiniteIntegrand(vec![
+                std::f64::consts::FRAC_PI_2
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_validate_integrand_large_finite_values() {
+        let integral = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 40.0, 1e6 as u64);
+        assert_eq!(integral.validate_integrand(11), Ok(()));
+
+        let integral = Integral::new(Box::new(|x: f64| x.powi(10)), 0.0, 100.0, 1e6 as u64);
+        assert_eq!(integral.validate_integrand(11), Ok(()));
+    }
+
+    #[test]
+    fn test_riemann_integration_vector_valued() {
+        let (lower_bound, upper_bound, num_intervals) = (0.0, 2.0, 1e6 as u64);
+        let result = Integral::new(
+            Box::new(|x: f64| [x.sin(), x * x]),
+            lower_bound,
+            upper_bound,
+            num_intervals,
+        )
+        .riemann_integration();
+
+        let sine = Integral::new(
+            Box::new(|x: f64| x.sin()),
+            lower_bound,
+            upper_bound,
+            num_intervals,
+        )
+        .riemann_integration();
+        let square = Integral::new(Box::new(|x| x * x), lower_bound, upper_bound, num_intervals)
+            .riemann_integration();
+        assert!((result[0] - sine).abs() < 1e-12);
+        assert!((result[1] - square).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_centroid_rectangle() {
+        // Rectangle [1, 3] x [0, 2], centred in (2, 1)
+        let (x, y) = Integral::new(Box::new(|_| 2.0), 1.0, 3.0, 1e3 as u64).centroid();
+        assert!((x - 2.0).abs() < 1e-12, "x: {}", x);
+        assert!((y - 1.0).abs() < 1e-12, "y: {}", y);
+    }
+
+    #[test]
+    fn test_centroid_zero_area() {
+        let (x, y) = Integral::new(Box::new(|_| 0.0), 0.0, 1.0, 1e3 as u64).centroid();
+        assert!(x.is_nan() && y.is_nan());
+
+        // Areas above and below the x-axis cancel out, up to the rounding errors
+        let (x, y) = Integral::new(Box::new(|x: f64| x.sin()), -1.0, 1.0, 1e3 as u64).centroid();
+        assert!(x.is_nan() && y.is_nan());
+    }
+
+    #[test]
+    fn test_volume_of_revolution_cylinder() {
+        let (radius, height) = (1.5, 4.0);
+        let volume = Integral::new(Box::new(move |_| radius), 0.0, height, 1e3 as u64)
+            .volume_of_revolution_x();
+        assert!((volume - std::f64::consts::PI * radius * radius * height).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_volume_of_revolution_cone() {
+        // Cone of radius 1 and height 1
+        let volume = Integral::new(Box::new(|x| x), 0.0, 1.0, 1e3 as u64).volume_of_revolution_x();
+        assert!((volume - std::f64::consts::PI / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_zero_width_interval() {
+        // So many intervals that looping over them would not end in a reasonable time, and a singularity at the point
+        let mut integral = Integral::new(Box::new(|x| 1.0 / x), 0.0, 0.0, u64::MAX);
+        let start = std::time::Instant::now();
+
+        assert_eq!(integral.riemann_integration(), 0.0);
+        assert_eq!(integral.simpson_integration_one_third(), 0.0);
+        assert_eq!(integral.monte_carlo_integration(u64::MAX, 1), 0.0);
+        assert_eq!(integral.weighted_moment(2), 0.0);
+        assert_eq!(integral.volume_of_revolution_x(), 0.0);
+        assert_eq!(integral.adaptive_simpson_with_grid(1e-9), (0.0, vec![0.0]));
+        assert_eq!(integral.integrate_with_extrema().0, 0.0);
+
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_adaptive_simpson_converges() {
+        let result = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 1.0, 1)
+            .adaptive_simpson(1e-10, MAX_ADAPTIVE_DEPTH)
+            .unwrap();
+        assert!((result - (std::f64::consts::E - 1.0)).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_adaptive_simpson_max_depth_reached() {
+        let spike =
+            |x: f64| (-1e6 * (x - 0.3).powi(2)).exp() + 1.0 / (x - 0.7).abs().sqrt().max(1e-3);
+        match Integral::new(Box::new(spike), 0.0, 1.0, 1).adaptive_simpson(1e-12, 3) {
+            Err(IntegrationError::MaxDepthReached(partial)) => assert!(partial.is_finite()),
+            other => panic!("Expected the maximum depth to be reached, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_integrate_complex() {
+        let result = integrate_complex(&Complex::from_angle, 0.0, std::f64::consts::PI, 1e3 as u64);
+        let expected =
+            (Complex::from_angle(std::f64::consts::PI) - Complex::new(1.0, 0.0)) / Complex::I;
+        assert!((result - expected).abs() < 1e-12);
+
+        // Three evaluations per slice, shared by the real and imaginary parts
+        let evaluations = std::cell::Cell::new(0);
+        let counted = |x: f64| {
+            evaluations.set(evaluations.get() + 1);
+            Complex::from_angle(x)
+        };
+        integrate_complex(&counted, 0.0, std::f64::consts::PI, 100);
+        assert_eq!(evaluations.get(), 300);
+    }
+
+    #[test]
+    fn test_integrate_and_collect() {
+        let mut integral =
+            Integral::new(Box::new(|x: f64| x.sin()), 0.0, std::f64::consts::PI, 1000);
+        let (result, samples) = integral.integrate_and_collect();
+
+        assert_eq!(samples.len(), 2001);
+        assert_eq!(samples.first().unwrap().0, 0.0);
+        assert_eq!(samples.last().unwrap().0, std::f64::consts::PI);
+        assert!((result - integral.simpson_integration_one_third()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_detect_discontinuities() {
+        let step = Integral::new(
+            Box::new(|x: f64| if x < 0.3 { 0.0 } else { 1.0 }),
+            0.0,
+            1.0,
+            1,
+        );
+        let discontinuities = step.detect_discontinuities(16);
+        assert_eq!(discontinuities.len(), 1);
+        assert!((discontinuities[0] - 0.3).abs() < 1e-12);
+
+        let steep = Integral::new(Box::new(|x: f64| (100.0 * (x - 0.3)).tanh()), 0.0, 1.0, 1);
+        assert!(steep.detect_discontinuities(16).is_empty());
+
+        // Reversed bounds
+        let step = Integral::new(
+            Box::new(|x: f64| if x < 0.3 { 0.0 } else { 1.0 }),
+            1.0,
+            0.0,
+            1,
+        );
+        let discontinuities = step.detect_discontinuities(16);
+        assert_eq!(discontinuities.len(), 1);
+        assert!((discontinuities[0] - 0.3).abs() < 1e-12);
+        let smooth = Integral::new(Box::new(|x: f64| x.sin()), std::f64::consts::PI, 0.0, 1);
+        assert!(smooth.detect_discontinuities(10).is_empty());
+
+        // The rounding tolerance is relative: a jump between tiny values is found, and a tiny jump between large values is ignored
+        let tiny_step = Integral::new(
+            Box::new(|x: f64| if x < 0.3 { 1e-13 } else { 2e-13 }),
+            0.0,
+            1.0,
+            1,
+        );
+        assert_eq!(tiny_step.detect_discontinuities(16).len(), 1);
+        let rounding_step = Integral::new(
+            Box::new(|x: f64| if x < 0.3 { 1e6 } else { 1e6 + 1e-3 }),
+            0.0,
+            1.0,
+            1,
+        );
+        assert!(rounding_step.detect_discontinuities(16).is_empty());
+    }
+
+    #[test]
+    fn test_integrate_auto() {
+        let mut smooth = Integral::new(
+            Box::new(|x: f64| x.sin()),
+            0.0,
+            std::f64::consts::PI,
+            1e3 as u64,
+        );
+        let (result, method) = smooth.integrate_auto();
+        assert_eq!(method, "Simpson's 1/3");
+        assert!((result - 2.0).abs() < 1e-12);
+
+        let mut spiky = Integral::new(
+            Box::new(|x: f64| (-1e4 * (x - 0.5).powi(2)).exp()),
+            0.0,
+            1.0,
+            1e3 as u64,
+        );
+        let (result, method) = spiky.integrate_auto();
+        assert_eq!(method, "Adaptive Simpson");
+        assert!((result - std::f64::consts::PI.sqrt() / 100.0).abs() < 1e-9);
+
+        let mut discontinuous = Integral::new(
+            Box::new(|x: f64| if x < 0.3 { 1.0 } else { x }),
+            0.0,
+            1.0,
+            1e3 as u64,
+        );
+        let (result, method) = discontinuous.integrate_auto();
+        assert_eq!(method, "Piecewise Adaptive Simpson");
+        assert!((result - 0.755).abs() < 1e-9);
+
+        // Reversed bounds: the same choices, with the opposite results
+        let mut smooth = Integral::new(
+            Box::new(|x: f64| x.sin()),
+            std::f64::consts::PI,
+            0.0,
+            1e3 as u64,
+        );
+        let (result, method) = smooth.integrate_auto();
+        assert_eq!(method, "Simpson's 1/3");
+        assert!((result + 2.0).abs() < 1e-12);
+
+        discontinuous.lower_bound = 1.0;
+        discontinuous.upper_bound = 0.0;
+        let (result, method) = discontinuous.integrate_auto();
+        assert_eq!(method, "Piecewise Adaptive Simpson");
+        assert!((result + 0.755).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_reconstruct_from_derivative() {
+        let xs: Vec<f64> = (0..=1000).map(|i| i as f64 * 0.003).collect();
+        let dydx: Vec<f64> = xs.iter().map(|x| 2.0 * x).collect();
+
+        // The trapezoidal rule is exact for a linear derivative
+        let ys = reconstruct_from_derivative(&xs, &dydx, 0.0).unwrap();
+        for (x, y) in xs.iter().zip(&ys) {
+            assert!((y - x * x).abs() < 1e-12, "x = {}: {}", x, y);
+        }
+
+        assert_eq!(
+            reconstruct_from_derivative(&[], &[], 0.0),
+            Err(IntegrationError::NotEnoughPoints {
+                required: 1,
+                found: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_polar_area() {
+        use std::f64::consts::PI;
+
+        let disk = polar_area(&|_| 1.0, 0.0, 2.0 * PI, 1e3 as u64);
+        assert!((disk - PI).abs() < 1e-12);
+
+        let cardioid = polar_area(&|theta| 1.0 + theta.cos(), 0.0, 2.0 * PI, 1e3 as u64);
+        assert!((cardioid - 1.5 * PI).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_monte_carlo_antithetic() {
+        let (samples, seed) = (10_000, 7);
+        let exact = std::f64::consts::E - 1.0;
+        let (result, standard_error) = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 1.0, 1)
+            .monte_carlo_antithetic(samples, seed);
+        assert!((result - exact).abs() < 5.0 * standard_error);
+
+        // Standard error of plain Monte Carlo with the same number of evaluations
+        let mut rng = Rng::new(seed);
+        let values: Vec<f64> = (0..samples).map(|_| rng.next_f64().exp()).collect();
+        let mean = values.iter().sum::<f64>() / samples as f64;
+        let variance =
+            values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (samples - 1) as f64;
+        let plain_standard_error = (variance / samples as f64).sqrt();
+
+        assert!(
+            standard_error < plain_standard_error,
+            "Antithetic: {}, plain: {}",
+            standard_error,
+            plain_standard_error
+        );
+    }
+
+    #[test]
+    fn test_monte_carlo_importance() {
+        let (samples, seed) = (10_000, 3);
+        let peaked: Function = Box::new(|x| x.powi(8));
+
+        let (sampler, pdf) = ramp_proposal(0.0, 1.0);
+        let (result, standard_error) =
+            monte_carlo_importance(&peaked, &sampler, &pdf, samples, seed);
+        assert!((result - 1.0 / 9.0).abs() < 5.0 * standard_error);
+
+        let uniform_sampler = |uniform: &mut dyn FnMut() -> f64| uniform();
+        let uniform_pdf: Function = Box::new(|_| 1.0);
+        let (_, uniform_standard_error) =
+            monte_carlo_importance(&peaked, &uniform_sampler, &uniform_pdf, samples, seed);
+        assert!(
+            standard_error < uniform_standard_error,
+            "Ramp: {}, uniform: {}",
+            standard_error,
+            uniform_standard_error
+        );
+    }
+
+    #[test]
+    fn test_sensitivity_to_scaling() {
+        let mut integral =
+            Integral::new(Box::new(|x: f64| x.exp() * x.cos()), -1.0, 2.0, 1e3 as u64);
+        let expected = integral.simpson_integration_one_third();
+
+        for epsilon in [1e-2, 1e-4, 1e-6] {
+            let sensitivity = integral.sensitivity_to_scaling(epsilon);
+            assert!(
+                (sensitivity - expected).abs() < 1e-8 * expected.abs().max(1.0),
+                "epsilon = {}: sensitivity {}, integral {}",
+                epsilon,
+                sensitivity,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_l1_distance() {
+        let sine: Function = Box::new(|x| x.sin());
+        let zero: Function = Box::new(|_| 0.0);
+
+        assert_eq!(l1_distance(&sine, &sine, 0.0, 10.0, 1e3 as u64), 0.0);
+        let distance = l1_distance(&sine, &zero, 0.0, std::f64::consts::PI, 1e3 as u64);
+        assert!((distance - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_num_intervals_clamped() {
+        let integral = Integral::new(Box::new(|x| x), 0.0, 1.0, 1e18 as u64);
+        assert_eq!(integral.num_intervals, MAX_NUM_INTERVALS);
+
+        let integral = Integral::with_max_intervals(Box::new(|x| x), 0.0, 1.0, 5000, 1000);
+        assert_eq!(integral.num_intervals, 1000);
+
+        let integral = Integral::new(Box::new(|x| x), 0.0, 1.0, 5000);
+        assert_eq!(integral.num_intervals, 5000);
+    }
+
+    #[test]
+    fn test_integrate_hybrid_kink() {
+        let kink = 0.37;
+        let expected = (kink * kink + (1.0 - kink) * (1.0 - kink)) / 2.0;
+        let mut integral = Integral::new(Box::new(move |x: f64| (x - kink).abs()), 0.0, 1.0, 10);
+
+        let simpson_error = (integral.simpson_integration_one_third() - expected).abs();
+        let hybrid_error = (integral.integrate_hybrid(1e-6) - expected).abs();
+        assert!(
+            hybrid_error < simpson_error / 100.0,
+            "Hybrid error: {}, Simpson's error: {}",
+            hybrid_error,
+            simpson_error
+        );
+
+        // Reversed bounds: the same accuracy, with the opposite result
+        (integral.lower_bound, integral.upper_bound) = (1.0, 0.0);
+        let reversed_error = (integral.integrate_hybrid(1e-6) + expected).abs();
+        assert!(
+            reversed_error < simpson_error / 100.0,
+            "Reversed error: {}",
+            reversed_error
+        );
+
+        // The summation strategy applies to the Simpson's slices
+        integral.with_summation(SummationStrategy::Kahan);
+        assert!((integral.integrate_hybrid(1e-6) + expected).abs() < simpson_error / 100.0);
+    }
+
+    #[test]
+    fn test_estimate_cost() {
+        let num_intervals = 12345;
+        let evaluations = std::cell::Cell::new(0);
+        let counted = |x: f64| {
+            evaluations.set(evaluations.get() + 1);
+            x.sin()
+        };
+        let cost =
+            Integral::new(Box::new(|x: f64| x.sin()), 0.0, 1.0, num_intervals).estimate_cost();
+
+        for (method, multiplier) in [
+            (IntegrationMethod::Riemann, 1),
+            (IntegrationMethod::Simpson, 3),
+            (IntegrationMethod::MonteCarlo, 1),
+        ] {
+            assert_eq!(cost.evaluations(method), multiplier * num_intervals);
+        }
+
+        // The counts match the evaluations actually performed
+        Integral::simpson_sum(SummationStrategy::Naive, 0.0, 1.0, num_intervals, counted);
+        assert_eq!(
+            evaluations.get(),
+            cost.evaluations(IntegrationMethod::Simpson)
+        );
+        assert!(
+            cost.estimated_time(IntegrationMethod::Simpson)
+                >= cost.estimated_time(IntegrationMethod::Riemann)
+        );
+    }
+
+    #[test]
+    fn test_integral_from_config() {
+        let configs: Vec<IntegralConfig> = (1..=5)
+            .map(|i| IntegralConfig {
+                lower_bound: -(i as f64),
+                upper_bound: i as f64,
+                num_intervals: 10,
+            })
+            .collect();
+
+        for config in configs {
+            let function: Function = Box::new(|x| x * x);
+            let mut integral = Integral::from((function, config));
+            let expected = 2.0 * config.upper_bound.powi(3) / 3.0;
+            assert!((integral.simpson_integration_one_third() - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_refinement_error_trace() {
+        let mut integral = Integral::new(Box::new(|x: f64| x.exp() * x.sin()), 0.0, 2.0, 4);
+        let trace = integral.refinement_error_trace(6);
+
+        assert_eq!(trace.len(), 6);
+        assert!(
+            trace.windows(2).all(|pair| pair[1] < pair[0]),
+            "Trace not decreasing: {:?}",
+            trace
+        );
+    }
+
+    #[test]
+    fn test_line_integral() {
+        // Straight path from (1, 1) to (4, 5)
+        let segment = |t: f64| (1.0 + 3.0 * t, 1.0 + 4.0 * t);
+        let length = line_integral(&|_, _| 1.0, &segment, 0.0, 1.0, 100, 1e-6);
+        assert!((length - 5.0).abs() < 1e-9);
+
+        // Mass of a wire along the same path, with a density growing linearly with x
+        let mass = line_integral(&|x, _| x, &segment, 0.0, 1.0, 100, 1e-6);
+        assert!((mass - 5.0 * 2.5).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_integrate_power() {
+        let mut integral = Integral::new(
+            Box::new(|x: f64| x.sin()),
+            0.0,
+            std::f64::consts::PI,
+            1e3 as u64,
+        );
+        let result = integral.integrate_power(2).unwrap();
+        assert!((result - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        assert_eq!(integral.integrate_power(0), Ok(std::f64::consts::PI));
+
+        let mut crossing = Integral::new(Box::new(|x: f64| x - 0.55), 0.0, 1.0, 10);
+        match crossing.integrate_power(-1) {
+            Err(IntegrationError::ZeroCrossing(x)) => assert!((0.55..=0.6).contains(&x)),
+            other => panic!("Expected a zero crossing, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_integrate_with_shared_cache() {
+        let calls = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = calls.clone();
+        let mut integral = Integral::new(
+            Box::new(move |x: f64| {
+                counter.set(counter.get() + 1);
+                x.exp()
+            }),
+            0.0,
+            1.0,
+            100,
+        );
+        let mut cache = HashMap::new();
+
+        let first = integral.integrate_with_shared_cache(&mut cache);
+        let first_calls = calls.get();
+        assert!((first - (std::f64::consts::E - 1.0)).abs() < 1e-9);
+        assert!(first_calls <= 3 * 100);
+        assert_eq!(cache.len(), first_calls);
+
+        // The second integration is served entirely by the cache
+        let second = integral.integrate_with_shared_cache(&mut cache);
+        assert_eq!(second, first);
+        assert_eq!(calls.get(), first_calls);
+    }
+
+    #[test]
+    fn test_simpson_odd_num_intervals() {
+        for num_intervals in [1, 7, 101] {
+            let result = Integral::new(Box::new(|x: f64| x.powi(3)), 0.0, 2.0, num_intervals)
+                .simpson_integration_one_third();
+            assert!((result - 4.0).abs() < 1e-12);
+        }
+
+        let result = Integral::new(Box::new(|x: f64| x.sin()), 0.0, std::f64::consts::PI, 101)
+            .simpson_integration_one_third();
+        assert!((result - 2.0).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_riemann_integration_deterministic_parallel() {
+        let function = |x: f64| x.sin() * x.exp();
+        let first = riemann_integration_deterministic_parallel(&function, 0.0, 2.0, 100_003, 7);
+        for _ in 0..5 {
+            let again = riemann_integration_deterministic_parallel(&function, 0.0, 2.0, 100_003, 7);
+            assert_eq!(again.to_bits(), first.to_bits());
+        }
+
+        let serial = Integral::new(Box::new(function), 0.0, 2.0, 100_003).riemann_integration();
+        assert!((first - serial).abs() < 1e-12);
+        assert_eq!(
+            riemann_integration_deterministic_parallel(&function, 0.0, 2.0, 100_003, 1),
+            serial
+        );
+
+        // Asking for millions of chunks spawns at most MAX_PARALLEL_CHUNKS threads
+        let many =
+            riemann_integration_deterministic_parallel(&function, 0.0, 2.0, 100_003, 1 << 20);
+        let capped = riemann_integration_deterministic_parallel(
+            &function,
+            0.0,
+            2.0,
+            100_003,
+            MAX_PARALLEL_CHUNKS as usize,
+        );
+        assert_eq!(many.to_bits(), capped.to_bits());
+
+        // The chunks cover all the slices, without overflowing near the limit of u64
+        assert_eq!(chunk_bounds(0, 3, u64::MAX), (0, u64::MAX / 3));
+        assert_eq!(chunk_bounds(2, 3, u64::MAX), (2 * (u64::MAX / 3), u64::MAX));
+        assert_eq!(chunk_bounds(1, 3, 10), (4, 7));
+        assert_eq!(chunk_bounds(2, 3, 10), (7, 10));
+    }
+
+    #[test]
+    fn test_integrate_implicit_circle() {
+        let circle = |x: f64, y: f64| x * x + y * y - 1.0;
+        let result = integrate_implicit(&circle, -1.0, 1.0, 1000, 0.05);
+        assert!(
+            (result - std::f64::consts::FRAC_PI_2).abs() < 1e-4,
+            "Result: {}",
+            result
+        );
+
+        // No solution: the curve does not reach x = 2
+        assert!(integrate_implicit(&circle, 0.0, 2.0, 10, 0.05).is_nan());
+    }
+
+    #[test]
+    fn test_integrate_absolute() {
+        use std::f64::consts::PI;
+
+        let forward = Integral::new(Box::new(|x: f64| x.sin()), 0.0, PI, 100).integrate_absolute();
+        let backward = Integral::new(Box::new(|x: f64| x.sin()), PI, 0.0, 100).integrate_absolute();
+        assert_eq!(forward, backward);
+        assert!((forward - 2.0).abs() < 1e-8);
+
+        // The signed behaviour is unchanged
+        let signed =
+            Integral::new(Box::new(|x: f64| x.sin()), PI, 0.0, 100).simpson_integration_one_third();
+        assert!((signed + 2.0).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_riemann_integration_batched() {
+        /// Squares whole batches, as a vectorised backend would
+        struct Square;
+
+        impl BatchFunction for Square {
+            fn eval_batch(&self, xs: &[f64], out: &mut [f64]) {
+                out.copy_from_slice(xs);
+                out.iter_mut().for_each(|y| *y *= *y);
+            }
+        }
+
+        // Not a multiple of the batch size, to cover a partial last batch
+        for strategy in [
+            SummationStrategy::Naive,
+            SummationStrategy::Kahan,
+            SummationStrategy::Pairwise,
+        ] {
+            let mut integral = Integral::new(Box::new(|x: f64| x * x), 0.0, 3.0, 2500);
+            integral.with_summation(strategy);
+            let batched = integral.riemann_integration_batched(&Square);
+            assert_eq!(batched, integral.riemann_integration());
+            assert!((batched - 9.0).abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_derivative_of_integral_at() {
+        let functions: Vec<(Function, f64)> = vec![
+            (Box::new(|x: f64| x * x), 2.0),
+            (Box::new(|x: f64| x.exp()), 1.5),
+            (Box::new(|x: f64| x.sin()), 0.3),
+            (Box::new(|x: f64| 1.0 / (1.0 + x * x)), -1.0),
+        ];
+        for (f, upper) in &functions {
+            let result = derivative_of_integral_at(f, 0.0, *upper, 1000, 1e-3);
+            assert!(
+                (result - f(*upper)).abs() < 1e-6,
+                "Result: {}, expected: {}",
+                result,
+                f(*upper)
+            );
+        }
+    }
+
+    #[test]
+    fn test_gauss_legendre_adaptive() {
+        use std::f64::consts::PI;
+
+        // A cubic is exact with 2 nodes, confirmed by the rule with 3 nodes
+        let (result, order) = Integral::new(Box::new(|x: f64| x.powi(3) - x), 0.0, 2.0, 1)
+            .gauss_legendre_adaptive(1e-12);
+        assert!((result - 2.0).abs() < 1e-12);
+        assert_eq!(order, 3);
+
+        // A smooth function converges at a low order
+        let (result, order) =
+            Integral::new(Box::new(|x: f64| x.sin()), 0.0, PI, 1).gauss_legendre_adaptive(1e-12);
+        assert!((result - 2.0).abs() < 1e-12);
+        assert!(order <= 12, "Order: {}", order);
+
+        // The rule with 5 nodes is exact up to degree 9, and its weights add up to the width of [-1, 1]
+        let rule = gauss_legendre_rule(5);
+        assert!((rule.iter().map(|(_, w)| w).sum::<f64>() - 2.0).abs() < 1e-14);
+        let moment: f64 = rule.iter().map(|(x, w)| w * x.powi(8)).sum();
+        assert!((moment - 2.0 / 9.0).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_integrate_with_monitor() {
+        let num_intervals = 1000;
+        let mut integral = Integral::new(Box::new(|x: f64| 3.0 * x * x), 0.0, 2.0, num_intervals);
+
+        // Stops halfway: the partial sum is the integral over the first half of the interval
+        let mut processed = 0;
+        let partial = integral.integrate_with_monitor(&mut |i, _| {
+            processed = i + 1;
+            processed < num_intervals / 2
+        });
+        assert_eq!(processed, num_intervals / 2);
+        assert!((partial - 1.0).abs() < 1e-2, "Partial: {}", partial);
+
+        // Never stopped: the same as the Riemann sum
+        let mut calls = 0;
+        let full = integral.integrate_with_monitor(&mut |_, _| {
+            calls += 1;
+            true
+        });
+        assert_eq!(calls, num_intervals);
+        assert_eq!(full, integral.riemann_integration());
+
+        // With compensated summation too
+        integral.with_summation(SummationStrategy::Kahan);
+        let full = integral.integrate_with_monitor(&mut |_, _| true);
+        assert_eq!(full, integral.riemann_integration());
+    }
+
+    #[test]
+    fn test_mean_values_constant() {
+        let mut integral = Integral::new(Box::new(|_| 3.0), -1.0, 4.0, 100);
+        assert!((integral.mean_value() - 3.0).abs() < 1e-12);
+        assert!((integral.geometric_mean_value().unwrap() - 3.0).abs() < 1e-12);
+        assert!((integral.harmonic_mean_value().unwrap() - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_mean_values_ordering() {
+        // For x over [1, 2]: harmonic 1 / ln 2 < geometric 4 / e < arithmetic 3 / 2
+        let mut integral = Integral::new(Box::new(|x| x), 1.0, 2.0, 1000);
+        let arithmetic = integral.mean_value();
+        let geometric = integral.geometric_mean_value().unwrap();
+        let harmonic = integral.harmonic_mean_value().unwrap();
+        assert!((arithmetic - 1.5).abs() < 1e-12);
+        assert!((geometric - 4.0 / std::f64::consts::E).abs() < 1e-12);
+        assert!((harmonic - 1.0 / 2f64.ln()).abs() < 1e-12);
+        assert!(harmonic < geometric && geometric < arithmetic);
+
+        // Crossing zero
+        let mut integral = Integral::new(Box::new(|x| x), -1.0, 2.0, 1000);
+        assert!(matches!(
+            integral.geometric_mean_value(),
+            Err(IntegrationError::ZeroCrossing(_))
+        ));
+        assert!(matches!(
+            integral.harmonic_mean_value(),
+            Err(IntegrationError::ZeroCrossing(_))
+        ));
+    }
+
+    #[test]
+    fn test_convolution_of_boxes() {
+        // The convolution of two unit boxes on [0, 1] is the triangle 1 - |t - 1| on [0, 2]
+        let unit_box = |x: f64| if (0.0..=1.0).contains(&x) { 1.0 } else { 0.0 };
+        let f: Function = Box::new(unit_box);
+        let g: Function = Box::new(unit_box);
+        for t in [0.25, 0.5, 1.0, 1.7] {
+            let result = convolution_at(&f, &g, t, 0.0, 1.0, 1000);
+            let expected = 1.0 - (t - 1.0_f64).abs();
+            assert!(
+                (result - expected).abs() < 1e-3,
+                "Result: {}, expected: {}",
+                result,
+                expected
             );
         }
+        assert_eq!(convolution_at(&f, &g, 2.5, 0.0, 1.0, 1000), 0.0);
     }
 
     #[test]
-    fn test_integrate_square() {
-        test_integration(|x| x * x, 0.0, 3.0, 1e7 as u32, 9.0, 1e-5);
+    fn test_rms_value() {
+        let rms = Integral::new(
+            Box::new(|x: f64| x.sin()),
+            0.0,
+            2.0 * std::f64::consts::PI,
+            1000,
+        )
+        .rms_value();
+        assert!((rms - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-12);
+
+        let rms = Integral::new(Box::new(|_| -4.5), 1.0, 3.0, 10).rms_value();
+        assert!((rms - 4.5).abs() < 1e-12);
     }
 
     #[test]
-    fn test_integrate_sine() {
-        test_integration(
-            |x| x.sin(),
+    #[cfg(feature = "parallel")]
+    fn test_integrate_regions_parallel() {
+        let regions = [
+            (0.0, 0.5, 100),
+            (0.5, 2.0, 301),
+            (2.0, 2.0, 10),
+            (3.0, 2.0, 7),
+        ];
+        let results = integrate_regions_parallel(&|x: f64| x.exp() * x.cos(), &regions);
+
+        assert_eq!(results.len(), regions.len());
+        for (&(start, end, num_intervals), result) in regions.iter().zip(&results) {
+            let serial = Integral::new(
+                Box::new(|x: f64| x.exp() * x.cos()),
+                start,
+                end,
+                num_intervals,
+            )
+            .simpson_integration_one_third();
+            assert_eq!(*result, serial);
+        }
+
+        // Many more regions than cores, and none at all
+        let regions: Vec<(f64, f64, u64)> = (0..1000)
+            .map(|i| (i as f64 / 1000.0, (i + 1) as f64 / 1000.0, 2))
+            .collect();
+        let results = integrate_regions_parallel(&|x: f64| x * x, &regions);
+        assert_eq!(results.len(), regions.len());
+        assert!((results.iter().sum::<f64>() - 1.0 / 3.0).abs() < 1e-12);
+        assert!(integrate_regions_parallel(&|x: f64| x, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_simpson_romberg_smooth() {
+        use std::f64::consts::PI;
+
+        let (result, iterations) = Integral::new(Box::new(|x: f64| x.sin()), 0.0, PI, 4)
+            .simpson_romberg(1e-10, 20)
+            .unwrap();
+        assert!((result - 2.0).abs() < 1e-10);
+        assert!(iterations <= 6, "Iterations: {}", iterations);
+    }
+
+    #[test]
+    fn test_simpson_romberg_non_smooth() {
+        // The kink of |x - 0.37| only lets the error shrink as h^2, far too slowly for the tolerance
+        let mut integral = Integral::new(Box::new(|x: f64| (x - 0.37).abs()), 0.0, 1.0, 1);
+        match integral.simpson_romberg(1e-14, 5) {
+            Err(ConvergenceError::MaxIterationsReached {
+                partial,
+                iterations,
+            }) => {
+                assert!((partial - (0.37_f64.powi(2) + 0.63_f64.powi(2)) / 2.0).abs() < 1e-3);
+                assert_eq!(iterations, 5);
+            }
+            other => panic!("Expected MaxIterationsReached, got {:?}", other),
+        }
+
+        // Reversed strict bounds are not a failure to converge
+        let mut integral = Integral::new(Box::new(|x: f64| x), 1.0, 0.0, 4);
+        integral.with_strict_bounds(true);
+        assert!(matches!(
+            integral.simpson_romberg(1e-10, 20),
+            Err(ConvergenceError::Integration(
+                IntegrationError::ReversedBounds { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_monte_carlo_confidence() {
+        let exact = std::f64::consts::E - 1.0;
+        let mut integral = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 1.0, 1);
+        let (result, (low, high)) = integral.monte_carlo_confidence(10_000, 7, 0.95);
+        assert_eq!(result, integral.monte_carlo_integration(10_000, 7));
+        assert!(low < exact && exact < high, "Interval: [{}, {}]", low, high);
+
+        // A higher confidence widens the interval around the same estimate
+        let (wider_result, (wider_low, wider_high)) =
+            integral.monte_carlo_confidence(10_000, 7, 0.999);
+        assert_eq!(wider_result, result);
+        assert!(wider_low < low && high < wider_high);
+
+        // A large offset shifts the interval without changing its width
+        let (_, (low, high)) = integral.monte_carlo_confidence(10_000, 7, 0.95);
+        let mut shifted = Integral::new(Box::new(|x: f64| 1e9 + x.exp()), 0.0, 1.0, 1);
+        let (_, (shifted_low, shifted_high)) = shifted.monte_carlo_confidence(10_000, 7, 0.95);
+        let (width, shifted_width) = (high - low, shifted_high - shifted_low);
+        assert!(
+            (shifted_width - width).abs() < 1e-3 * width,
+            "Widths: {} vs {}",
+            shifted_width,
+            width
+        );
+
+        assert!((normal_quantile(0.975) - 1.959963985).abs() < 1e-8);
+        assert!((normal_quantile(0.001) + 3.090232306).abs() < 1e-8);
+        assert!(normal_quantile(1.0).is_nan());
+    }
+
+    #[test]
+    fn test_midpoint_integration_endpoint_singularity() {
+        let mut integral = Integral::new(Box::new(|x: f64| x.ln()), 0.0, 1.0, 1e5 as u64);
+        let result = integral.midpoint_integration();
+        assert!(result.is_finite());
+        assert!((result + 1.0).abs() < 1e-4, "Result: {}", result);
+
+        // The Riemann sum hits ln(0)
+        assert_eq!(integral.riemann_integration(), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_integrate_density_adaptive_peak() {
+        // Lorentzian peak of width 0.01 at x = 0.5
+        let peak = |x: f64| 1.0 / (1e-4 + (x - 0.5).powi(2));
+        let exact = 200.0 * 50f64.atan();
+        let num_intervals = 50;
+
+        let uniform =
+            Integral::new(Box::new(peak), 0.0, 1.0, num_intervals).simpson_integration_one_third();
+        let density: Function = Box::new(move |x| peak(x).sqrt());
+        let adaptive = Integral::new(Box::new(peak), 0.0, 1.0, num_intervals)
+            .integrate_density_adaptive(&density)
+            .unwrap();
+        assert!(
+            (adaptive - exact).abs() < (uniform - exact).abs() / 100.0,
+            "Adaptive: {}, uniform: {}, exact: {}",
+            adaptive,
+            uniform,
+            exact
+        );
+
+        // A uniform density gives the uniform grid
+        let density: Function = Box::new(|_| 2.0);
+        let result = Integral::new(Box::new(peak), 0.0, 1.0, num_intervals)
+            .integrate_density_adaptive(&density)
+            .unwrap();
+        assert!((result - uniform).abs() < 1e-9 * uniform);
+
+        let density: Function = Box::new(|x| x - 0.25);
+        assert!(matches!(
+            Integral::new(Box::new(peak), 0.0, 1.0, num_intervals)
+                .integrate_density_adaptive(&density),
+            Err(IntegrationError::NonPositiveDensity(_))
+        ));
+    }
+
+    #[test]
+    fn test_plan_and_integrate() {
+        use std::f64::consts::PI;
+
+        for target in [1e-6, 1e-8, 1e-11] {
+            let (result, num_intervals) =
+                Integral::new(Box::new(|x: f64| x.sin()), 0.0, PI, 1).plan_and_integrate(target);
+            let error = (result - 2.0).abs();
+            // Met, but not by a wide margin, which would mean the integral was over-resolved
+            assert!(error <= target, "Target: {}, error: {}", target, error);
+            assert!(
+                error > target / 10.0,
+                "Target: {}, error: {}",
+                target,
+                error
+            );
+            assert!(num_intervals > 16);
+        }
+
+        // A cubic is integrated exactly by the pilot runs
+        let (result, num_intervals) =
+            Integral::new(Box::new(|x: f64| x.powi(3)), 0.0, 2.0, 1).plan_and_integrate(1e-12);
+        assert!((result - 4.0).abs() < 1e-12);
+        assert_eq!(num_intervals, 16);
+    }
+
+    #[test]
+    fn test_trapezoidal_composite() {
+        let num_intervals = 1000;
+        let calls = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = calls.clone();
+        let mut integral = Integral::new(
+            Box::new(move |x: f64| {
+                counter.set(counter.get() + 1);
+                x.sin()
+            }),
             0.0,
-            std::f64::consts::PI,
-            1e7 as u32,
             2.0,
-            1e-5,
+            num_intervals,
         );
+        let composite = integral.trapezoidal_composite();
+        assert_eq!(calls.get(), num_intervals + 1);
+
+        // Slice by slice, each shared end is evaluated twice
+        calls.set(0);
+        let width = 2.0 / num_intervals as f64;
+        let per_panel: f64 = (0..num_intervals)
+            .map(|i| {
+                let x = i as f64 * width;
+                ((integral.function)(x) + (integral.function)(x + width)) / 2.0 * width
+            })
+            .sum();
+        assert_eq!(calls.get(), 2 * num_intervals);
+
+        assert!((composite - per_panel).abs() < 1e-12);
+        assert!((composite - (1.0 - 2f64.cos())).abs() < 1e-6);
     }
 
     #[test]
-    fn test_integrate_sine_reverse() {
-        test_integration(
-            |x| x.sin(),
-            std::f64::consts::PI,
+    fn test_config_eq() {
+        let sine = Integral::new(Box::new(|x: f64| x.sin()), 0.0, 2.0, 100);
+        let exponential = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 2.0, 100);
+        assert!(sine.config_eq(&exponential));
+        assert!(exponential.config_eq(&sine));
+
+        // Any parameter differing
+        assert!(!sine.config_eq(&Integral::new(Box::new(|x: f64| x.sin()), 0.0, 2.0, 101)));
+        assert!(!sine.config_eq(&Integral::new(Box::new(|x: f64| x.sin()), 0.0, 3.0, 100)));
+        assert!(!sine.config_eq(&Integral::new(Box::new(|x: f64| x.sin()), 1.0, 2.0, 100)));
+
+        // Clamped or defaulted numbers of intervals are compared after clamping
+        let defaulted = Integral::new(Box::new(|x: f64| x), 0.0, 1.0, 0);
+        let explicit = Integral::new(Box::new(|x: f64| x), 0.0, 1.0, 1e6 as u64);
+        assert!(defaulted.config_eq(&explicit));
+        assert_eq!(defaulted.config().num_intervals, 1e6 as u64);
+    }
+
+    #[test]
+    fn test_quantile() {
+        let uniform: Function = Box::new(|_| 0.1);
+        let median = quantile(&uniform, 0.0, 10.0, 0.5, 1000).unwrap();
+        assert!((median - 5.0).abs() < 1e-9);
+        for q in [0.0, 0.1, 0.9, 1.0] {
+            let result = quantile(&uniform, 0.0, 10.0, q, 1000).unwrap();
+            assert!((result - 10.0 * q).abs() < 1e-9, "q: {}", q);
+        }
+
+        // Unnormalised density of the triangular distribution over [0, 1]: the quantile of q is sqrt(q)
+        let triangular: Function = Box::new(|x| 3.0 * x);
+        let result = quantile(&triangular, 0.0, 1.0, 0.25, 100).unwrap();
+        assert!((result - 0.5).abs() < 1e-12);
+
+        assert_eq!(
+            quantile(&uniform, 0.0, 10.0, 1.5, 1000),
+            Err(roots::RootError::InvalidQuantile(1.5))
+        );
+        let zero: Function = Box::new(|_| 0.0);
+        assert_eq!(
+            quantile(&zero, 0.0, 10.0, 0.5, 1000),
+            Err(roots::RootError::NonPositiveMass(0.0))
+        );
+    }
+
+    #[test]
+    fn test_adaptive_simpson_with_effort_cusp() {
+        let cusp = 0.37;
+        let (result, effort) =
+            Integral::new(Box::new(move |x: f64| (x - cusp).abs().sqrt()), 0.0, 1.0, 1)
+                .adaptive_simpson_with_effort(1e-10);
+
+        let expected = 2.0 / 3.0 * (cusp.powf(1.5) + (1.0 - cusp).powf(1.5));
+        assert!((result - expected).abs() < 1e-9);
+
+        // The panels tile the interval
+        assert_eq!(effort.first().unwrap().0, 0.0);
+        assert_eq!(effort.last().unwrap().1, 1.0);
+        assert!(effort.windows(2).all(|pair| pair[0].1 == pair[1].0));
+
+        // The deepest region, i.e. the panels within one halving of the deepest one, is a narrow bracket of the cusp
+        let max_depth = effort.iter().map(|panel| panel.2).max().unwrap();
+        let deepest = effort.iter().filter(|panel| panel.2 + 1 >= max_depth);
+        let (start, end) = deepest
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(start, end), panel| {
+                (start.min(panel.0), end.max(panel.1))
+            });
+        assert!(
+            start <= cusp && cusp <= end,
+            "Deepest region: [{}, {}]",
+            start,
+            end
+        );
+        assert!(end - start < 1e-9, "Deepest region: [{}, {}]", start, end);
+        let flat_depth = effort.iter().find(|panel| panel.0 >= 0.8).unwrap().2;
+        assert!(
+            max_depth > flat_depth + 5,
+            "Depths: {} and {}",
+            max_depth,
+            flat_depth
+        );
+    }
+
+    #[test]
+    fn test_summation_strategies() {
+        use std::f64::consts::PI;
+
+        for strategy in [
+            SummationStrategy::Naive,
+            SummationStrategy::Kahan,
+            SummationStrategy::Pairwise,
+        ] {
+            let mut integral = Integral::new(Box::new(|x: f64| x.sin()), 0.0, PI, 1001);
+            integral.with_summation(strategy);
+            assert!((integral.simpson_integration_one_third() - 2.0).abs() < 1e-12);
+            assert!((integral.trapezoidal_composite() - 2.0).abs() < 1e-5);
+            assert!((integral.midpoint_integration() - 2.0).abs() < 1e-5);
+            assert!((integral.riemann_integration() - 2.0).abs() < 1e-5);
+
+            // Vector-valued functions too
+            let mut integral: Integral<[f64; 2]> =
+                Integral::new(Box::new(|x: f64| [1.0, x]), 0.0, 2.0, 1000);
+            integral.with_summation(strategy);
+            let [area, moment] = integral.riemann_integration();
+            assert!((area - 2.0).abs() < 1e-12 && (moment - 2.0).abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_summation_strategies_variants() {
+        use std::f64::consts::PI;
+
+        for strategy in [SummationStrategy::Kahan, SummationStrategy::Pairwise] {
+            let mut integral = Integral::new(Box::new(|x: f64| x.sin()), 0.0, PI, 1001);
+            integral.with_summation(strategy);
+            let riemann = integral.riemann_integration();
+
+            // The variants of the Riemann sum add the same slices in the same order
+            assert_eq!(integral.integrate_with_monitor(&mut |_, _| true), riemann);
+            assert_eq!(
+                integral.riemann_integration_batched(&|x: f64| x.sin()),
+                riemann
+            );
+            assert_eq!(integral.integrate_with_extrema().0, riemann);
+
+            // Collecting the samples weighs them as Simpson's rule, which is far more accurate than the Riemann sum
+            let (collected, _) = integral.integrate_and_collect();
+            assert!((collected - 2.0).abs() < 1e-12, "Collected: {}", collected);
+            assert!((collected - 2.0).abs() < (riemann - 2.0).abs());
+        }
+    }
+
+    #[test]
+    fn test_integrate_with_lipschitz() {
+        use std::f64::consts::PI;
+
+        let mut integral = Integral::new(Box::new(|x: f64| x.sin()), 0.0, PI, 1000);
+        let (result, lipschitz) = integral.integrate_with_lipschitz(1e-5);
+        assert!((result - 2.0).abs() < 1e-5, "Result: {}", result);
+        assert!((lipschitz - 1.0).abs() < 1e-8, "Lipschitz: {}", lipschitz);
+
+        // A steeper function, with its largest slope at x = 0
+        let mut integral = Integral::new(Box::new(|x: f64| (3.0 * x).sin()), 0.0, 1.0, 1000);
+        let (_, lipschitz) = integral.integrate_with_lipschitz(1e-5);
+        assert!((lipschitz - 3.0).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_piecewise_linear_integral_triangle() {
+        let triangle = [(0.0, 0.0), (1.0, 2.0), (3.0, 0.0)];
+        assert_eq!(piecewise_linear_integral(&triangle), Ok(3.0));
+
+        assert_eq!(
+            piecewise_linear_integral(&[(0.0, 1.0)]),
+            Err(IntegrationError::NotEnoughPoints {
+                required: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            piecewise_linear_integral(&[(0.0, 0.0), (1.0, 1.0), (1.0, 0.0)]),
+            Err(IntegrationError::UnsortedAbscissae(2))
+        );
+    }
+
+    #[test]
+    fn test_piecewise_linear_integral_sawtooth() {
+        // Ten teeth of height 1, rising over 0.75 and dropping over 0.25: half a unit square each
+        let points: Vec<(f64, f64)> = (0..10)
+            .flat_map(|k| [(k as f64, 0.0), (k as f64 + 0.75, 1.0)])
+            .chain([(10.0, 0.0)])
+            .collect();
+        assert_eq!(piecewise_linear_integral(&points), Ok(5.0));
+    }
+
+    #[test]
+    fn test_integrate_budgeted() {
+        // Steep at the upper end, where most of the budget goes
+        let exact = (6f64.exp() - 1.0) / 3.0;
+        for budget in [1e-4, 1e-8] {
+            let mut integral = Integral::new(Box::new(|x: f64| (3.0 * x).exp()), 0.0, 2.0, 1);
+            let (result, errors) = integral.integrate_budgeted(budget, 8);
+            assert_eq!(errors.len(), 8);
+            assert!(errors.iter().sum::<f64>() <= budget, "Errors: {:?}", errors);
+            assert!(
+                (result - exact).abs() <= budget,
+                "Error: {}",
+                (result - exact).abs()
+            );
+        }
+
+        // Zero function: equal shares, met at once
+        let mut integral = Integral::new(Box::new(|_| 0.0), 0.0, 1.0, 1);
+        assert_eq!(integral.integrate_budgeted(1e-6, 3), (0.0, vec![0.0; 3]));
+    }
+
+    #[test]
+    fn test_integrate_curvature_adaptive() {
+        // A corner at x = 0.3, rounded over about 1e-3: strongly curved there, straight elsewhere
+        fn corner(x: f64) -> f64 {
+            ((x - 0.3).powi(2) + 1e-6).sqrt()
+        }
+        let antiderivative = |u: f64| (u * (u * u + 1e-6).sqrt() + 1e-6 * (u / 1e-3).asinh()) / 2.0;
+        let exact = antiderivative(0.7) - antiderivative(-0.3);
+        let tolerance = 1e-6;
+
+        let evaluations = std::rc::Rc::new(std::cell::Cell::new(0));
+        let near_corner = std::rc::Rc::new(std::cell::Cell::new(0));
+        let (total, near) = (evaluations.clone(), near_corner.clone());
+        let mut integral = Integral::new(
+            Box::new(move |x| {
+                total.set(total.get() + 1);
+                if (x - 0.3).abs() < 0.05 {
+                    near.set(near.get() + 1);
+                }
+                corner(x)
+            }),
             0.0,
-            1e7 as u32,
-            -2.0,
-            1e-5,
+            1.0,
+            16,
+        );
+        let result = integral.integrate_curvature_adaptive(tolerance, 1e-4);
+        assert!(
+            (result - exact).abs() <= tolerance,
+            "Error: {}",
+            (result - exact).abs()
+        );
+        // Most of the evaluations are spent on the tenth of the interval around the corner
+        assert!(2 * near_corner.get() > evaluations.get());
+
+        // Uniform Simpson's rule needs more evaluations to meet the tolerance
+        let mut num_intervals = 1;
+        while (Integral::new(Box::new(corner), 0.0, 1.0, num_intervals)
+            .simpson_integration_one_third()
+            - exact)
+            .abs()
+            > tolerance
+        {
+            num_intervals *= 2;
+        }
+        let uniform_evaluations =
+            IntegrationMethod::Simpson.evaluations_per_interval() * num_intervals;
+        assert!(
+            (evaluations.get() as u64) < uniform_evaluations,
+            "Adaptive: {}, uniform: {}",
+            evaluations.get(),
+            uniform_evaluations
         );
     }
 
     #[test]
-    fn test_integrate_custom_function_1() {
-        test_integration(
-            |x| ((x + 2.0).powi(2) + 3.0 * (4.0 * x).sin()) / (x + 4.0),
-            -1.0,
-            3.0,
-            1e7 as u32,
-            7.15492507558,
-            1e-5,
+    fn test_total_variation() {
+        use std::f64::consts::PI;
+
+        let mut integral = Integral::new(Box::new(|x: f64| x.sin()), 0.0, 2.0 * PI, 1000);
+        let variation = integral.total_variation(1e-5);
+        assert!((variation - 4.0).abs() < 1e-6, "Variation: {}", variation);
+
+        // The function is given back after the integration
+        assert!((integral.simpson_integration_one_third()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_last_method() {
+        let mut integral = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 1.0, 100);
+        assert_eq!(integral.last_method(), None);
+        integral.riemann_integration();
+        assert_eq!(integral.last_method(), Some("riemann_integration"));
+        integral.adaptive_simpson(1e-10, 20).unwrap();
+        assert_eq!(integral.last_method(), Some("adaptive_simpson"));
+
+        // Reported by the outer method, not by the one it is built on
+        integral.mean_value();
+        assert_eq!(integral.last_method(), Some("mean_value"));
+    }
+
+    #[test]
+    fn test_strict_bounds() {
+        let mut integral = Integral::new(Box::new(|x: f64| x.exp()), 1.0, 0.0, 100);
+        let expected = 1.0 - std::f64::consts::E;
+
+        // Permissive by default: the integral is negated
+        assert!(integral.check_bounds().is_ok());
+        let result = integral.adaptive_simpson(1e-10, 20).unwrap();
+        assert!((result - expected).abs() < 1e-9);
+        assert!((integral.integrate_power(1).unwrap() - expected).abs() < 1e-9);
+
+        integral.with_strict_bounds(true);
+        let error = IntegrationError::ReversedBounds {
+            lower_bound: 1.0,
+            upper_bound: 0.0,
+        };
+        assert_eq!(integral.check_bounds(), Err(error.clone()));
+        assert_eq!(integral.adaptive_simpson(1e-10, 20), Err(error.clone()));
+        assert_eq!(integral.integrate_power(1), Err(error.clone()));
+        assert_eq!(integral.validate_integrand(11), Err(error.clone()));
+
+        // The core methods return a bare value, so they are checked through try_integrate
+        assert_eq!(
+            integral.try_integrate(Integral::riemann_integration),
+            Err(error.clone())
+        );
+        assert_eq!(
+            integral.try_integrate(Integral::simpson_integration_one_third),
+            Err(error.clone())
+        );
+        assert_eq!(
+            integral.try_integrate(|integral| integral.monte_carlo_integration(1000, 42)),
+            Err(error)
         );
+        integral.with_strict_bounds(false);
+        let result = integral
+            .try_integrate(Integral::simpson_integration_one_third)
+            .unwrap();
+        assert!((result - expected).abs() < 1e-9);
+
+        // Ordered bounds are fine in strict mode
+        let mut integral = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 1.0, 100);
+        integral.with_strict_bounds(true);
+        assert!((integral.adaptive_simpson(1e-10, 20).unwrap() + expected).abs() < 1e-9);
+        let result = integral
+            .try_integrate(Integral::riemann_integration)
+            .unwrap();
+        assert!((result + expected).abs() < 1e-1);
     }
 
     #[test]
-    fn test_integrate_custom_function_2() {
-        test_integration(
-            |x| ((0.5 * x + 3.0).ln() / (-x).cos()).sqrt(),
-            -1.5,
-            1.4,
-            1e7 as u32,
-            4.00152756063,
-            1e-5,
+    fn test_compensated_summation_large_grid() {
+        // The Riemann sum of a constant is exactly n times the area of a slice, up to a single rounding
+        let num_intervals = 1e7 as u64;
+        let exact = num_intervals as f64 * (0.1 / num_intervals as f64);
+        let error = |strategy| {
+            let mut integral = Integral::new(Box::new(|_| 1.0), 0.0, 0.1, num_intervals);
+            integral.with_summation(strategy);
+            (integral.riemann_integration() - exact).abs()
+        };
+
+        let naive = error(SummationStrategy::Naive);
+        let kahan = error(SummationStrategy::Kahan);
+        let pairwise = error(SummationStrategy::Pairwise);
+        assert!(kahan <= naive && pairwise <= naive);
+        assert!(
+            kahan < 1e-16 && pairwise < 1e-15,
+            "Kahan: {:e}, pairwise: {:e}",
+            kahan,
+            pairwise
+        );
+        assert!(naive > 1e-12, "Naive: {:e}", naive);
+    }
+
+    #[test]
+    fn test_integrate_clamped() {
+        // x exceeds 1 over [1, 2]: clamping cuts off the triangle of area 1/2 above it
+        let mut integral = Integral::new(Box::new(|x: f64| x), 0.0, 2.0, 10);
+        let unclamped = integral.simpson_integration_one_third();
+        let clamped = integral.integrate_clamped(-10.0, 1.0);
+        assert!((unclamped - 2.0).abs() < 1e-12);
+        assert!((unclamped - clamped - 0.5).abs() < 1e-12);
+
+        // Swapped limits, and limits never reached
+        assert_eq!(integral.integrate_clamped(1.0, -10.0), clamped);
+        assert_eq!(integral.integrate_clamped(-10.0, 10.0), unclamped);
+    }
+
+    #[test]
+    fn test_riemann_early_exit() {
+        let num_intervals = 1e6 as u64;
+        let calls = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = calls.clone();
+        let mut integral = Integral::new(
+            Box::new(move |x: f64| {
+                counter.set(counter.get() + 1);
+                (-x).exp()
+            }),
+            0.0,
+            100.0,
+            num_intervals,
+        );
+
+        let result = integral.riemann_early_exit(1000, 1e-12);
+        let full = integral.riemann_integration();
+        // The left Riemann sum overestimates by about half a slice width
+        assert!((result - 1.0).abs() < 1e-4, "Result: {}", result);
+        assert!((result - full).abs() < 1e-10);
+        // Stopped around x = 28, where a window of width 0.1 adds less than 1e-12 relatively
+        let early_calls = calls.get() - num_intervals as usize;
+        assert!(
+            early_calls < num_intervals as usize / 3,
+            "Evaluations: {}",
+            early_calls
+        );
+
+        // A function which is zero at the start is not mistaken for a stabilised one
+        let mut late = Integral::new(
+            Box::new(|x: f64| if x < 50.0 { 0.0 } else { 1.0 }),
+            0.0,
+            100.0,
+            1000,
+        );
+        assert!((late.riemann_early_exit(10, 1e-12) - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_integrate_with_dominant_region_peak() {
+        let peak = 0.63;
+        let mut integral = Integral::new(
+            Box::new(move |x: f64| (-1000.0 * (x - peak).powi(2)).exp()),
+            0.0,
+            1.0,
+            1000,
+        );
+        let (result, (start, end)) = integral.integrate_with_dominant_region(10);
+        assert!((result - (std::f64::consts::PI / 1000.0).sqrt()).abs() < 1e-9);
+        assert!(
+            start <= peak && peak <= end,
+            "Dominant region: [{}, {}]",
+            start,
+            end
+        );
+        assert!((end - start - 0.1).abs() < 1e-12);
+
+        // A negative lobe dominates by its absolute value
+        let lobes = |x: f64| (std::f64::consts::PI * x).sin() * if x < 1.0 { 1.0 } else { 3.0 };
+        let mut integral = Integral::new(Box::new(lobes), 0.0, 2.0, 100);
+        let (result, region) = integral.integrate_with_dominant_region(2);
+        assert!((result + 4.0 / std::f64::consts::PI).abs() < 1e-7);
+        assert_eq!(region, (1.0, 2.0));
+    }
+
+    #[test]
+    fn test_integrate_with_dominant_region_uneven_bins() {
+        let evaluations = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = std::rc::Rc::clone(&evaluations);
+        let mut integral = Integral::new(
+            Box::new(move |x: f64| {
+                counter.set(counter.get() + 1);
+                x * x
+            }),
+            0.0,
+            3.0,
+            10,
         );
+
+        // 3 bins do not divide 10 intervals: the last bin takes the remainder, and Simpson's rule evaluates 3 points per interval
+        let (result, _) = integral.integrate_with_dominant_region(3);
+        assert!((result - 9.0).abs() < 1e-12);
+        assert_eq!(evaluations.get(), 30);
+
+        // More bins than intervals: each bin still gets an interval
+        integral.num_intervals = 2;
+        let (result, region) = integral.integrate_with_dominant_region(5);
+        assert!((result - 9.0).abs() < 1e-12);
+        assert!((region.0 - 2.4).abs() < 1e-12 && region.1 == 3.0);
     }
 }
@@ -0,0 +1,571 @@
+//! This module implements tools for working with sampled data, i.e. functions known only at a set of points.
+//!
+//! The samples are given as two slices of the same length: the abscissae `xs`, strictly increasing, and the values `ys` of the function at the abscissae.
+
+use std::fmt;
+
+/// Errors that can occur when working with sampled data.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InterpError {
+    /// Fewer points than the method requires were provided.
+    NotEnoughPoints { required: usize, found: usize },
+    /// The abscissae and the ordinates of the samples have different lengths.
+    LengthMismatch { xs: usize, ys: usize },
+    /// The abscissae of the samples are not strictly increasing at the given index.
+    UnsortedAbscissae(usize),
+}
+
+impl fmt::Display for InterpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InterpError::NotEnoughPoints { required, found } => write!(
+                f,
+                "at least {} points are required, but {} were provided",
+                required, found
+            ),
+            InterpError::LengthMismatch { xs, ys } => {
+                write!(f, "{} abscissae and {} ordinates were provided", xs, ys)
+            }
+            InterpError::UnsortedAbscissae(index) => write!(
+                f,
+                "the abscissae are not strictly increasing at index {}",
+                index
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InterpError {}
+
+/// Errors that can occur when fitting a model to sampled data.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FitError {
+    /// Fewer points than the coefficients of the model were provided.
+    NotEnoughPoints { required: usize, found: usize },
+    /// The abscissae and the ordinates of the samples have different lengths.
+    LengthMismatch { xs: usize, ys: usize },
+    /// The samples do not determine the coefficients, e.g. because they have fewer distinct abscissae than the coefficients.
+    Singular,
+}
+
+impl fmt::Display for FitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FitError::NotEnoughPoints { required, found } => write!(
+                f,
+                "at least {} points are required, but {} were provided",
+                required, found
+            ),
+            FitError::LengthMismatch { xs, ys } => {
+                write!(f, "{} abscissae and {} ordinates were provided", xs, ys)
+            }
+            FitError::Singular => write!(f, "the samples do not determine the fit"),
+        }
+    }
+}
+
+impl std::error::Error for FitError {}
+
+/// Relative size of a column of the least-squares problem, after removing its projection on the previous ones, below which `polyfit` considers it dependent on them.
+const FIT_RANK_TOLERANCE: f64 = 1e-12;
+
+/// Checks that the samples have matching lengths, at least `min_points` points and strictly increasing abscissae.
+pub(crate) fn validate_samples(
+    xs: &[f64],
+    ys: &[f64],
+    min_points: usize,
+) -> Result<(), InterpError> {
+    if xs.len() != ys.len() {
+        return Err(InterpError::LengthMismatch {
+            xs: xs.len(),
+            ys: ys.len(),
+        });
+    }
+    if xs.len() < min_points {
+        return Err(InterpError::NotEnoughPoints {
+            required: min_points,
+            found: xs.len(),
+        });
+    }
+    match xs.windows(2).position(|pair| pair[1] <= pair[0]) {
+        Some(index) => Err(InterpError::UnsortedAbscissae(index + 1)),
+        None => Ok(()),
+    }
+}
+
+/// The polynomial interpolating a set of samples, in Newton form.
+///
+/// The polynomial of degree `n - 1` through `n` samples is stored as its divided differences, which are cheap to compute and numerically stable to evaluate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewtonPolynomial {
+    xs: Vec<f64>,           // Abscissae of the samples, the nodes of the Newton basis
+    coefficients: Vec<f64>, // Divided differences f[x0], f[x0, x1], ..., f[x0, ..., xn-1]
+}
+
+impl NewtonPolynomial {
+    /// Creates the polynomial interpolating the samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `xs` - The abscissae of the samples, strictly increasing.
+    /// * `ys` - The values of the function at the abscissae.
+    ///
+    /// # Returns
+    ///
+    /// The interpolating polynomial, or an error if there are no samples, or the abscissae are not strictly increasing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_math_lib::interpolation::NewtonPolynomial;
+    ///
+    /// let polynomial = NewtonPolynomial::new(&[0.0, 1.0, 2.0], &[1.0, 2.0, 5.0]).unwrap(); // x^2 + 1
+    /// assert_eq!(polynomial.evaluate(3.0), 10.0);
+    /// assert_eq!(polynomial.derivative(3.0), 6.0);
+    /// ```
+    pub fn new(xs: &[f64], ys: &[f64]) -> Result<Self, InterpError> {
+        validate_samples(xs, ys, 1)?;
+
+        // Divided differences computed in place, column by column
+        let mut coefficients = ys.to_vec();
+        for level in 1..xs.len() {
+            for i in (level..xs.len()).rev() {
+                coefficients[i] = (coefficients[i] - coefficients[i - 1]) / (xs[i] - xs[i - level]);
+            }
+        }
+
+        Ok(NewtonPolynomial {
+            xs: xs.to_vec(),
+            coefficients,
+        })
+    }
+
+    /// Returns the value of the polynomial at `x`.
+    pub fn evaluate(&self, x: f64) -> f64 {
+        self.evaluate_with_derivative(x).0
+    }
+
+    /// Returns the value of the first derivative of the polynomial at `x`, computed analytically.
+    pub fn derivative(&self, x: f64) -> f64 {
+        self.evaluate_with_derivative(x).1
+    }
+
+    /// Evaluates the polynomial and its first derivative at `x` together, with Horner's scheme on the Newton form.
+    fn evaluate_with_derivative(&self, x: f64) -> (f64, f64) {
+        let last = self.coefficients.len() - 1;
+        let mut value = self.coefficients[last];
+        let mut derivative = 0.0;
+
+        for k in (0..last).rev() {
+            derivative = derivative * (x - self.xs[k]) + value;
+            value = value * (x - self.xs[k]) + self.coefficients[k];
+        }
+
+        (value, derivative)
+    }
+}
+
+/// Computes the integral of the cubic smoothing spline fitted to noisy samples.
+///
+/// The smoothing spline `g` minimises `Σ (y_i - g(x_i))^2 + smoothing * ∫ g''(x)^2 dx`, trading the fidelity to the samples for smoothness:
+/// with `smoothing = 0` it is the natural cubic spline interpolating the samples, and as `smoothing` grows it tends to the least-squares line through them.
+/// The spline is fitted with the algorithm of C. H. Reinsch, "Smoothing by Spline Functions" (1967), and integrated exactly over `[xs[0], xs[xs.len() - 1]]`.
+/// The right amount of smoothing depends on the scale of the data: it is roughly the cube of the distance over which the noise is averaged out, in the units of the abscissae.
+///
+/// # Arguments
+///
+/// * `xs` - The abscissae of the samples, strictly increasing.
+/// * `ys` - The measured values at the abscissae.
+/// * `smoothing` - The weight of the roughness penalty. Negative values are treated as `0`.
+///
+/// # Returns
+///
+/// The integral of the smoothing spline, or an error if there are fewer than three samples, or the abscissae are not strictly increasing.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::interpolation::smoothing_spline_integral;
+///
+/// // A line is fitted exactly, whatever the smoothing
+/// let xs = [0.0, 1.0, 3.0, 4.0];
+/// let ys: Vec<f64> = xs.iter().map(|x| 2.0 * x + 1.0).collect();
+/// let result = smoothing_spline_integral(&xs, &ys, 10.0).unwrap();
+/// assert!((result - 20.0).abs() < 1e-12);
+/// ```
+pub fn smoothing_spline_integral(
+    xs: &[f64],
+    ys: &[f64],
+    smoothing: f64,
+) -> Result<f64, InterpError> {
+    validate_samples(xs, ys, 3)?;
+
+    let (values, second_derivatives) = fit_smoothing_spline(xs, ys, smoothing.max(0.0));
+
+    // Exact integral of the cubic on each slice, from its end values and second derivatives
+    let integral = (0..xs.len() - 1)
+        .map(|i| {
+            let h = xs[i + 1] - xs[i];
+            h * (values[i] + values[i + 1]) / 2.0
+                - h.powi(3) * (second_derivatives[i] + second_derivatives[i + 1]) / 24.0
+        })
+        .sum();
+    Ok(integral)
+}
+
+/// Fits the cubic smoothing spline to the samples, with the algorithm of Reinsch.
+///
+/// # Returns
+///
+/// The values of the spline at the abscissae, and its second derivatives there, which are zero at the ends.
+fn fit_smoothing_spline(xs: &[f64], ys: &[f64], smoothing: f64) -> (Vec<f64>, Vec<f64>) {
+    let n = xs.len();
+    let m = n - 2; // Number of interior points
+    let h: Vec<f64> = xs.windows(2).map(|pair| pair[1] - pair[0]).collect();
+
+    // Column `j` of the n x m matrix Q, for the interior point `j + 1`: nonzero in rows `j`, `j + 1` and `j + 2`
+    let q: Vec<[f64; 3]> = (0..m)
+        .map(|j| [1.0 / h[j], -1.0 / h[j] - 1.0 / h[j + 1], 1.0 / h[j + 1]])
+        .collect();
+
+    // Band of the symmetric pentadiagonal matrix R + smoothing * Q^T Q, with the diagonals at offsets -2..=2 of each row
+    let mut band = vec![[0.0; 5]; m];
+    for j in 0..m {
+        band[j][2] = (h[j] + h[j + 1]) / 3.0 + smoothing * q[j].iter().map(|v| v * v).sum::<f64>();
+        if j + 1 < m {
+            let r = h[j + 1] / 6.0 + smoothing * (q[j][1] * q[j + 1][0] + q[j][2] * q[j + 1][1]);
+            band[j][3] = r;
+            band[j + 1][1] = r;
+        }
+        if j + 2 < m {
+            let r = smoothing * q[j][2] * q[j + 2][0];
+            band[j][4] = r;
+            band[j + 2][0] = r;
+        }
+    }
+    let mut rhs: Vec<f64> = (0..m)
+        .map(|j| q[j][0] * ys[j] + q[j][1] * ys[j + 1] + q[j][2] * ys[j + 2])
+        .collect();
+
+    // Gaussian elimination within the band, without pivoting since the matrix is positive definite
+    for k in 0..m {
+        for i in k + 1..(k + 3).min(m) {
+            let factor = band[i][k + 2 - i] / band[k][2];
+            for j in k..(k + 3).min(m) {
+                band[i][j + 2 - i] -= factor * band[k][j + 2 - k];
+            }
+            rhs[i] -= factor * rhs[k];
+        }
+    }
+    let mut interior = vec![0.0; m];
+    for k in (0..m).rev() {
+        let known: f64 = (k + 1..(k + 3).min(m))
+            .map(|j| band[k][j + 2 - k] * interior[j])
+            .sum();
+        interior[k] = (rhs[k] - known) / band[k][2];
+    }
+
+    // The values are pulled away from the samples by the penalty: g = y - smoothing * Q gamma
+    let mut values = ys.to_vec();
+    for j in 0..m {
+        for (row, weight) in q[j].iter().enumerate() {
+            values[j + row] -= smoothing * weight * interior[j];
+        }
+    }
+    let mut second_derivatives = vec![0.0; n];
+    second_derivatives[1..n - 1].copy_from_slice(&interior);
+
+    (values, second_derivatives)
+}
+
+/// Fits a polynomial of the given degree to the samples, in the least-squares sense.
+///
+/// The polynomial minimises the sum of the squared differences from the samples, so it is a smooth model of noisy data, unlike the interpolating `NewtonPolynomial` which goes through every sample.
+/// The least-squares problem is solved by the QR factorisation of its Vandermonde matrix with Householder reflections, which is more accurate than the normal equations.
+/// Keep the degree low, e.g. below 10, and the abscissae near the origin: the powers of the abscissae grow apart quickly, making the problem ill-conditioned.
+/// Unlike the other functions of this module, the abscissae need not be sorted.
+///
+/// # Arguments
+///
+/// * `xs` - The abscissae of the samples.
+/// * `ys` - The measured values at the abscissae.
+/// * `degree` - The degree of the polynomial.
+///
+/// # Returns
+///
+/// The coefficients of the polynomial, in increasing powers of `x`,
+/// or an error if there are fewer samples than coefficients, the lengths of the slices differ, or there are too few distinct abscissae.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::interpolation::polyfit;
+///
+/// // Line of best fit
+/// let coefficients = polyfit(&[0.0, 1.0, 2.0, 3.0], &[1.1, 2.9, 5.1, 6.9], 1).unwrap();
+/// assert!((coefficients[0] - 1.06).abs() < 1e-12);
+/// assert!((coefficients[1] - 1.96).abs() < 1e-12);
+/// ```
+pub fn polyfit(xs: &[f64], ys: &[f64], degree: usize) -> Result<Vec<f64>, FitError> {
+    let num_coefficients = degree + 1;
+    if xs.len() != ys.len() {
+        return Err(FitError::LengthMismatch {
+            xs: xs.len(),
+            ys: ys.len(),
+        });
+    }
+    if xs.len() < num_coefficients {
+        return Err(FitError::NotEnoughPoints {
+            required: num_coefficients,
+            found: xs.len(),
+        });
+    }
+
+    // Vandermonde matrix, row by row
+    let mut matrix: Vec<Vec<f64>> = xs
+        .iter()
+        .map(|x| (0..num_coefficients).map(|k| x.powi(k as i32)).collect())
+        .collect();
+    let mut rhs = ys.to_vec();
+    let column_norm = |matrix: &[Vec<f64>], k: usize, from: usize| {
+        matrix[from..]
+            .iter()
+            .map(|row| row[k] * row[k])
+            .sum::<f64>()
+            .sqrt()
+    };
+    let original_norms: Vec<f64> = (0..num_coefficients)
+        .map(|k| column_norm(&matrix, k, 0))
+        .collect();
+
+    // Householder reflections, zeroing each column below the diagonal
+    for k in 0..num_coefficients {
+        let norm = column_norm(&matrix, k, k);
+        if norm <= FIT_RANK_TOLERANCE * original_norms[k] {
+            return Err(FitError::Singular);
+        }
+        let alpha = if matrix[k][k] > 0.0 { -norm } else { norm };
+        let mut reflector: Vec<f64> = matrix[k..].iter().map(|row| row[k]).collect();
+        reflector[0] -= alpha;
+        let reflector_norm2: f64 = reflector.iter().map(|v| v * v).sum();
+
+        for j in k..num_coefficients {
+            let dot: f64 = reflector
+                .iter()
+                .zip(&matrix[k..])
+                .map(|(v, row)| v * row[j])
+                .sum();
+            let factor = 2.0 * dot / reflector_norm2;
+            for (v, row) in reflector.iter().zip(&mut matrix[k..]) {
+                row[j] -= factor * v;
+            }
+        }
+        let dot: f64 = reflector.iter().zip(&rhs[k..]).map(|(v, y)| v * y).sum();
+        let factor = 2.0 * dot / reflector_norm2;
+        for (v, y) in reflector.iter().zip(&mut rhs[k..]) {
+            *y -= factor * v;
+        }
+    }
+
+    // Back substitution with the triangular factor
+    let mut coefficients = vec![0.0; num_coefficients];
+    for k in (0..num_coefficients).rev() {
+        let known: f64 = (k + 1..num_coefficients)
+            .map(|j| matrix[k][j] * coefficients[j])
+            .sum();
+        coefficients[k] = (rhs[k] - known) / matrix[k][k];
+    }
+
+    Ok(coefficients)
+}
+
+/// Integrates a polynomial exactly over `[a, b]`, e.g. a fit returned by `polyfit`.
+///
+/// # Arguments
+///
+/// * `coeffs` - The coefficients of the polynomial, in increasing powers of `x`.
+/// * `a` - The lower limit of the integration.
+/// * `b` - The upper limit of the integration.
+///
+/// # Returns
+///
+/// The integral of the polynomial, negative if `b < a`.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::interpolation::integrate_polyfit;
+///
+/// // 1 + 2x + 3x^2 over [0, 2]
+/// assert_eq!(integrate_polyfit(&[1.0, 2.0, 3.0], 0.0, 2.0), 14.0);
+/// ```
+pub fn integrate_polyfit(coeffs: &[f64], a: f64, b: f64) -> f64 {
+    // Antiderivative in Horner form: x * (c0 + x * (c1 / 2 + x * (c2 / 3 + ...)))
+    let antiderivative = |x: f64| {
+        x * coeffs
+            .iter()
+            .enumerate()
+            .rev()
+            .fold(0.0, |sum, (k, c)| sum * x + c / (k + 1) as f64)
+    };
+    antiderivative(b) - antiderivative(a)
+}
+
+// ---- Tests ---- //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_newton_polynomial() {
+        // Exact for a cubic, with unevenly spaced samples
+        let cubic = |x: f64| 2.0 * x.powi(3) - x + 3.0;
+        let xs = [-1.0, 0.5, 2.0, 2.5];
+        let ys: Vec<f64> = xs.iter().map(|&x| cubic(x)).collect();
+        let polynomial = NewtonPolynomial::new(&xs, &ys).unwrap();
+        for x in [-2.0, 0.0, 1.3, 4.0] {
+            assert!((polynomial.evaluate(x) - cubic(x)).abs() < 1e-10);
+            assert!((polynomial.derivative(x) - (6.0 * x * x - 1.0)).abs() < 1e-10);
+        }
+
+        // A single sample gives a constant
+        let constant = NewtonPolynomial::new(&[1.0], &[4.0]).unwrap();
+        assert_eq!(constant.evaluate(7.0), 4.0);
+        assert_eq!(constant.derivative(7.0), 0.0);
+
+        assert_eq!(
+            NewtonPolynomial::new(&[0.0, 0.0], &[1.0, 2.0]),
+            Err(InterpError::UnsortedAbscissae(1))
+        );
+    }
+
+    #[test]
+    fn test_smoothing_spline_interpolates_without_smoothing() {
+        // Integral of the natural cubic spline through the samples, from the classical tridiagonal system of its second derivatives
+        let xs = [0.0, 0.4, 1.0, 1.5, 2.5, 3.0];
+        let ys: Vec<f64> = xs.iter().map(|x: &f64| x.sin() + 0.3 * x).collect();
+        let n = xs.len();
+        let h: Vec<f64> = xs.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        let mut diagonal: Vec<f64> = (1..n - 1).map(|i| (h[i - 1] + h[i]) / 3.0).collect();
+        let mut rhs: Vec<f64> = (1..n - 1)
+            .map(|i| (ys[i + 1] - ys[i]) / h[i] - (ys[i] - ys[i - 1]) / h[i - 1])
+            .collect();
+        for i in 1..n - 2 {
+            let factor = h[i] / 6.0 / diagonal[i - 1];
+            diagonal[i] -= factor * h[i] / 6.0;
+            rhs[i] -= factor * rhs[i - 1];
+        }
+        let mut gamma = vec![0.0; n];
+        for i in (1..n - 1).rev() {
+            gamma[i] = (rhs[i - 1] - h[i] / 6.0 * gamma[i + 1]) / diagonal[i - 1];
+        }
+        let expected: f64 = (0..n - 1)
+            .map(|i| {
+                h[i] * (ys[i] + ys[i + 1]) / 2.0 - h[i].powi(3) * (gamma[i] + gamma[i + 1]) / 24.0
+            })
+            .sum();
+
+        let result = smoothing_spline_integral(&xs, &ys, 0.0).unwrap();
+        assert!(
+            (result - expected).abs() < 1e-12,
+            "Result: {}, expected: {}",
+            result,
+            expected
+        );
+
+        assert_eq!(
+            smoothing_spline_integral(&xs[..2], &ys[..2], 0.0),
+            Err(InterpError::NotEnoughPoints {
+                required: 3,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_smoothing_spline_reduces_noise_sensitivity() {
+        // Deviation of the integral of noisy samples from the one of the clean samples, averaged over several noise draws.
+        // Integrating already averages out most of the noise, so the gain of smoothing is modest, but systematic.
+        let xs: Vec<f64> = (0..41).map(|i| i as f64 * 0.1).collect();
+        let clean: Vec<f64> = xs.iter().map(|x| x.sin()).collect();
+        let deviation = |smoothing: f64| {
+            let reference = smoothing_spline_integral(&xs, &clean, smoothing).unwrap();
+            (1..=20)
+                .map(|seed| {
+                    let mut rng = crate::utils::random::Rng::new(seed);
+                    let noisy: Vec<f64> = clean
+                        .iter()
+                        .map(|y| y + 0.2 * (rng.next_f64() - 0.5))
+                        .collect();
+                    (smoothing_spline_integral(&xs, &noisy, smoothing).unwrap() - reference).abs()
+                })
+                .sum::<f64>()
+                / 20.0
+        };
+
+        let interpolating = deviation(0.0);
+        let smoothed = deviation(1.0);
+        assert!(
+            smoothed < interpolating,
+            "Smoothed: {}, interpolating: {}",
+            smoothed,
+            interpolating
+        );
+    }
+
+    #[test]
+    fn test_polyfit_recovers_polynomial() {
+        // Exact samples of 2 - x + 0.5x^3, fitted with a higher degree than needed
+        let xs: Vec<f64> = (0..20).map(|i| -1.0 + i as f64 * 0.15).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| 2.0 - x + 0.5 * x.powi(3)).collect();
+        let coefficients = polyfit(&xs, &ys, 4).unwrap();
+        for (coefficient, expected) in coefficients.iter().zip([2.0, -1.0, 0.0, 0.5, 0.0]) {
+            assert!(
+                (coefficient - expected).abs() < 1e-12,
+                "Coefficients: {:?}",
+                coefficients
+            );
+        }
+
+        // The integral of the fit is the integral of the polynomial
+        let integral = integrate_polyfit(&coefficients, -1.0, 1.85);
+        let antiderivative = |x: f64| 2.0 * x - x * x / 2.0 + x.powi(4) / 8.0;
+        assert!((integral - (antiderivative(1.85) - antiderivative(-1.0))).abs() < 1e-12);
+        assert!((integrate_polyfit(&coefficients, 1.85, -1.0) + integral).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_polyfit_noisy_data() {
+        // Noise alternating in sign averages out of the fitted line
+        let xs: Vec<f64> = (0..100).map(|i| i as f64 / 10.0).collect();
+        let ys: Vec<f64> = xs
+            .iter()
+            .enumerate()
+            .map(|(i, x)| 3.0 * x + 1.0 + if i % 2 == 0 { 0.1 } else { -0.1 })
+            .collect();
+        let coefficients = polyfit(&xs, &ys, 1).unwrap();
+        assert!((coefficients[1] - 3.0).abs() < 1e-3);
+        assert!((integrate_polyfit(&coefficients, 0.0, 2.0) - 8.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_polyfit_errors() {
+        assert_eq!(
+            polyfit(&[0.0, 1.0], &[0.0, 1.0], 2),
+            Err(FitError::NotEnoughPoints {
+                required: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            polyfit(&[0.0, 1.0, 2.0], &[0.0, 1.0], 1),
+            Err(FitError::LengthMismatch { xs: 3, ys: 2 })
+        );
+        // A parabola through two distinct abscissae is not determined
+        assert_eq!(
+            polyfit(&[0.0, 1.0, 1.0, 0.0], &[0.0, 1.0, 2.0, 3.0], 2),
+            Err(FitError::Singular)
+        );
+    }
+}
@@ -6,5 +6,23 @@ pub mod integrals;
 /// The `derivatives` module provides functions for performing derivative calculations.
 pub mod derivatives;
 
+/// The `applications` module provides applications of integrals and derivatives to physics and other sciences.
+pub mod applications;
+
+/// The `interpolation` module provides functions for working with sampled data.
+pub mod interpolation;
+
+/// The `roots` module provides functions for solving equations numerically.
+pub mod roots;
+
+/// The `self_test` module provides a check of the numerical methods at runtime.
+pub mod self_test;
+
+/// The `viz` module provides text renderings of the results, for the terminal.
+pub mod viz;
+
+/// The `history` module provides an append-only log of the calculations performed.
+pub mod history;
+
 /// Various utilities.
 pub mod utils;
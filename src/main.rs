@@ -8,19 +8,37 @@
 //! - meval: for parsing and evaluating mathematical expressions and numbers scientifically written
 //! - tabled: for displaying results in a table format
 
+mod export;
+mod symbolic;
+
+use std::rc::Rc;
 use std::time::Instant;
 
 use figlet_rs::FIGfont;
 use inquire::error::InquireError;
 use inquire::list_option::ListOption;
 use inquire::validator::{MinLengthValidator, Validation};
-use inquire::{MultiSelect, Select, Text};
-use meval;
-use tabled;
+use inquire::{Confirm, MultiSelect, Select, Text};
 use tabled::{Table, Tabled};
 
-use rust_math_lib::derivatives::Derivative;
-use rust_math_lib::integrals::Integral;
+use rust_math_lib::derivatives::{Derivative, partial_derivative};
+use rust_math_lib::history::{HistoryEntry, append_history, default_history_path, load_history};
+use rust_math_lib::integrals::{Integral, IntegrationMethod, MAX_NUM_INTERVALS};
+use rust_math_lib::self_test::self_test;
+use rust_math_lib::utils::format::{NumberFormat, format_result, round_for_display};
+use rust_math_lib::utils::random::seed_or_time_based;
+
+/// Number of points where the function is checked before integrating it.
+const INTEGRAND_CHECK_SAMPLES: usize = 101;
+
+/// Estimated duration of the integrations, in seconds, beyond which the user is asked to confirm them.
+const CONFIRM_COST_SECONDS: f64 = 2.0;
+
+/// Number of decimals the results are rounded to before being displayed, to hide floating-point noise.
+const DISPLAY_DECIMALS: u32 = 12;
+
+/// Name of the symbolic differentiation in the results, when the function could not be differentiated symbolically and central differences were used instead.
+const SYMBOLIC_FALLBACK: &str = "Symbolic (fallback: Central Difference)";
 
 /// Struct to hold the statistics of the calculations performed.
 /// Used to display the results in a table format.
@@ -32,36 +50,90 @@ use rust_math_lib::integrals::Integral;
 /// - `algorithm`: The name of the algorithm used for the calculation.
 /// - `process_time`: The time taken to perform the calculation.
 /// - `result`: The result of the calculation.
+/// - `number_format`: The notation used to display the result.
+/// - `units`: The optional unit label of the result, displayed after it.
+/// - `order`: The order of accuracy of the algorithm, used to weight the results in `consensus_result`.
 #[derive(Tabled)]
 struct CalculationStats {
     #[tabled(rename = "Algorithm")]
     pub algorithm: String,
     #[tabled(rename = "Process Time")]
     pub process_time: String,
-    #[tabled(rename = "Result")]
+    #[tabled(rename = "Result", display("display_result", self))]
     pub result: f64,
+    #[tabled(skip)]
+    pub number_format: NumberFormat,
+    #[tabled(skip)]
+    pub units: Option<String>,
+    #[tabled(skip)]
+    pub order: u32,
+}
+
+/// Formats the result of a calculation for the table, with the notation chosen by the user.
+/// The result is rounded for display only: the history keeps its full precision.
+fn display_result(result: &f64, stats: &CalculationStats) -> String {
+    let result = format_result(
+        round_for_display(*result, DISPLAY_DECIMALS),
+        stats.number_format,
+    );
+    match &stats.units {
+        Some(units) => format!("{} {}", result, units),
+        None => result,
+    }
 }
 
 /// Main function that serves as the entry point for the CLI application.
 ///
 /// It displays a title and a menu for the user to select between calculations.
 /// Based on the user's selection, it calls the appropriate function to perform the calculation.
+/// With the `--pipe` argument, it reads integrations from the standard input instead, see `run_pipe`.
+/// With the `--self-test` argument, it checks the numerical methods and exits with a non-zero status if any check fails.
 fn main() {
+    if std::env::args().skip(1).any(|arg| arg == "--pipe") {
+        run_pipe();
+        return;
+    }
+    if std::env::args().skip(1).any(|arg| arg == "--self-test") {
+        match self_test() {
+            Ok(()) => println!("All the checks passed."),
+            Err(failures) => {
+                failures.iter().for_each(|failure| eprintln!("{}", failure));
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
     print_title(); // Print the title using FIGlet
     println!("Welcome to the Rust Math CLI!\n");
 
+    // Notation of the results, which can be changed from the settings
+    let mut number_format = NumberFormat::default();
+
     // Display the main menu and get the user's selection
     loop {
         match main_menu().as_str() {
             "Integrals" => {
                 println!("You selected Integrals.");
                 // Call the integrals module or function here
-                call_integrals();
+                call_integrals(number_format);
             }
             "Derivatives" => {
                 println!("You selected Derivatives.");
                 // Call the derivatives module or function here
-                call_derivatives();
+                call_derivatives(number_format);
+            }
+            "Both" => {
+                println!("You selected Both.");
+                call_both(number_format);
+            }
+            "Settings" => {
+                println!("You selected Settings.");
+                number_format = ask_number_format(number_format);
+            }
+            "History" => {
+                println!("You selected History.");
+                call_history();
             }
             "Exit" => {
                 println!("Exiting...");
@@ -89,7 +161,14 @@ fn print_title() {
 /// A string representing the user's selection.
 fn main_menu() -> String {
     // Define the options for the menu
-    let options = vec!["Integrals", "Derivatives", "Exit"];
+    let options = vec![
+        "Integrals",
+        "Derivatives",
+        "Both",
+        "History",
+        "Settings",
+        "Exit",
+    ];
 
     // Ask the user to select an option from the menu
     let selected: Result<&str, InquireError> =
@@ -97,9 +176,7 @@ fn main_menu() -> String {
 
     // Check the result of the selection
     match selected {
-        Ok(choice) => {
-            return String::from(choice);
-        }
+        Ok(choice) => String::from(choice),
         Err(_) => {
             println!("Something went wrong! Exiting...");
             std::process::exit(1);
@@ -107,6 +184,95 @@ fn main_menu() -> String {
     }
 }
 
+/// Validates a function of `x` typed by the user.
+fn expr_validator(input: &str) -> Result<Validation, inquire::CustomUserError> {
+    match input.parse::<meval::Expr>() {
+        Ok(expr) => match expr.bind("x") {
+            Ok(_) => Ok(Validation::Valid),
+            Err(e) => Ok(Validation::Invalid(e.into())),
+        },
+        Err(e) => Ok(Validation::Invalid(e.into())),
+    }
+}
+
+/// Validates a number typed by the user, possibly as an expression such as `pi / 2`.
+fn number_validator(input: &str) -> Result<Validation, inquire::CustomUserError> {
+    match meval::eval_str(input) {
+        Ok(_) => Ok(Validation::Valid),
+        Err(e) => Ok(Validation::Invalid(e.into())),
+    }
+}
+
+/// Validates the number of intervals typed by the user.
+/// Numbers which are not positive, or above `MAX_NUM_INTERVALS`, are rejected rather than clamped, since they are most likely typos.
+fn num_intervals_validator(input: &str) -> Result<Validation, inquire::CustomUserError> {
+    match meval::eval_str(input) {
+        Ok(value) if value >= 1.0 && value <= MAX_NUM_INTERVALS as f64 => Ok(Validation::Valid),
+        Ok(_) => Ok(Validation::Invalid(
+            format!(
+                "The number of intervals must be between 1 and {:e}",
+                MAX_NUM_INTERVALS as f64
+            )
+            .into(),
+        )),
+        Err(e) => Ok(Validation::Invalid(e.into())),
+    }
+}
+
+/// Prompts the user for an optional unit label of the results, e.g. `meters`.
+///
+/// # Returns
+/// The label, or `None` if the user left it empty.
+fn ask_units() -> Option<String> {
+    let units = Text::new("Insert the unit of the result")
+        .with_placeholder("none")
+        .with_help_message("Leave empty for a result without unit")
+        .prompt()
+        .unwrap();
+    let units = units.trim();
+    (!units.is_empty()).then(|| units.to_string())
+}
+
+/// Checks that the function can be integrated over the interval, and lets the user confirm integrations estimated to take long.
+///
+/// # Arguments
+/// * `integral` - The integral about to be computed.
+/// * `methods` - The methods which will integrate it.
+///
+/// # Returns
+/// Whether the integrations should go on: `false` if the function is not finite somewhere, or the user declined a long calculation.
+fn confirm_integration(integral: &Integral, methods: &[IntegrationMethod]) -> bool {
+    // Check the function over the interval, to fail fast instead of after a long integration
+    if let Err(e) = integral.validate_integrand(INTEGRAND_CHECK_SAMPLES) {
+        println!("The function cannot be integrated: {}", e);
+        return false;
+    }
+
+    // Estimate the cost of the integrations, and let the user confirm a long one
+    let cost = integral.estimate_cost();
+    let estimated_time: std::time::Duration = methods
+        .iter()
+        .map(|&method| cost.estimated_time(method))
+        .sum();
+    estimated_time.as_secs_f64() <= CONFIRM_COST_SECONDS
+        || Confirm::new(&format!(
+            "The calculation is estimated to take {:.1?}. Do you want to continue?",
+            estimated_time
+        ))
+        .with_default(true)
+        .prompt()
+        .unwrap()
+}
+
+/// Warns the user when the increment is lost in the rounding of the X coordinate, which silently gives a derivative of 0 or NaN.
+fn warn_step_underflow(derivative: &Derivative) {
+    if derivative.has_step_underflow() {
+        println!(
+            "Warning: the increment is too small relative to the X coordinate, the result is not reliable."
+        );
+    }
+}
+
 /// Prompts the user to ask if they want to perform another calculation.
 ///
 fn ask_for_another_calculation() -> bool {
@@ -116,9 +282,49 @@ fn ask_for_another_calculation() -> bool {
         .with_placeholder("yes/no")
         .prompt()
         .unwrap();
-    match another.to_lowercase().as_str() {
-        "y" | "yes" => return true,
-        _ => return false,
+    matches!(another.to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Prompts the user to choose the notation and the number of significant digits of the results.
+///
+/// # Returns
+/// The chosen notation, or `current` if the user cancels.
+fn ask_number_format(current: NumberFormat) -> NumberFormat {
+    let (current_style, current_digits) = match current {
+        NumberFormat::Fixed(digits) => (0, digits),
+        NumberFormat::Scientific(digits) => (1, digits),
+        NumberFormat::Auto(digits) => (2, digits),
+    };
+
+    let style = Select::new(
+        "Select the notation of the results:",
+        vec!["Fixed", "Scientific", "Auto"],
+    )
+    .with_starting_cursor(current_style)
+    .prompt();
+    let digits = Text::new("Insert the number of significant digits")
+        .with_default(&current_digits.to_string())
+        .with_validator(|input: &str| match input.trim().parse::<usize>() {
+            Ok(digits) if (1..=17).contains(&digits) => Ok(Validation::Valid),
+            _ => Ok(Validation::Invalid(
+                "Please, insert an integer between 1 and 17".into(),
+            )),
+        })
+        .prompt();
+
+    match (style, digits) {
+        (Ok(style), Ok(digits)) => {
+            let digits = digits.trim().parse::<usize>().unwrap();
+            match style {
+                "Fixed" => NumberFormat::Fixed(digits),
+                "Scientific" => NumberFormat::Scientific(digits),
+                _ => NumberFormat::Auto(digits),
+            }
+        }
+        _ => {
+            println!("Settings not changed.");
+            current
+        }
     }
 }
 
@@ -133,31 +339,473 @@ fn get_stats_table(stats: &Vec<CalculationStats>) -> Table {
     table
 }
 
-/// Requests the user to input a function, lower and upper bounds, and the number of intervals for integration.
-/// It then performs numerical integration and prints the result.
-fn call_integrals() {
-    // Expression validator for the function input
-    let expr_validator = |input: &str| match input.parse::<meval::Expr>() {
-        Ok(expr) => match expr.bind("x") {
-            Ok(_) => Ok(Validation::Valid),
-            Err(e) => return Ok(Validation::Invalid(e.into())),
-        },
-        Err(e) => Ok(Validation::Invalid(e.into())),
+/// Asks the user whether to print the results as a Markdown table too, e.g. to paste them into a report.
+fn offer_markdown_export(stats: &[CalculationStats]) {
+    let export = Confirm::new("Do you want to print the results as Markdown?")
+        .with_default(false)
+        .prompt()
+        .unwrap_or(false);
+    if export {
+        println!("\n{}", export::to_markdown(stats));
+    }
+}
+
+/// Runs the integration algorithm called `algorithm`, as named in the menu.
+/// Monte Carlo uses as many samples as intervals, and the given seed.
+fn run_integral_algorithm(integral: &mut Integral, algorithm: &str, seed: u64) -> f64 {
+    match algorithm {
+        "Riemann" => integral.riemann_integration(),
+        "Simpson's 1/3" => integral.simpson_integration_one_third(),
+        "Monte Carlo" => {
+            let samples = integral.num_intervals;
+            integral.monte_carlo_integration(samples, seed)
+        }
+        _ => {
+            println!("Invalid algorithm selected. Using Riemann as default.");
+            integral.riemann_integration()
+        }
+    }
+}
+
+/// Runs the differentiation algorithm called `algorithm`, as named in the menu.
+/// `symbolic` is the derivative found by `symbolic::differentiate`, if any: without it, the symbolic differentiation falls back to central differences.
+fn run_derivative_algorithm(
+    derivative: &mut Derivative,
+    algorithm: &str,
+    symbolic: Option<&dyn Fn(f64) -> f64>,
+) -> f64 {
+    match (algorithm, symbolic) {
+        ("Symbolic", Some(symbolic)) => symbolic(derivative.x_coordinate),
+        ("Symbolic", None) | (SYMBOLIC_FALLBACK, _) => derivative.central_difference(),
+        ("Forward Difference", _) => derivative.forward_difference(),
+        ("Central Difference", _) => derivative.central_difference(),
+        ("Backward Difference", _) => derivative.backward_difference(),
+        _ => {
+            println!("Invalid algorithm selected. Using Forward Difference as default.");
+            derivative.forward_difference()
+        }
+    }
+}
+
+/// Returns the order of accuracy of the algorithm called `algorithm`, as named in the menu: the error decreases as `h^order`, where `h` is the width of the intervals or the increment.
+/// Monte Carlo has no order in this sense, since its error only decreases as the square root of the number of samples, and gets `0`.
+/// The symbolic derivative is exact up to the rounding, so it gets an order above all the numerical algorithms.
+fn algorithm_order(algorithm: &str) -> u32 {
+    match algorithm {
+        "Symbolic" => 8,
+        "Simpson's 1/3" => 4,
+        "Central Difference" | SYMBOLIC_FALLBACK => 2,
+        "Riemann" | "Forward Difference" | "Backward Difference" => 1,
+        _ => 0,
+    }
+}
+
+/// Combines the results of several algorithms into a single best guess, when they disagree slightly.
+///
+/// The results are averaged, each weighted by the order of accuracy of its algorithm, so that higher-order algorithms count more:
+/// e.g. Simpson's 1/3 rule (order 4) weighs four times as much as the Riemann sum (order 1).
+/// Algorithms of order `0`, like Monte Carlo, are left out, unless all the algorithms have order `0`, in which case the plain average is returned.
+///
+/// # Returns
+/// The weighted average of the results, or `NaN` if there are no results.
+fn consensus_result(stats: &[CalculationStats]) -> f64 {
+    let total_order: u32 = stats.iter().map(|stat| stat.order).sum();
+    if total_order == 0 {
+        return stats.iter().map(|stat| stat.result).sum::<f64>() / stats.len() as f64;
+    }
+
+    stats
+        .iter()
+        .map(|stat| stat.order as f64 * stat.result)
+        .sum::<f64>()
+        / total_order as f64
+}
+
+/// Asks the user whether to re-run the calculations with refined parameters, described by `refinement`.
+fn ask_for_refinement(refinement: &str) -> bool {
+    Confirm::new(&format!(
+        "Do you want to refine the results, {}?",
+        refinement
+    ))
+    .with_default(false)
+    .prompt()
+    .unwrap_or(false)
+}
+
+/// Re-runs the integrations of `stats` with twice the number of intervals, capped to `MAX_NUM_INTERVALS`.
+///
+/// # Returns
+/// The previous and refined results of each integration, in the order of `stats`.
+fn refine_integral(
+    integral: &mut Integral,
+    stats: &[CalculationStats],
+    seed: u64,
+) -> Vec<(f64, f64)> {
+    integral.num_intervals = integral
+        .num_intervals
+        .saturating_mul(2)
+        .min(MAX_NUM_INTERVALS);
+    stats
+        .iter()
+        .map(|stat| {
+            (
+                stat.result,
+                run_integral_algorithm(integral, &stat.algorithm, seed),
+            )
+        })
+        .collect()
+}
+
+/// Re-runs the differentiations of `stats` with half the increment.
+/// The symbolic derivative, if any, does not depend on the increment, so it is unchanged.
+///
+/// # Returns
+/// The previous and refined results of each differentiation, in the order of `stats`.
+fn refine_derivative(
+    derivative: &mut Derivative,
+    stats: &[CalculationStats],
+    symbolic: Option<&dyn Fn(f64) -> f64>,
+) -> Vec<(f64, f64)> {
+    derivative.increment /= 2.0;
+    stats
+        .iter()
+        .map(|stat| {
+            (
+                stat.result,
+                run_derivative_algorithm(derivative, &stat.algorithm, symbolic),
+            )
+        })
+        .collect()
+}
+
+/// Differentiates the expression symbolically with respect to `x`, as a function ready to be evaluated.
+///
+/// # Returns
+/// The derivative, or `None` if the expression cannot be differentiated symbolically.
+fn symbolic_derivative(expr: &meval::Expr) -> Option<impl Fn(f64) -> f64 + use<>> {
+    symbolic::differentiate(expr, "x")?
+        .parse::<meval::Expr>()
+        .ok()?
+        .bind("x")
+        .ok()
+}
+
+/// Prints the previous and refined results of each calculation, with the change between them.
+fn print_refinement(stats: &[CalculationStats], refined: &[(f64, f64)]) {
+    println!("\nRefined results:");
+    for (stat, &(previous, result)) in stats.iter().zip(refined) {
+        println!(
+            "{}: {} -> {} (change: {})",
+            stat.algorithm,
+            format_result(previous, stat.number_format),
+            format_result(result, stat.number_format),
+            format_result(result - previous, NumberFormat::Scientific(3))
+        );
+    }
+}
+
+/// Appends an entry to the history file in the home directory of the user.
+/// A failure to write the history is reported, but does not stop the application.
+fn record_history(entry: &HistoryEntry) {
+    if let Some(path) = default_history_path()
+        && let Err(e) = append_history(entry, &path)
+    {
+        println!("Could not write the history file: {}", e);
+    }
+}
+
+/// Displays the calculations stored in the history file and re-runs the one selected by the user.
+fn call_history() {
+    let Some(path) = default_history_path() else {
+        println!("Could not locate the history file.");
+        return;
     };
+    let entries = match load_history(&path) {
+        Ok(entries) => entries,
+        Err(e) => {
+            println!("Could not read the history file: {}", e);
+            return;
+        }
+    };
+    if entries.is_empty() {
+        println!("The history is empty.");
+        return;
+    }
 
-    // Number validator
-    let number_validator = |input: &str| match meval::eval_str(input) {
-        Ok(_) => Ok(Validation::Valid),
-        Err(e) => return Ok(Validation::Invalid(e.into())),
+    // Describe each entry with its index, so that it can be selected
+    let options: Vec<String> = entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let inputs: Vec<String> = entry
+                .inputs
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, value))
+                .collect();
+            format!(
+                "{}. {} ({}) with {} = {}",
+                index,
+                entry.calculation,
+                entry.method,
+                inputs.join(", "),
+                entry.result
+            )
+        })
+        .collect();
+
+    // Ask the user to select an entry to re-run
+    let selected = match Select::new("Select the calculation to re-run:", options).raw_prompt() {
+        Ok(selected) => selected,
+        Err(_) => {
+            println!("Something went wrong! Exiting...");
+            std::process::exit(1);
+        }
+    };
+
+    let entry = &entries[selected.index];
+    match replay_history_entry(entry) {
+        Ok(res) => {
+            println!("The result of the calculation is: {}", res);
+            record_history(&HistoryEntry::new(
+                &entry.calculation,
+                &entry.method,
+                entry.inputs.clone(),
+                res,
+            ));
+        }
+        Err(e) => println!("Could not re-run the calculation: {}", e),
+    }
+}
+
+/// Re-runs the calculation described by a history entry.
+///
+/// # Returns
+/// The result of the calculation, or a description of the input which could not be used.
+fn replay_history_entry(entry: &HistoryEntry) -> Result<f64, String> {
+    // Reads an input of the entry, evaluating it as a number
+    let number = |name: &str| -> Result<f64, String> {
+        let value = entry
+            .input(name)
+            .ok_or(format!("missing input '{}'", name))?;
+        meval::eval_str(value).map_err(|e| format!("invalid input '{}': {}", name, e))
+    };
+
+    // Parse the function and bind the variable 'x' to the expression
+    let expr = entry
+        .input("function")
+        .ok_or("missing input 'function'".to_string())?
+        .parse::<meval::Expr>()
+        .map_err(|e| format!("invalid function: {}", e))?;
+    let func = expr
+        .clone()
+        .bind("x")
+        .map_err(|e| format!("invalid function: {}", e))?;
+
+    match entry.calculation.as_str() {
+        "Integrals" => {
+            let mut integral = Integral::new(
+                Box::new(func),
+                number("lower_bound")?,
+                number("upper_bound")?,
+                number("num_intervals")? as u64,
+            );
+            match entry.method.as_str() {
+                "Riemann" => Ok(integral.riemann_integration()),
+                "Simpson's 1/3" => Ok(integral.simpson_integration_one_third()),
+                "Monte Carlo" => {
+                    // Seeds are integers which may not be represented exactly by a floating-point number
+                    let seed = entry
+                        .input("seed")
+                        .and_then(|seed| seed.parse::<u64>().ok())
+                        .ok_or("missing or invalid input 'seed'".to_string())?;
+                    let samples = integral.num_intervals;
+                    Ok(integral.monte_carlo_integration(samples, seed))
+                }
+                method => Err(format!("unknown integration method '{}'", method)),
+            }
+        }
+        "Derivatives" => {
+            let mut derivative = Derivative::new(
+                Box::new(func),
+                number("x_coordinate")?,
+                number("increment")?,
+            );
+            match entry.method.as_str() {
+                "Symbolic" => symbolic_derivative(&expr)
+                    .map(|symbolic| symbolic(derivative.x_coordinate))
+                    .ok_or("the function cannot be differentiated symbolically".to_string()),
+                SYMBOLIC_FALLBACK => Ok(derivative.central_difference()),
+                "Forward Difference" => Ok(derivative.forward_difference()),
+                "Central Difference" => Ok(derivative.central_difference()),
+                "Backward Difference" => Ok(derivative.backward_difference()),
+                method => Err(format!("unknown differentiation method '{}'", method)),
+            }
+        }
+        calculation => Err(format!("unknown calculation '{}'", calculation)),
+    }
+}
+
+/// An integration read from a line of the standard input in pipe mode.
+#[derive(Debug, PartialEq)]
+struct PipeJob {
+    algorithm: &'static str, // As named in the menu
+    function: String,
+    lower_bound: f64,
+    upper_bound: f64,
+    num_intervals: u64,
+    seed: Option<u64>, // Only used by Monte Carlo
+}
+
+/// Parses a line of the standard input in pipe mode.
+///
+/// A line has the fields `method; function; lower bound; upper bound; number of intervals`, separated by semicolons, e.g. `simpson; x^2; 0; 3; 1e6`.
+/// The method is one of `riemann`, `simpson` or `montecarlo`, in any case, and Monte Carlo accepts a sixth field with the seed.
+/// The numbers are evaluated as expressions, as in the interactive mode, e.g. `pi / 2`.
+///
+/// # Returns
+/// The integration described by the line, or a description of the malformed field.
+fn parse_pipe_line(line: &str) -> Result<PipeJob, String> {
+    let fields: Vec<&str> = line.split(';').map(str::trim).collect();
+    if fields.len() < 5 || fields.len() > 6 {
+        return Err(format!("expected 5 or 6 fields, found {}", fields.len()));
+    }
+
+    let algorithm = match fields[0]
+        .to_lowercase()
+        .replace([' ', '_', '-'], "")
+        .as_str()
+    {
+        "riemann" => "Riemann",
+        "simpson" => "Simpson's 1/3",
+        "montecarlo" => "Monte Carlo",
+        _ => return Err(format!("unknown method '{}'", fields[0])),
+    };
+    fields[1]
+        .parse::<meval::Expr>()
+        .and_then(|expr| expr.bind("x").map(|_| ()))
+        .map_err(|e| format!("invalid function '{}': {}", fields[1], e))?;
+    let number = |index: usize, name: &str| {
+        meval::eval_str(fields[index])
+            .map_err(|e| format!("invalid {} '{}': {}", name, fields[index], e))
+    };
+    let num_intervals = number(4, "number of intervals")?;
+    if !(1.0..=MAX_NUM_INTERVALS as f64).contains(&num_intervals) {
+        return Err(format!(
+            "invalid number of intervals '{}': must be between 1 and {:e}",
+            fields[4], MAX_NUM_INTERVALS as f64
+        ));
+    }
+    let seed = match fields.get(5) {
+        Some(seed) if algorithm == "Monte Carlo" => Some(
+            seed.parse::<u64>()
+                .map_err(|e| format!("invalid seed '{}': {}", seed, e))?,
+        ),
+        Some(_) => {
+            return Err(format!(
+                "a seed is only accepted by Monte Carlo, not {}",
+                algorithm
+            ));
+        }
+        None => None,
+    };
+
+    Ok(PipeJob {
+        algorithm,
+        function: fields[1].to_string(),
+        lower_bound: number(2, "lower bound")?,
+        upper_bound: number(3, "upper bound")?,
+        num_intervals: num_intervals as u64,
+        seed,
+    })
+}
+
+/// Runs the integrations read from the standard input, one per line, printing one result per line to the standard output.
+///
+/// This non-interactive mode lets the CLI be used in shell pipelines, e.g. `cat jobs.txt | rust_math_bin --pipe`.
+/// Empty lines and lines starting with `#` are skipped. A malformed line prints an error to the standard error, and the next lines are still run.
+/// Nothing is written to the history.
+fn run_pipe() {
+    for (line_number, line) in std::io::stdin().lines().enumerate() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("Error: cannot read the standard input: {}", e);
+                return;
+            }
+        };
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+
+        match parse_pipe_line(&line) {
+            Ok(job) => {
+                // The function was validated by the parser
+                let func = job
+                    .function
+                    .parse::<meval::Expr>()
+                    .unwrap()
+                    .bind("x")
+                    .unwrap();
+                let mut integral = Integral::new(
+                    Box::new(func),
+                    job.lower_bound,
+                    job.upper_bound,
+                    job.num_intervals,
+                );
+                let seed = seed_or_time_based(job.seed);
+                println!(
+                    "{}",
+                    run_integral_algorithm(&mut integral, job.algorithm, seed)
+                );
+            }
+            Err(e) => eprintln!("Error on line {}: {}", line_number + 1, e),
+        }
+    }
+}
+
+/// Requests the user to select the algorithms and input a function, lower and upper bounds, and the number of intervals for integration.
+/// It then performs numerical integration with each algorithm and prints the results.
+/// Monte Carlo uses as many samples as intervals, and a seed which is either provided by the user or time-based.
+fn call_integrals(number_format: NumberFormat) {
+    // Define the options for the algorithms
+    let algorithms_options = vec![
+        ListOption::new(0, "Riemann"),
+        ListOption::new(1, "Simpson's 1/3"),
+        ListOption::new(2, "Monte Carlo"),
+    ];
+
+    // Seed validator, accepting an empty input for a time-based seed
+    let seed_validator = |input: &str| {
+        if input.trim().is_empty() || input.trim().parse::<u64>().is_ok() {
+            Ok(Validation::Valid)
+        } else {
+            Ok(Validation::Invalid(
+                "The seed must be a non-negative integer".into(),
+            ))
+        }
     };
 
     // Define the default values for the user inputs
+    let mut default_algorithms: Vec<usize> = vec![0, 1];
     let mut default_func = "sin(x)".to_string();
     let mut default_lower_bound = "0".to_string();
     let mut default_upper_bound = "pi".to_string();
     let mut default_num_intervals = "1e7".to_string();
 
     loop {
+        // Request user input for algorithm
+        let algorithms = MultiSelect::new(
+            "Select which algorithms to run:",
+            algorithms_options.clone(),
+        )
+        .with_default(&default_algorithms)
+        .with_validator(
+            MinLengthValidator::new(1).with_message("Please, select at least one algorithm!"),
+        )
+        .prompt()
+        .unwrap();
+        default_algorithms = algorithms.iter().map(|x| x.index).collect();
+
         // Request user input for function
         let func = Text::new("Insert the function")
             .with_default(&default_func)
@@ -193,25 +841,107 @@ fn call_integrals() {
         // Request user input for number of intervals
         let num_intervals = Text::new("Insert the number of intervals")
             .with_default(&default_num_intervals)
-            .with_validator(number_validator)
+            .with_validator(num_intervals_validator)
             .prompt()
             .unwrap();
         default_num_intervals = num_intervals.clone();
         // Parse the number of intervals string into an unsigned 64-bit integer
         let num_intervals = meval::eval_str(num_intervals).unwrap() as u64;
 
+        // Request user input for the seed, if Monte Carlo is selected
+        let seed = if algorithms.iter().any(|x| x.value == "Monte Carlo") {
+            let seed = Text::new("Insert the seed for Monte Carlo")
+                .with_placeholder("time-based")
+                .with_help_message("Use the same seed to reproduce a result")
+                .with_validator(seed_validator)
+                .prompt()
+                .unwrap();
+            // An empty input gives a time-based seed
+            Some(seed_or_time_based(seed.trim().parse::<u64>().ok()))
+        } else {
+            None
+        };
+
+        // Request user input for the unit of the results
+        let units = ask_units();
+
         // Print the user inputs
         println!("Test function: {}", default_func);
         println!("Lower bound: {}", default_lower_bound);
         println!("Upper bound: {}", default_upper_bound);
         println!("Intervals: {}", default_num_intervals);
+        if let Some(seed) = seed {
+            println!("Seed: {}", seed);
+        }
 
         // Perform numerical integration using the Integral struct
-        let res = Integral::new(Box::new(func), lower_bound, upper_bound, num_intervals)
-            .riemann_integration();
+        let mut integral = Integral::new(Box::new(func), lower_bound, upper_bound, num_intervals);
 
-        // Print the result of the integration
-        println!("The result of the integral is: {}", res);
+        // Check the function and the cost of the integrations before running them
+        let methods: Vec<IntegrationMethod> = algorithms
+            .iter()
+            .map(|algorithm| match algorithm.value {
+                "Simpson's 1/3" => IntegrationMethod::Simpson,
+                "Monte Carlo" => IntegrationMethod::MonteCarlo,
+                _ => IntegrationMethod::Riemann,
+            })
+            .collect();
+        if !confirm_integration(&integral, &methods) {
+            if !ask_for_another_calculation() {
+                break;
+            }
+            continue;
+        }
+
+        let mut stats: Vec<CalculationStats> = vec![];
+        algorithms.iter().for_each(|algorithm| {
+            println!("Using algorithm: {}", algorithm.value);
+            let process_time = Instant::now();
+            let res =
+                run_integral_algorithm(&mut integral, algorithm.value, seed.unwrap_or_default());
+            let process_time = process_time.elapsed();
+            stats.push(CalculationStats {
+                algorithm: algorithm.value.to_string(),
+                process_time: format!("{:?}", process_time),
+                result: res,
+                number_format,
+                units: units.clone(),
+                order: algorithm_order(algorithm.value),
+            });
+            // Log the calculation in the history file
+            let mut inputs = vec![
+                ("function".to_string(), default_func.clone()),
+                ("lower_bound".to_string(), default_lower_bound.clone()),
+                ("upper_bound".to_string(), default_upper_bound.clone()),
+                ("num_intervals".to_string(), default_num_intervals.clone()),
+            ];
+            if let (Some(seed), "Monte Carlo") = (seed, algorithm.value) {
+                inputs.push(("seed".to_string(), seed.to_string()));
+            }
+            record_history(&HistoryEntry::new(
+                "Integrals",
+                algorithm.value,
+                inputs,
+                res,
+            ));
+        });
+
+        // Print the results of the integration
+        println!("\nResults of the integration:");
+        println!("{}", get_stats_table(&stats));
+        if stats.len() > 1 {
+            println!(
+                "Consensus result: {}",
+                display_result(&consensus_result(&stats), &stats[0])
+            );
+        }
+        offer_markdown_export(&stats);
+
+        // Offer to refine the results, to assess their convergence
+        if ask_for_refinement("doubling the number of intervals") {
+            let refined = refine_integral(&mut integral, &stats, seed.unwrap_or_default());
+            print_refinement(&stats, &refined);
+        }
 
         // Ask the user if they want to perform another calculation
         if !ask_for_another_calculation() {
@@ -222,29 +952,28 @@ fn call_integrals() {
 
 /// Requests the user to input a function, X coordinate, and increment for derivative calculation.
 /// It then performs numerical differentiation and prints the result.
-fn call_derivatives() {
+/// Functions of several variables are handed over to `call_gradient`.
+fn call_derivatives(number_format: NumberFormat) {
+    // Request user input for the type of function
+    let function_type = Select::new(
+        "Select the type of function:",
+        vec!["One variable", "Several variables"],
+    )
+    .prompt()
+    .unwrap();
+    if function_type == "Several variables" {
+        call_gradient(number_format);
+        return;
+    }
+
     // Define the options for the algorithms
     let algorithms_options = vec![
         ListOption::new(0, "Forward Difference"),
         ListOption::new(1, "Central Difference"),
         ListOption::new(2, "Backward Difference"),
+        ListOption::new(3, "Symbolic"),
     ];
 
-    // Expression validator for the function input
-    let expr_validator = |input: &str| match input.parse::<meval::Expr>() {
-        Ok(expr) => match expr.bind("x") {
-            Ok(_) => Ok(Validation::Valid),
-            Err(e) => return Ok(Validation::Invalid(e.into())),
-        },
-        Err(e) => Ok(Validation::Invalid(e.into())),
-    };
-
-    // Number validator
-    let number_validator = |input: &str| match meval::eval_str(input) {
-        Ok(_) => Ok(Validation::Valid),
-        Err(e) => return Ok(Validation::Invalid(e.into())),
-    };
-
     // Define the default values for the user inputs
     let mut default_algorithms: Vec<usize> = vec![0, 1, 2];
     let mut default_func = "sin(x)".to_string();
@@ -276,6 +1005,9 @@ fn call_derivatives() {
         let expr: meval::Expr = func.parse().unwrap();
         // Bind the variable 'x' to the expression
         let func = expr.clone().bind("x").unwrap();
+        // Differentiate the expression symbolically, if its operators are supported
+        let symbolic = symbolic_derivative(&expr);
+        let symbolic = symbolic.as_ref().map(|f| f as &dyn Fn(f64) -> f64);
 
         // Request user input for X coordinate
         let x_coord = Text::new("Insert the X coordinate")
@@ -297,6 +1029,9 @@ fn call_derivatives() {
         // Parse the increment string into a floating-point number
         let increment = meval::eval_str(increment).unwrap();
 
+        // Request user input for the unit of the results
+        let units = ask_units();
+
         // Print the user inputs
         println!("Test function: {}", default_func);
         println!("X coordinate: {}", default_x_coord);
@@ -304,32 +1039,39 @@ fn call_derivatives() {
 
         // Perform numerical differentiation using the Derivative struct
         let mut derivative = Derivative::new(Box::new(func), x_coord, increment);
+
+        warn_step_underflow(&derivative);
+
         let mut stats: Vec<CalculationStats> = vec![];
         algorithms.iter().for_each(|algorithm| {
             println!("Using algorithm: {}", algorithm.value);
-            let res;
+            // Report whether the symbolic differentiation fell back to a numerical one
+            let name = match (algorithm.value, symbolic) {
+                ("Symbolic", None) => SYMBOLIC_FALLBACK,
+                (name, _) => name,
+            };
             let process_time = Instant::now();
-            match algorithm.value {
-                "Forward Difference" => {
-                    res = derivative.forward_difference();
-                }
-                "Central Difference" => {
-                    res = derivative.central_difference();
-                }
-                "Backward Difference" => {
-                    res = derivative.backward_difference();
-                }
-                _ => {
-                    println!("Invalid algorithm selected. Using Forward Difference as default.");
-                    res = derivative.forward_difference();
-                }
-            }
+            let res = run_derivative_algorithm(&mut derivative, name, symbolic);
             let process_time = process_time.elapsed();
             stats.push(CalculationStats {
-                algorithm: algorithm.value.to_string(),
+                algorithm: name.to_string(),
                 process_time: format!("{:?}", process_time),
                 result: res,
+                number_format,
+                units: units.clone(),
+                order: algorithm_order(name),
             });
+            // Log the calculation in the history file
+            record_history(&HistoryEntry::new(
+                "Derivatives",
+                name,
+                vec![
+                    ("function".to_string(), default_func.clone()),
+                    ("x_coordinate".to_string(), default_x_coord.clone()),
+                    ("increment".to_string(), default_increment.clone()),
+                ],
+                res,
+            ));
             // Print the result of the differentiation
             // println!("The result of the derivate is: {}", res);
         });
@@ -337,6 +1079,152 @@ fn call_derivatives() {
         // Print the results of the differentiation
         println!("\nResults of the differentiation:");
         println!("{}", get_stats_table(&stats));
+        if stats.len() > 1 {
+            println!(
+                "Consensus result: {}",
+                display_result(&consensus_result(&stats), &stats[0])
+            );
+        }
+        offer_markdown_export(&stats);
+
+        // Offer to refine the results, to assess their convergence
+        if ask_for_refinement("halving the increment") {
+            let refined = refine_derivative(&mut derivative, &stats, symbolic);
+            print_refinement(&stats, &refined);
+        }
+
+        // Ask the user if they want to perform another calculation
+        if !ask_for_another_calculation() {
+            break;
+        }
+    }
+}
+
+/// Requests the user to input a function of several variables, a point, and an increment.
+/// It then computes the gradient with central differences and prints each partial derivative.
+fn call_gradient(number_format: NumberFormat) {
+    // Variables validator, accepting a comma-separated list of names
+    let variables_validator = |input: &str| {
+        if parse_variables(input)
+            .iter()
+            .all(|name| name.chars().next().is_some_and(char::is_alphabetic))
+        {
+            Ok(Validation::Valid)
+        } else {
+            Ok(Validation::Invalid(
+                "Please, insert a comma-separated list of variable names".into(),
+            ))
+        }
+    };
+
+    // Define the default values for the user inputs
+    let mut default_variables = "x, y".to_string();
+    let mut default_func = "x^2 * y".to_string();
+    let mut default_point = "1, 2".to_string();
+    let mut default_increment = "1e-7".to_string();
+
+    loop {
+        // Request user input for the variables
+        let variables = Text::new("Insert the variables")
+            .with_default(&default_variables)
+            .with_help_message("Separate the names with commas")
+            .with_validator(variables_validator)
+            .prompt()
+            .unwrap();
+        default_variables = variables.clone();
+        let variables = parse_variables(&variables);
+        let variables: Vec<&str> = variables.iter().map(String::as_str).collect();
+
+        // Request user input for function, validated against the variables
+        let expr_validator = {
+            let variables: Vec<String> = variables.iter().map(|name| name.to_string()).collect();
+            move |input: &str| {
+                let variables: Vec<&str> = variables.iter().map(String::as_str).collect();
+                match bind_variables(input, &variables) {
+                    Ok(_) => Ok(Validation::Valid),
+                    Err(e) => Ok(Validation::Invalid(e.into())),
+                }
+            }
+        };
+        let func = Text::new("Insert the function")
+            .with_default(&default_func)
+            .with_validator(expr_validator)
+            .prompt()
+            .unwrap();
+        default_func = func.clone();
+        let func = bind_variables(&func, &variables).unwrap();
+
+        // Request user input for the point, with one coordinate per variable
+        let num_variables = variables.len();
+        let point_validator = move |input: &str| {
+            let coordinates: Vec<&str> = input.split(',').collect();
+            if coordinates.len() != num_variables {
+                return Ok(Validation::Invalid(
+                    format!(
+                        "Please, insert {} comma-separated coordinates",
+                        num_variables
+                    )
+                    .into(),
+                ));
+            }
+            match coordinates
+                .iter()
+                .try_for_each(|c| meval::eval_str(c).map(|_| ()))
+            {
+                Ok(_) => Ok(Validation::Valid),
+                Err(e) => Ok(Validation::Invalid(e.into())),
+            }
+        };
+        let point = Text::new("Insert the point")
+            .with_default(&default_point)
+            .with_validator(point_validator)
+            .prompt()
+            .unwrap();
+        default_point = point.clone();
+        // Parse the coordinates into floating-point numbers
+        let point: Vec<f64> = point
+            .split(',')
+            .map(|c| meval::eval_str(c).unwrap())
+            .collect();
+
+        // Request user input for increment
+        let increment = Text::new("Insert the increment")
+            .with_default(&default_increment)
+            .with_validator(number_validator)
+            .prompt()
+            .unwrap();
+        default_increment = increment.clone();
+        // Parse the increment string into a floating-point number
+        let increment = meval::eval_str(increment).unwrap();
+
+        // Print the user inputs
+        println!("Test function: {}", default_func);
+        println!("Variables: {}", default_variables);
+        println!("Point: {}", default_point);
+        println!("Increment: {}", default_increment);
+
+        // Compute each partial derivative of the gradient
+        let stats: Vec<CalculationStats> = variables
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let process_time = Instant::now();
+                let res = partial_derivative(&func, &point, index, increment);
+                CalculationStats {
+                    algorithm: format!("∂/∂{} (Central Difference)", name),
+                    process_time: format!("{:?}", process_time.elapsed()),
+                    result: res,
+                    number_format,
+                    units: None,
+                    order: algorithm_order("Central Difference"),
+                }
+            })
+            .collect();
+
+        // Print the results of the differentiation
+        println!("\nGradient:");
+        println!("{}", get_stats_table(&stats));
+        offer_markdown_export(&stats);
 
         // Ask the user if they want to perform another calculation
         if !ask_for_another_calculation() {
@@ -344,3 +1232,372 @@ fn call_derivatives() {
         }
     }
 }
+
+/// Splits a comma-separated list of variable names, trimming the whitespace around each name.
+fn parse_variables(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(|name| name.trim().to_string())
+        .collect()
+}
+
+/// Parses a function of several variables, binding the given variables in order.
+///
+/// # Arguments
+/// * `func` - The function to parse.
+/// * `variables` - The names of the variables, in the order of the coordinates passed to the function.
+///
+/// # Returns
+/// The function, taking the values of the variables as a slice, or an error if the expression is invalid or uses other variables.
+fn bind_variables<'a>(
+    func: &str,
+    variables: &'a [&'a str],
+) -> Result<impl Fn(&[f64]) -> f64 + 'a, meval::Error> {
+    func.parse::<meval::Expr>()?.bindn(variables)
+}
+
+/// Requests the user to input a function once, then integrates it over an interval and differentiates it at a point.
+/// The integral uses Simpson's 1/3 rule and the derivative the central difference, and both results are shown in one table.
+/// The inputs are checked as in `call_integrals` and `call_derivatives`.
+fn call_both(number_format: NumberFormat) {
+    // Define the default values for the user inputs
+    let mut default_func = "sin(x)".to_string();
+    let mut default_lower_bound = "0".to_string();
+    let mut default_upper_bound = "pi".to_string();
+    let mut default_num_intervals = "1e6".to_string();
+    let mut default_x_coord = "0".to_string();
+    let mut default_increment = "1e-7".to_string();
+
+    loop {
+        // Request user input for function
+        let func = Text::new("Insert the function")
+            .with_default(&default_func)
+            .with_validator(expr_validator)
+            .prompt()
+            .unwrap();
+        default_func = func.clone();
+        // Parse the function string into a meval expression
+        let expr: meval::Expr = func.parse().unwrap();
+
+        // Request user input for the numbers, parsing each into a floating-point number
+        let ask_number = |message: &str, default: &mut String| {
+            let input = Text::new(message)
+                .with_default(default)
+                .with_validator(number_validator)
+                .prompt()
+                .unwrap();
+            *default = input.clone();
+            meval::eval_str(input).unwrap()
+        };
+        let lower_bound = ask_number("Insert the lower bound", &mut default_lower_bound);
+        let upper_bound = ask_number("Insert the upper bound", &mut default_upper_bound);
+        let num_intervals = Text::new("Insert the number of intervals")
+            .with_default(&default_num_intervals)
+            .with_validator(num_intervals_validator)
+            .prompt()
+            .unwrap();
+        default_num_intervals = num_intervals.clone();
+        let num_intervals = meval::eval_str(num_intervals).unwrap() as u64;
+        let x_coord = ask_number("Insert the X coordinate", &mut default_x_coord);
+        let increment = ask_number("Insert the increment", &mut default_increment);
+
+        // Print the user inputs
+        println!("Test function: {}", default_func);
+        println!("Lower bound: {}", default_lower_bound);
+        println!("Upper bound: {}", default_upper_bound);
+        println!("Intervals: {}", default_num_intervals);
+        println!("X coordinate: {}", default_x_coord);
+        println!("Increment: {}", default_increment);
+
+        let (mut integral, mut derivative) = match bind_both(
+            &expr,
+            (lower_bound, upper_bound, num_intervals),
+            (x_coord, increment),
+        ) {
+            Ok(calculations) => calculations,
+            Err(e) => {
+                println!("The function cannot be evaluated: {}", e);
+                if !ask_for_another_calculation() {
+                    break;
+                }
+                continue;
+            }
+        };
+
+        // Run the same checks as the separate integrals and derivatives
+        if !confirm_integration(&integral, &[IntegrationMethod::Simpson]) {
+            if !ask_for_another_calculation() {
+                break;
+            }
+            continue;
+        }
+        warn_step_underflow(&derivative);
+
+        let stats = compute_both(&mut integral, &mut derivative, number_format);
+
+        // Log the calculations in the history file
+        record_history(&HistoryEntry::new(
+            "Integrals",
+            "Simpson's 1/3",
+            vec![
+                ("function".to_string(), default_func.clone()),
+                ("lower_bound".to_string(), default_lower_bound.clone()),
+                ("upper_bound".to_string(), default_upper_bound.clone()),
+                ("num_intervals".to_string(), default_num_intervals.clone()),
+            ],
+            stats[0].result,
+        ));
+        record_history(&HistoryEntry::new(
+            "Derivatives",
+            "Central Difference",
+            vec![
+                ("function".to_string(), default_func.clone()),
+                ("x_coordinate".to_string(), default_x_coord.clone()),
+                ("increment".to_string(), default_increment.clone()),
+            ],
+            stats[1].result,
+        ));
+
+        // Print the results of both calculations
+        println!("\nResults of the integration and differentiation:");
+        println!("{}", get_stats_table(&stats));
+        offer_markdown_export(&stats);
+
+        // Ask the user if they want to perform another calculation
+        if !ask_for_another_calculation() {
+            break;
+        }
+    }
+}
+
+/// Sets up the integral and the derivative of the same function, binding the parsed expression only once.
+///
+/// # Arguments
+/// * `expr` - The parsed function of `x`.
+/// * `integral` - The lower bound, upper bound and number of intervals of the integral.
+/// * `derivative` - The X coordinate and increment of the derivative.
+///
+/// # Returns
+/// The integral and the derivative, sharing the bound function, or an error if the expression cannot be bound to `x`.
+fn bind_both(
+    expr: &meval::Expr,
+    (lower_bound, upper_bound, num_intervals): (f64, f64, u64),
+    (x_coord, increment): (f64, f64),
+) -> Result<(Integral, Derivative), meval::Error> {
+    // Share the bound function between the two calculations
+    let func = Rc::new(expr.clone().bind("x")?);
+
+    let integral_func = Rc::clone(&func);
+    let integral = Integral::new(
+        Box::new(move |x| integral_func(x)),
+        lower_bound,
+        upper_bound,
+        num_intervals,
+    );
+    let derivative = Derivative::new(Box::new(move |x| func(x)), x_coord, increment);
+    Ok((integral, derivative))
+}
+
+/// Integrates with Simpson's 1/3 rule and differentiates with the central difference.
+///
+/// # Arguments
+/// * `integral` - The integral to compute.
+/// * `derivative` - The derivative to compute.
+/// * `number_format` - The notation used to display the results.
+///
+/// # Returns
+/// The statistics of the integral and of the derivative, in this order.
+fn compute_both(
+    integral: &mut Integral,
+    derivative: &mut Derivative,
+    number_format: NumberFormat,
+) -> Vec<CalculationStats> {
+    let process_time = Instant::now();
+    let integral_result = integral.simpson_integration_one_third();
+    let integral_time = process_time.elapsed();
+
+    let process_time = Instant::now();
+    let derivative_result = derivative.central_difference();
+    let derivative_time = process_time.elapsed();
+
+    vec![
+        CalculationStats {
+            algorithm: "Simpson's 1/3 (integral)".to_string(),
+            process_time: format!("{:?}", integral_time),
+            result: integral_result,
+            number_format,
+            units: None,
+            order: algorithm_order("Simpson's 1/3"),
+        },
+        CalculationStats {
+            algorithm: "Central Difference (derivative)".to_string(),
+            process_time: format!("{:?}", derivative_time),
+            result: derivative_result,
+            number_format,
+            units: None,
+            order: algorithm_order("Central Difference"),
+        },
+    ]
+}
+
+// ---- Tests ---- //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_both() {
+        let expr: meval::Expr = "x^3".parse().unwrap();
+        let (mut integral, mut derivative) =
+            bind_both(&expr, (0.0, 2.0, 1000), (1.0, 1e-5)).unwrap();
+        let stats = compute_both(&mut integral, &mut derivative, NumberFormat::default());
+
+        assert_eq!(stats.len(), 2);
+        assert!((stats[0].result - 4.0).abs() < 1e-9);
+        assert!((stats[1].result - 3.0).abs() < 1e-6);
+
+        let unbound: meval::Expr = "x + y".parse().unwrap();
+        assert!(bind_both(&unbound, (0.0, 1.0, 10), (0.0, 1e-5)).is_err());
+    }
+
+    #[test]
+    fn test_bind_variables() {
+        let variables = parse_variables("x, y ,z");
+        assert_eq!(variables, vec!["x", "y", "z"]);
+        let variables: Vec<&str> = variables.iter().map(String::as_str).collect();
+
+        let func = bind_variables("x^2 * y + z", &variables).unwrap();
+        assert_eq!(func(&[3.0, 2.0, 1.0]), 19.0);
+
+        assert!(bind_variables("x^2 * y + z", &variables[..2]).is_err());
+        assert!(bind_variables("x^2 *", &variables).is_err());
+    }
+
+    #[test]
+    fn test_num_intervals_validator() {
+        assert!(matches!(
+            num_intervals_validator("1e6"),
+            Ok(Validation::Valid)
+        ));
+        for input in ["1e18", "0", "-5", "x"] {
+            assert!(matches!(
+                num_intervals_validator(input),
+                Ok(Validation::Invalid(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_units_in_table() {
+        let stats = vec![CalculationStats {
+            algorithm: "Riemann".to_string(),
+            process_time: "1ms".to_string(),
+            result: 2.5,
+            number_format: NumberFormat::Fixed(3),
+            units: Some("meters".to_string()),
+            order: 1,
+        }];
+        assert_eq!(stats[0].units.as_deref(), Some("meters"));
+        assert!(get_stats_table(&stats).to_string().contains("2.50 meters"));
+    }
+
+    /// Helper function returning the statistics of a calculation, with its result only.
+    fn stats_with_result(algorithm: &str, result: f64) -> CalculationStats {
+        CalculationStats {
+            algorithm: algorithm.to_string(),
+            process_time: String::new(),
+            result,
+            number_format: NumberFormat::default(),
+            units: None,
+            order: algorithm_order(algorithm),
+        }
+    }
+
+    #[test]
+    fn test_parse_pipe_line() {
+        assert_eq!(
+            parse_pipe_line("simpson; x^2; 0; 3; 1e6"),
+            Ok(PipeJob {
+                algorithm: "Simpson's 1/3",
+                function: "x^2".to_string(),
+                lower_bound: 0.0,
+                upper_bound: 3.0,
+                num_intervals: 1_000_000,
+                seed: None,
+            })
+        );
+        let job = parse_pipe_line("Monte Carlo;sin(x);0;pi / 2;1000;42").unwrap();
+        assert_eq!(job.algorithm, "Monte Carlo");
+        assert_eq!(job.upper_bound, std::f64::consts::FRAC_PI_2);
+        assert_eq!(job.seed, Some(42));
+        assert_eq!(
+            parse_pipe_line("RIEMANN; x; 0; 1; 10").unwrap().algorithm,
+            "Riemann"
+        );
+
+        for malformed in [
+            "simpson; x^2; 0; 3",
+            "trapezoid; x^2; 0; 3; 10",
+            "simpson; x^; 0; 3; 10",
+            "simpson; x^2; zero; 3; 10",
+            "simpson; x^2; 0; 3; 0",
+            "simpson; x^2; 0; 3; 1e18",
+            "simpson; x^2; 0; 3; 0/0",
+            "simpson; x^2; 0; 3; 10; 42",
+            "montecarlo; x^2; 0; 3; 10; -1",
+            "",
+        ] {
+            assert!(
+                parse_pipe_line(malformed).is_err(),
+                "Accepted: {}",
+                malformed
+            );
+        }
+    }
+
+    #[test]
+    fn test_consensus_result() {
+        let mut integral = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 1.0, 100);
+        let stats = vec![
+            stats_with_result("Riemann", integral.riemann_integration()),
+            stats_with_result("Simpson's 1/3", integral.simpson_integration_one_third()),
+            stats_with_result("Monte Carlo", integral.monte_carlo_integration(100, 1)),
+        ];
+
+        let exact = std::f64::consts::E - 1.0;
+        let worst_error = stats
+            .iter()
+            .map(|stat| (stat.result - exact).abs())
+            .fold(0.0, f64::max);
+        let consensus = consensus_result(&stats);
+        assert!((consensus - exact).abs() < worst_error);
+        assert_eq!(consensus, (stats[0].result + 4.0 * stats[1].result) / 5.0);
+    }
+
+    #[test]
+    fn test_refine_integral() {
+        let mut integral = Integral::new(Box::new(|x: f64| x.exp()), 0.0, 1.0, 10);
+        let stats = vec![stats_with_result("Riemann", integral.riemann_integration())];
+
+        let refined = refine_integral(&mut integral, &stats, 0);
+        assert_eq!(integral.num_intervals, 20);
+        assert_eq!(refined[0].0, stats[0].result);
+        let exact = std::f64::consts::E - 1.0;
+        assert!((refined[0].1 - exact).abs() < (refined[0].0 - exact).abs());
+    }
+
+    #[test]
+    fn test_refine_derivative() {
+        let mut derivative = Derivative::new(Box::new(|x: f64| x.exp()), 0.0, 1e-2);
+        let stats = vec![stats_with_result(
+            "Forward Difference",
+            derivative.forward_difference(),
+        )];
+
+        let refined = refine_derivative(&mut derivative, &stats, None);
+        assert_eq!(derivative.increment, 5e-3);
+        assert_eq!(refined[0].0, stats[0].result);
+        assert!((refined[0].1 - 1.0).abs() < (refined[0].0 - 1.0).abs());
+    }
+}
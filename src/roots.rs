@@ -0,0 +1,297 @@
+//! This module implements root-finding algorithms, i.e. methods to solve `f(x) = 0` for `x`.
+//!
+//! The roots are found in two steps:
+//! - `find_bracket`: Searches an interval where the function changes sign, stepping away from a starting point.
+//! - `bisection`: Halves a bracketing interval until it is as narrow as the tolerance.
+//!
+//! `find_all_roots` combines both steps to find all the roots in an interval.
+
+use std::fmt;
+
+/// Maximum number of times a bracketing interval is halved by `bisection`, enough to reach the rounding error from any interval of finite width.
+const MAX_BISECTIONS: u32 = 2100;
+
+/// Errors that can occur when finding a root.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RootError {
+    /// The function has the same sign at both ends of the given interval, so it is not known to contain a root.
+    NoSignChange(f64, f64),
+    /// No change of sign was found within the given distance of the starting point.
+    BracketNotFound(f64),
+    /// The requested quantile is not a fraction between `0` and `1`.
+    InvalidQuantile(f64),
+    /// The integral of the density is not positive, so the distribution has no quantiles. Carries the integral.
+    NonPositiveMass(f64),
+}
+
+impl fmt::Display for RootError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RootError::NoSignChange(a, b) => write!(
+                f,
+                "the function has the same sign at x = {} and x = {}",
+                a, b
+            ),
+            RootError::BracketNotFound(distance) => write!(
+                f,
+                "the function does not change sign within {} of the starting point",
+                distance
+            ),
+            RootError::InvalidQuantile(q) => {
+                write!(f, "the quantile {} is not between 0 and 1", q)
+            }
+            RootError::NonPositiveMass(total) => {
+                write!(f, "the integral of the density, {}, is not positive", total)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RootError {}
+
+/// Finds a root of the function in `[a, b]` with the bisection method.
+///
+/// The function must change sign over the interval, i.e. `f(a)` and `f(b)` must have opposite signs, or one of them must be zero.
+/// The interval is halved, keeping the half where the function changes sign, until it is no wider than `tolerance`:
+/// the method is slow, gaining one bit per step, but it cannot fail on a continuous function.
+///
+/// # Arguments
+///
+/// * `function` - The function whose root is searched.
+/// * `a` - One end of the bracketing interval.
+/// * `b` - The other end of the bracketing interval.
+/// * `tolerance` - The width of the interval below which the search stops. The search also stops at the rounding error of the root.
+///
+/// # Returns
+///
+/// The middle of the final interval, or an error if the function does not change sign over `[a, b]`.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::roots::bisection;
+///
+/// let root = bisection(&|x| x * x - 2.0, 0.0, 2.0, 1e-12).unwrap();
+/// assert!((root - 2f64.sqrt()).abs() < 1e-12);
+/// ```
+pub fn bisection(
+    function: &dyn Fn(f64) -> f64,
+    a: f64,
+    b: f64,
+    tolerance: f64,
+) -> Result<f64, RootError> {
+    let (mut low, mut high) = (a.min(b), a.max(b));
+    let mut f_low = function(low);
+    let f_high = function(high);
+    if f_low == 0.0 {
+        return Ok(low);
+    }
+    if f_high == 0.0 {
+        return Ok(high);
+    }
+    if f_low.signum() == f_high.signum() {
+        return Err(RootError::NoSignChange(a, b));
+    }
+
+    for _ in 0..MAX_BISECTIONS {
+        let mid = low + (high - low) / 2.0;
+        if high - low <= tolerance || mid <= low || mid >= high {
+            break; // Narrow enough, or no floating point number left between the ends
+        }
+
+        let f_mid = function(mid);
+        if f_mid == 0.0 {
+            return Ok(mid);
+        }
+        if f_mid.signum() == f_low.signum() {
+            low = mid;
+            f_low = f_mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    Ok(low + (high - low) / 2.0)
+}
+
+/// Searches an interval where the function changes sign, stepping away from `start`.
+///
+/// The intervals `[start + (k - 1) * step, start + k * step]` and `[start - k * step, start - (k - 1) * step]` are tried for `k = 1, 2, ...`, the one above `start` first:
+/// the bracket found is the one of the root nearest to `start`, preferring roots above it at equal distance, provided `step` is smaller than the distance between roots.
+///
+/// # Arguments
+///
+/// * `function` - The function whose root is searched.
+/// * `start` - The point the search starts from, e.g. a guess of the root.
+/// * `step` - The width of the intervals tried.
+/// * `max_steps` - The number of intervals tried on each side of `start`.
+///
+/// # Returns
+///
+/// The ends of the bracketing interval, in increasing order, or an error if the function does not change sign within `max_steps * step` of `start`.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::roots::find_bracket;
+///
+/// let (a, b) = find_bracket(&|x| x - 2.5, 0.0, 1.0, 10).unwrap();
+/// assert_eq!((a, b), (2.0, 3.0));
+/// ```
+pub fn find_bracket(
+    function: &dyn Fn(f64) -> f64,
+    start: f64,
+    step: f64,
+    max_steps: usize,
+) -> Result<(f64, f64), RootError> {
+    let step = step.abs();
+    let changes_sign = |a: f64, b: f64| {
+        let (fa, fb) = (function(a), function(b));
+        fa == 0.0 || fb == 0.0 || fa.signum() != fb.signum()
+    };
+
+    for k in 1..=max_steps {
+        let above = (start + (k - 1) as f64 * step, start + k as f64 * step);
+        if changes_sign(above.0, above.1) {
+            return Ok(above);
+        }
+        let below = (start - k as f64 * step, start - (k - 1) as f64 * step);
+        if changes_sign(below.0, below.1) {
+            return Ok(below);
+        }
+    }
+
+    Err(RootError::BracketNotFound(max_steps as f64 * step))
+}
+
+/// Finds all the roots of the function in `[a, b]`, scanning the interval for changes of sign and refining each bracket with `bisection`.
+///
+/// The function is sampled at `scan_points` evenly spaced points, bounds included.
+/// Roots are only found where the function changes sign between two neighbouring points, or is exactly zero at one of them, so the resolution of the scan must be finer than the distance between roots:
+/// two roots between the same pair of points cancel out and are both missed, and so is a double root, e.g. the root of `x^2`, where the function touches zero without changing sign.
+/// The function is also evaluated `tolerance` beyond each bound, so that a root lying on a bound is found even if the function is not exactly zero there because of the rounding, e.g. `sin(3π)`.
+///
+/// # Arguments
+///
+/// * `function` - The function whose roots are searched.
+/// * `a` - The lower end of the interval.
+/// * `b` - The upper end of the interval.
+/// * `scan_points` - The number of points sampled to search the brackets, at least 2.
+/// * `tolerance` - The width of the interval below which the refinement of each root stops.
+///
+/// # Returns
+///
+/// The roots found, in increasing order.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::roots::find_all_roots;
+///
+/// let roots = find_all_roots(&|x| x * x - 2.0, -5.0, 5.0, 100, 1e-12);
+/// assert_eq!(roots.len(), 2);
+/// assert!((roots[1] - 2f64.sqrt()).abs() < 1e-12);
+/// ```
+pub fn find_all_roots(
+    function: &dyn Fn(f64) -> f64,
+    a: f64,
+    b: f64,
+    scan_points: usize,
+    tolerance: f64,
+) -> Vec<f64> {
+    let (a, b) = (a.min(b), a.max(b));
+    let scan_points = scan_points.max(2);
+    let step = (b - a) / (scan_points - 1) as f64;
+    let samples: Vec<(f64, f64)> = (0..scan_points)
+        .map(|i| {
+            let x = if i == scan_points - 1 {
+                b
+            } else {
+                a + i as f64 * step
+            };
+            (x, function(x))
+        })
+        .collect();
+    let changes_sign = |fa: f64, fb: f64| {
+        fa.is_finite() && fb.is_finite() && fa != 0.0 && fb != 0.0 && fa.signum() != fb.signum()
+    };
+
+    let mut roots = vec![];
+    if changes_sign(function(a - tolerance), samples[0].1) {
+        roots.push(a); // Root on the lower bound, hidden by the rounding
+    }
+    for (i, &(x, fx)) in samples.iter().enumerate() {
+        if fx == 0.0 {
+            roots.push(x);
+        } else if let Some(&(x_next, f_next)) = samples.get(i + 1)
+            && changes_sign(fx, f_next)
+            && let Ok(root) = bisection(function, x, x_next, tolerance)
+        {
+            roots.push(root);
+        }
+    }
+    if changes_sign(samples[scan_points - 1].1, function(b + tolerance)) {
+        roots.push(b); // Root on the upper bound, hidden by the rounding
+    }
+
+    roots
+}
+
+// ---- Tests ---- //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bisection() {
+        let root = bisection(&|x: f64| x.cos(), 3.0, 0.0, 1e-10).unwrap();
+        assert!((root - std::f64::consts::FRAC_PI_2).abs() < 1e-10);
+
+        // Down to the rounding error with a zero tolerance
+        let root = bisection(&|x: f64| x.powi(3) - 8.0, 0.0, 10.0, 0.0).unwrap();
+        assert!((root - 2.0).abs() <= 4.0 * f64::EPSILON);
+
+        assert_eq!(
+            bisection(&|x: f64| x * x + 1.0, -1.0, 1.0, 1e-10),
+            Err(RootError::NoSignChange(-1.0, 1.0))
+        );
+    }
+
+    #[test]
+    fn test_find_bracket() {
+        // Roots at -1 and 1, at the same distance from 0: the one above is preferred
+        let function = |x: f64| x * x - 1.0;
+        let (a, b) = find_bracket(&function, 0.0, 0.3, 10).unwrap();
+        assert!(a < 1.0 && 1.0 < b && b - a < 0.3 + 1e-12);
+        let (a, b) = find_bracket(&function, -0.5, 0.3, 10).unwrap();
+        assert!(a < -1.0 && -1.0 < b);
+
+        assert_eq!(
+            find_bracket(&|x: f64| x * x + 1.0, 0.0, 0.5, 4),
+            Err(RootError::BracketNotFound(2.0))
+        );
+    }
+
+    #[test]
+    fn test_find_all_roots_sine() {
+        use std::f64::consts::PI;
+
+        let roots = find_all_roots(&|x: f64| x.sin(), 0.0, 3.0 * PI, 100, 1e-12);
+        assert_eq!(roots.len(), 4, "Roots: {:?}", roots);
+        for (k, root) in roots.iter().enumerate() {
+            assert!((root - k as f64 * PI).abs() < 1e-11, "Roots: {:?}", roots);
+        }
+    }
+
+    #[test]
+    fn test_find_all_roots_close_roots() {
+        // Roots 0.01 apart: missed by a coarse scan, found by a fine one
+        let function = |x: f64| (x - 1.0) * (x - 1.01);
+        assert!(find_all_roots(&function, 0.0, 2.0, 10, 1e-12).is_empty());
+
+        let roots = find_all_roots(&function, 0.0, 2.0, 1000, 1e-12);
+        assert_eq!(roots.len(), 2);
+        assert!((roots[0] - 1.0).abs() < 1e-11 && (roots[1] - 1.01).abs() < 1e-11);
+    }
+}
@@ -0,0 +1,163 @@
+//! This module implements a check of the numerical methods at runtime, against integrals and derivatives whose exact values are known.
+//!
+//! Unlike the unit tests, which only run at development time, `self_test` is part of the library and of the binary:
+//! users, or a CI pipeline, can run it on the target machine, e.g. to catch a miscompilation or an unexpected floating-point mode.
+
+use std::f64::consts::{E, PI};
+
+use crate::derivatives::Derivative;
+use crate::integrals::Integral;
+use crate::roots;
+
+/// A computed value, compared with its exact value.
+struct Check {
+    name: &'static str,
+    computed: f64,
+    exact: f64,
+    tolerance: f64, // Absolute
+}
+
+impl Check {
+    /// Checks whether the computed value is within the tolerance of the exact value, which a `NaN` never is.
+    fn passes(&self) -> bool {
+        (self.computed - self.exact).abs() <= self.tolerance
+    }
+}
+
+/// Runs a small battery of integrals, derivatives and roots with known exact values, in a few milliseconds.
+///
+/// The tolerances are loose enough for any correct build, so a failure means the numerical behaviour of the build is broken, not merely imprecise.
+///
+/// # Returns
+///
+/// `Ok(())` if all the checks pass, or a description of each failed check.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::self_test::self_test;
+///
+/// if let Err(failures) = self_test() {
+///     for failure in failures {
+///         eprintln!("{}", failure);
+///     }
+/// }
+/// ```
+pub fn self_test() -> Result<(), Vec<String>> {
+    let failures: Vec<String> = checks()
+        .iter()
+        .filter(|check| !check.passes())
+        .map(|check| {
+            format!(
+                "{}: expected {}, got {} (error {:e} above the tolerance {:e})",
+                check.name,
+                check.exact,
+                check.computed,
+                (check.computed - check.exact).abs(),
+                check.tolerance
+            )
+        })
+        .collect();
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures)
+    }
+}
+
+/// Computes the values of the battery of checks.
+fn checks() -> Vec<Check> {
+    let square = || Integral::new(Box::new(|x| x * x), 0.0, 3.0, 1e4 as u64);
+    let sine = || Integral::new(Box::new(|x: f64| x.sin()), 0.0, PI, 100);
+    let exponential =
+        |x_coordinate| Derivative::new(Box::new(|x: f64| x.exp()), x_coordinate, 1e-6);
+
+    vec![
+        Check {
+            name: "Riemann sum of x^2 over [0, 3]",
+            computed: square().riemann_integration(),
+            exact: 9.0,
+            tolerance: 1e-2,
+        },
+        Check {
+            name: "Simpson's 1/3 rule for sin(x) over [0, pi]",
+            computed: sine().simpson_integration_one_third(),
+            exact: 2.0,
+            tolerance: 1e-8,
+        },
+        Check {
+            name: "Midpoint rule for sin(x) over [0, pi]",
+            computed: sine().midpoint_integration(),
+            exact: 2.0,
+            tolerance: 1e-3,
+        },
+        Check {
+            name: "Trapezoidal rule for sin(x) over [0, pi]",
+            computed: sine().trapezoidal_composite(),
+            exact: 2.0,
+            tolerance: 1e-3,
+        },
+        Check {
+            name: "Monte Carlo integration of x^2 over [0, 3]",
+            computed: square().monte_carlo_integration(1e4 as u64, 42),
+            exact: 9.0,
+            tolerance: 0.5,
+        },
+        Check {
+            name: "Adaptive Simpson's method for exp(x) over [0, 1]",
+            computed: Integral::new(Box::new(|x: f64| x.exp()), 0.0, 1.0, 1)
+                .adaptive_simpson(1e-10, 50)
+                .unwrap_or(f64::NAN),
+            exact: E - 1.0,
+            tolerance: 1e-9,
+        },
+        Check {
+            name: "Forward difference of exp(x) at 1",
+            computed: exponential(1.0).forward_difference(),
+            exact: E,
+            tolerance: 1e-4,
+        },
+        Check {
+            name: "Central difference of exp(x) at 1",
+            computed: exponential(1.0).central_difference(),
+            exact: E,
+            tolerance: 1e-8,
+        },
+        Check {
+            name: "Backward difference of exp(x) at 1",
+            computed: exponential(1.0).backward_difference(),
+            exact: E,
+            tolerance: 1e-4,
+        },
+        Check {
+            name: "Bisection for the root of x^2 - 2 in [0, 2]",
+            computed: roots::bisection(&|x| x * x - 2.0, 0.0, 2.0, 1e-12).unwrap_or(f64::NAN),
+            exact: 2f64.sqrt(),
+            tolerance: 1e-11,
+        },
+    ]
+}
+
+// ---- Tests ---- //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_test() {
+        assert_eq!(self_test(), Ok(()));
+    }
+
+    #[test]
+    fn test_check_fails_on_nan() {
+        let check = Check {
+            name: "NaN",
+            computed: f64::NAN,
+            exact: 1.0,
+            tolerance: f64::INFINITY,
+        };
+        assert!(!check.passes());
+    }
+}
@@ -0,0 +1,185 @@
+//! Differentiates the expressions typed by the user symbolically, so that the exact derivative can be compared with the numerical ones.
+//!
+//! The expression is walked in the reverse Polish notation produced by `meval`, keeping on a stack each sub-expression together with its derivative, both as text:
+//! the derivative of the whole expression is then parsed back by `meval`, like the function typed by the user.
+//! The derivatives are simplified only where a term is `0` or a factor is `1`, so they are correct but verbose.
+
+use meval::tokenizer::{Operation, Token};
+
+/// Differentiates the expression with respect to the variable `var`.
+///
+/// The arithmetic operators except `%`, and the functions `sqrt`, `exp`, `ln`, `abs`, `sin`, `cos`, `tan`, `asin`, `acos`, `atan`, `sinh`, `cosh` and `tanh` are supported.
+/// Any other variable, e.g. `pi`, is taken as a constant.
+///
+/// # Arguments
+/// * `expr` - The expression to differentiate.
+/// * `var` - The name of the variable of differentiation.
+///
+/// # Returns
+/// The derivative as an expression which `meval` can parse, or `None` if the expression uses an unsupported operator or function.
+pub fn differentiate(expr: &meval::Expr, var: &str) -> Option<String> {
+    // Pairs of a sub-expression and its derivative
+    let mut stack: Vec<(String, String)> = vec![];
+
+    for token in expr.iter() {
+        let item = match token {
+            Token::Number(value) => (format!("{}", value), "0".to_string()),
+            Token::Var(name) if name == var => (name.clone(), "1".to_string()),
+            Token::Var(name) => (name.clone(), "0".to_string()),
+            Token::Unary(Operation::Plus) => stack.pop()?,
+            Token::Unary(Operation::Minus) => {
+                let (u, du) = stack.pop()?;
+                (format!("(-{})", u), neg(&du))
+            }
+            Token::Binary(operation) => {
+                let (v, dv) = stack.pop()?;
+                let (u, du) = stack.pop()?;
+                let derivative = match operation {
+                    Operation::Plus => add(&du, &dv),
+                    Operation::Minus => sub(&du, &dv),
+                    Operation::Times => add(&mul(&du, &v), &mul(&u, &dv)),
+                    Operation::Div => {
+                        div(&sub(&mul(&du, &v), &mul(&u, &dv)), &format!("({}^2)", v))
+                    }
+                    Operation::Pow if dv == "0" => {
+                        // Power rule, with a constant exponent
+                        mul(&mul(&v, &format!("({}^({} - 1))", u, v)), &du)
+                    }
+                    Operation::Pow => mul(
+                        &format!("({}^{})", u, v),
+                        &add(&mul(&dv, &format!("ln({})", u)), &div(&mul(&v, &du), &u)),
+                    ),
+                    Operation::Rem => return None,
+                };
+                (format!("({} {} {})", u, symbol(*operation), v), derivative)
+            }
+            Token::Func(name, Some(1)) => {
+                let (u, du) = stack.pop()?;
+                let outer = match name.as_str() {
+                    "sqrt" => format!("(1 / (2 * sqrt({})))", u),
+                    "exp" => format!("exp({})", u),
+                    "ln" => format!("(1 / {})", u),
+                    "abs" => format!("signum({})", u),
+                    "sin" => format!("cos({})", u),
+                    "cos" => format!("(-sin({}))", u),
+                    "tan" => format!("(1 / cos({})^2)", u),
+                    "asin" => format!("(1 / sqrt(1 - {}^2))", u),
+                    "acos" => format!("(-1 / sqrt(1 - {}^2))", u),
+                    "atan" => format!("(1 / (1 + {}^2))", u),
+                    "sinh" => format!("cosh({})", u),
+                    "cosh" => format!("sinh({})", u),
+                    "tanh" => format!("(1 / cosh({})^2)", u),
+                    _ => return None,
+                };
+                (format!("{}({})", name, u), mul(&outer, &du)) // Chain rule
+            }
+            _ => return None,
+        };
+        stack.push(item);
+    }
+
+    match (stack.pop(), stack.is_empty()) {
+        (Some((_, derivative)), true) => Some(derivative),
+        _ => None,
+    }
+}
+
+/// Returns the symbol of a binary operation.
+fn symbol(operation: Operation) -> &'static str {
+    match operation {
+        Operation::Plus => "+",
+        Operation::Minus => "-",
+        Operation::Times => "*",
+        Operation::Div => "/",
+        Operation::Rem => "%",
+        Operation::Pow => "^",
+    }
+}
+
+/// Adds two expressions, dropping a zero term.
+fn add(a: &str, b: &str) -> String {
+    match (a, b) {
+        ("0", _) => b.to_string(),
+        (_, "0") => a.to_string(),
+        _ => format!("({} + {})", a, b),
+    }
+}
+
+/// Subtracts two expressions, dropping a zero term.
+fn sub(a: &str, b: &str) -> String {
+    match (a, b) {
+        (_, "0") => a.to_string(),
+        ("0", _) => neg(b),
+        _ => format!("({} - {})", a, b),
+    }
+}
+
+/// Multiplies two expressions, dropping a unit factor, and collapsing to zero with a zero factor.
+fn mul(a: &str, b: &str) -> String {
+    match (a, b) {
+        ("0", _) | (_, "0") => "0".to_string(),
+        ("1", _) => b.to_string(),
+        (_, "1") => a.to_string(),
+        _ => format!("({} * {})", a, b),
+    }
+}
+
+/// Divides two expressions, collapsing to zero with a zero numerator.
+fn div(a: &str, b: &str) -> String {
+    match a {
+        "0" => "0".to_string(),
+        _ => format!("({} / {})", a, b),
+    }
+}
+
+/// Negates an expression, leaving zero as it is.
+fn neg(a: &str) -> String {
+    match a {
+        "0" => "0".to_string(),
+        _ => format!("(-{})", a),
+    }
+}
+
+// ---- Tests ---- //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Helper function differentiating `function` and evaluating the derivative at `x`.
+    fn derivative_at(function: &str, x: f64) -> Option<f64> {
+        let expr: meval::Expr = function.parse().unwrap();
+        let derivative: meval::Expr = differentiate(&expr, "x")?.parse().unwrap();
+        Some(derivative.bind("x").unwrap()(x))
+    }
+
+    #[test]
+    fn test_differentiate() {
+        let x: f64 = 0.7;
+        let derivative = derivative_at("x^2 + sin(x)", x).unwrap();
+        assert!((derivative - (2.0 * x + x.cos())).abs() < 1e-14);
+
+        // Quotient, chain rule and constants
+        let derivative = derivative_at("exp(-x) / (1 + x) - pi * ln(x)", x).unwrap();
+        let exact =
+            -(-x).exp() / (1.0 + x) - (-x).exp() / (1.0 + x).powi(2) - std::f64::consts::PI / x;
+        assert!((derivative - exact).abs() < 1e-14);
+
+        // A variable exponent
+        let derivative = derivative_at("x^x", x).unwrap();
+        assert!((derivative - x.powf(x) * (x.ln() + 1.0)).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_differentiate_simplified() {
+        let expr: meval::Expr = "3 * x + 2".parse().unwrap();
+        assert_eq!(differentiate(&expr, "x").as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn test_differentiate_unsupported() {
+        assert_eq!(derivative_at("x % 2", 1.0), None);
+        assert_eq!(derivative_at("floor(x)", 1.0), None);
+        assert_eq!(derivative_at("max(x, 1)", 1.0), None);
+    }
+}
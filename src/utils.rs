@@ -1 +1,5 @@
 pub mod colours;
+pub mod complex;
+pub mod format;
+pub mod numeric;
+pub mod random;
@@ -0,0 +1,119 @@
+//! A module for a minimal complex number type, used to integrate complex-valued functions.
+
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// A complex number in Cartesian form.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex {
+    pub re: f64, // Real part
+    pub im: f64, // Imaginary part
+}
+
+impl Complex {
+    /// The imaginary unit.
+    pub const I: Complex = Complex { re: 0.0, im: 1.0 };
+
+    pub fn new(re: f64, im: f64) -> Self {
+        Complex { re, im }
+    }
+
+    /// Returns `e^(i * theta)`, i.e. the point of the unit circle at angle `theta`.
+    pub fn from_angle(theta: f64) -> Self {
+        Complex::new(theta.cos(), theta.sin())
+    }
+
+    /// Returns the exponential of the number.
+    pub fn exp(self) -> Self {
+        Complex::from_angle(self.im) * self.re.exp()
+    }
+
+    /// Returns the modulus of the number.
+    pub fn abs(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+
+    fn add(self, other: Complex) -> Complex {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+
+    fn sub(self, other: Complex) -> Complex {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+
+    fn mul(self, other: Complex) -> Complex {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl Mul<f64> for Complex {
+    type Output = Complex;
+
+    fn mul(self, factor: f64) -> Complex {
+        Complex::new(self.re * factor, self.im * factor)
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+
+    fn div(self, other: Complex) -> Complex {
+        let denominator = other.re * other.re + other.im * other.im;
+        Complex::new(
+            (self.re * other.re + self.im * other.im) / denominator,
+            (self.im * other.re - self.re * other.im) / denominator,
+        )
+    }
+}
+
+impl fmt::Display for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.im.is_sign_negative() {
+            write!(f, "{} - {}i", self.re, -self.im)
+        } else {
+            write!(f, "{} + {}i", self.re, self.im)
+        }
+    }
+}
+
+// ---- Tests ---- //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_complex_arithmetic() {
+        let z = Complex::new(1.0, 2.0);
+        let w = Complex::new(3.0, -1.0);
+
+        assert_eq!(z + w, Complex::new(4.0, 1.0));
+        assert_eq!(z - w, Complex::new(-2.0, 3.0));
+        assert_eq!(z * w, Complex::new(5.0, 5.0));
+        assert_eq!((z * w) / w, z);
+        assert_eq!(Complex::I * Complex::I, Complex::new(-1.0, 0.0));
+        assert_eq!(w.to_string(), "3 - 1i");
+    }
+
+    #[test]
+    fn test_complex_exp() {
+        let z = (Complex::I * PI).exp(); // Euler's identity
+        assert!((z - Complex::new(-1.0, 0.0)).abs() < 1e-15);
+    }
+}
@@ -0,0 +1,185 @@
+//! A module for formatting numerical results for display.
+
+/// Decimal exponent of the smallest magnitude displayed in fixed-point notation by `NumberFormat::Auto`, i.e. `1e-4`.
+const MIN_FIXED_MAGNITUDE: i32 = -4;
+
+/// The notation used to display a number, with its number of significant digits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberFormat {
+    /// Fixed-point notation, e.g. `123.46`.
+    Fixed(usize),
+    /// Scientific notation, e.g. `1.2346e2`.
+    Scientific(usize),
+    /// Fixed-point notation for moderate magnitudes, scientific notation for very small or very large ones.
+    Auto(usize),
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat::Auto(10)
+    }
+}
+
+/// Formats a number with the given notation and number of significant digits.
+///
+/// Non-finite numbers are formatted as `NaN`, `inf` and `-inf` regardless of the notation.
+///
+/// # Arguments
+///
+/// * `value` - The number to format.
+/// * `style` - The notation and number of significant digits. At least one significant digit is always displayed.
+///
+/// # Returns
+///
+/// The formatted number.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::utils::format::{NumberFormat, format_result};
+///
+/// assert_eq!(format_result(2.0000000000000004, NumberFormat::Fixed(6)), "2.00000");
+/// assert_eq!(format_result(123456.0, NumberFormat::Scientific(3)), "1.23e5");
+/// ```
+pub fn format_result(value: f64, style: NumberFormat) -> String {
+    if !value.is_finite() {
+        return value.to_string();
+    }
+
+    match style {
+        NumberFormat::Fixed(digits) => {
+            let digits = digits.max(1) as i32;
+            let decimals = (digits - 1 - magnitude(value)).max(0) as usize;
+            format!("{:.*}", decimals, value)
+        }
+        NumberFormat::Scientific(digits) => format!("{:.*e}", digits.max(1) - 1, value),
+        NumberFormat::Auto(digits) => {
+            let magnitude = magnitude(value);
+            if value != 0.0
+                && (magnitude < MIN_FIXED_MAGNITUDE || magnitude >= digits.max(1) as i32)
+            {
+                format_result(value, NumberFormat::Scientific(digits))
+            } else {
+                format_result(value, NumberFormat::Fixed(digits))
+            }
+        }
+    }
+}
+
+/// Rounds a number to the given number of decimals, to suppress floating-point noise such as `2.0000000000000004` before displaying it.
+///
+/// Numbers smaller than `1e-4` in magnitude are returned unchanged, because rounding would remove their significant digits: they are displayed in scientific notation instead.
+/// Non-finite numbers, and numbers too large to have decimals, are returned unchanged as well.
+/// Only the displayed value should be rounded, keeping the full precision for further calculations.
+///
+/// # Arguments
+///
+/// * `value` - The number to round.
+/// * `decimals` - The number of decimals to keep.
+///
+/// # Returns
+///
+/// The rounded number.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::utils::format::round_for_display;
+///
+/// assert_eq!(round_for_display(2.0000000000000004, 12), 2.0);
+/// assert_eq!(round_for_display(0.1 + 0.2, 12), 0.3);
+/// ```
+pub fn round_for_display(value: f64, decimals: u32) -> f64 {
+    if !value.is_finite() || magnitude(value) < MIN_FIXED_MAGNITUDE {
+        return value;
+    }
+
+    // Round the decimal representation, which is exact, unlike scaling by a power of ten
+    format!("{:.*}", decimals as usize, value)
+        .parse()
+        .unwrap_or(value)
+}
+
+/// Returns the decimal exponent of the most significant digit of `value`, or `0` for zero.
+fn magnitude(value: f64) -> i32 {
+    if value == 0.0 {
+        0
+    } else {
+        value.abs().log10().floor() as i32
+    }
+}
+
+// ---- Tests ---- //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_result_fixed() {
+        assert_eq!(
+            format_result(2.0000000000000004, NumberFormat::Fixed(6)),
+            "2.00000"
+        );
+        assert_eq!(format_result(123.456, NumberFormat::Fixed(5)), "123.46");
+        assert_eq!(
+            format_result(-0.00123456, NumberFormat::Fixed(3)),
+            "-0.00123"
+        );
+        assert_eq!(format_result(123456.0, NumberFormat::Fixed(3)), "123456");
+        assert_eq!(format_result(0.0, NumberFormat::Fixed(3)), "0.00");
+    }
+
+    #[test]
+    fn test_format_result_scientific() {
+        assert_eq!(
+            format_result(123456.0, NumberFormat::Scientific(3)),
+            "1.23e5"
+        );
+        assert_eq!(
+            format_result(-0.00123456, NumberFormat::Scientific(4)),
+            "-1.235e-3"
+        );
+        assert_eq!(format_result(1.0, NumberFormat::Scientific(1)), "1e0");
+        assert_eq!(format_result(1.0, NumberFormat::Scientific(0)), "1e0");
+    }
+
+    #[test]
+    fn test_format_result_auto() {
+        assert_eq!(
+            format_result(std::f64::consts::PI, NumberFormat::Auto(4)),
+            "3.142"
+        );
+        assert_eq!(format_result(1.5e-7, NumberFormat::Auto(3)), "1.50e-7");
+        assert_eq!(
+            format_result(6.02214076e23, NumberFormat::Auto(4)),
+            "6.022e23"
+        );
+        assert_eq!(format_result(0.0, NumberFormat::Auto(4)), "0.000");
+    }
+
+    #[test]
+    fn test_format_result_non_finite() {
+        assert_eq!(format_result(f64::NAN, NumberFormat::Fixed(3)), "NaN");
+        assert_eq!(
+            format_result(f64::INFINITY, NumberFormat::Scientific(3)),
+            "inf"
+        );
+        assert_eq!(
+            format_result(f64::NEG_INFINITY, NumberFormat::Auto(3)),
+            "-inf"
+        );
+    }
+
+    #[test]
+    fn test_round_for_display() {
+        assert_eq!(round_for_display(2.0000000000000004, 12), 2.0);
+        assert_eq!(round_for_display(-1.9999999999999998, 12), -2.0);
+        assert_eq!(round_for_display(123.456789, 2), 123.46);
+        assert_eq!(round_for_display(0.00123456, 4), 0.0012);
+        assert_eq!(round_for_display(6.02214076e23, 2), 6.02214076e23);
+        assert_eq!(round_for_display(1.23456e-10, 4), 1.23456e-10); // Displayed in scientific notation
+        assert_eq!(round_for_display(0.0, 3), 0.0);
+        assert!(round_for_display(f64::NAN, 3).is_nan());
+    }
+}
@@ -0,0 +1,115 @@
+//! A module for the tolerances used to compare floating-point numbers, so that the notion of "numerically zero" is shared by the whole library.
+
+/// Default tolerance of `approx_eq`, well above the rounding errors of a few operations, and well below the accuracy of the numerical methods.
+pub const EPSILON: f64 = 1e-12;
+
+/// Tolerance of `approx_eq` within which two estimates of the same quantity, at different resolutions, agree: the truncation error of a method is well above the rounding errors.
+pub const AGREEMENT_TOLERANCE: f64 = 1e-6;
+
+/// Tolerance of `approx_eq` for the same quantity computed by two different formulas, e.g. a bound mapped by a change of variables and the value it is expected to map to.
+pub const CONSISTENCY_TOLERANCE: f64 = 1e-9;
+
+/// Default absolute tolerance of the adaptive methods, when the caller does not choose one.
+pub const ADAPTIVE_TOLERANCE: f64 = 1e-10;
+
+/// Tolerance of `rel_eq` within which two values of a function only differ by rounding errors, `sqrt(f64::EPSILON)`:
+/// about half of the digits survive the cancellations of a typical evaluation.
+pub const ROUNDING_TOLERANCE: f64 = 1.490_116_119_384_765_6e-8;
+
+/// Checks whether two numbers are equal within a tolerance.
+///
+/// The tolerance is absolute for numbers smaller than `1` in magnitude, and relative to the larger magnitude otherwise,
+/// so that numbers close to zero are compared to zero, and large numbers are compared to their significant digits.
+///
+/// # Arguments
+///
+/// * `a` - The first number.
+/// * `b` - The second number.
+/// * `eps` - The tolerance, e.g. `EPSILON`.
+///
+/// # Returns
+///
+/// `true` if the numbers are within the tolerance of each other. Infinities are only equal to themselves, and `NaN` to nothing.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::utils::numeric::{EPSILON, approx_eq};
+///
+/// assert!(approx_eq(0.1 + 0.2, 0.3, EPSILON));
+/// assert!(!approx_eq(1.0, 1.001, EPSILON));
+/// ```
+pub fn approx_eq(a: f64, b: f64, eps: f64) -> bool {
+    a == b || (a - b).abs() <= eps * a.abs().max(b.abs()).max(1.0) && (a - b).is_finite()
+}
+
+/// Checks whether two numbers are equal within a tolerance relative to the larger magnitude.
+///
+/// Unlike `approx_eq`, the comparison is relative even close to zero, so tiny numbers are compared to their significant digits too, and only `0` is equal to `0`.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::utils::numeric::{ROUNDING_TOLERANCE, rel_eq};
+///
+/// assert!(rel_eq(1e-20, 1e-20 * (1.0 + 1e-10), ROUNDING_TOLERANCE));
+/// assert!(!rel_eq(1e-20, 2e-20, ROUNDING_TOLERANCE));
+/// ```
+pub fn rel_eq(a: f64, b: f64, eps: f64) -> bool {
+    (a - b).abs() <= eps * a.abs().max(b.abs())
+}
+
+/// Checks whether the interval `[a, b]` has zero width, i.e. its bounds are equal up to the rounding error of the larger one.
+///
+/// The comparison is relative, as in `rel_eq`, so tiny intervals such as `[0, 1e-20]` do not have zero width.
+pub fn is_zero_width(a: f64, b: f64) -> bool {
+    rel_eq(a, b, f64::EPSILON)
+}
+
+// ---- Tests ---- //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_approx_eq_absolute() {
+        assert!(approx_eq(0.0, 1e-13, EPSILON));
+        assert!(approx_eq(-1e-13, 1e-13, EPSILON));
+        assert!(!approx_eq(0.0, 1e-11, EPSILON));
+        assert!(approx_eq(0.5, 0.5 + 1e-13, EPSILON));
+    }
+
+    #[test]
+    fn test_approx_eq_relative() {
+        assert!(approx_eq(1e6, 1e6 + 1e-7, EPSILON));
+        assert!(!approx_eq(1e6, 1e6 + 1e-5, EPSILON));
+        assert!(approx_eq(-3e20, -3e20 * (1.0 + 1e-13), EPSILON));
+        assert!(approx_eq(100.0, 101.0, 0.01));
+    }
+
+    #[test]
+    fn test_approx_eq_non_finite() {
+        assert!(approx_eq(f64::INFINITY, f64::INFINITY, EPSILON));
+        assert!(!approx_eq(f64::INFINITY, f64::NEG_INFINITY, EPSILON));
+        assert!(!approx_eq(f64::NAN, f64::NAN, EPSILON));
+    }
+
+    #[test]
+    fn test_rel_eq() {
+        assert_eq!(ROUNDING_TOLERANCE, f64::EPSILON.sqrt());
+        assert!(rel_eq(0.0, 0.0, ROUNDING_TOLERANCE));
+        assert!(!rel_eq(0.0, 1e-300, ROUNDING_TOLERANCE));
+        assert!(!rel_eq(1e-13, 2e-13, ROUNDING_TOLERANCE)); // Equal for approx_eq with EPSILON
+        assert!(rel_eq(1e6, 1e6 + 1e-3, ROUNDING_TOLERANCE));
+        assert!(!rel_eq(f64::NAN, f64::NAN, ROUNDING_TOLERANCE));
+    }
+
+    #[test]
+    fn test_is_zero_width() {
+        assert!(is_zero_width(2.0, 2.0));
+        assert!(is_zero_width(0.1 + 0.2, 0.3));
+        assert!(!is_zero_width(0.0, 1e-20));
+        assert!(!is_zero_width(1.0, 1.0 + 1e-12));
+    }
+}
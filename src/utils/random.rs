@@ -0,0 +1,76 @@
+//! A module for reproducible pseudo-random number generation, used by the Monte Carlo methods.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A small and fast pseudo-random number generator (SplitMix64).
+///
+/// It is not suitable for cryptography, but its output is reproducible from the seed, which is what matters for numerical experiments.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    /// Returns the next pseudo-random 64-bit integer.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns the next pseudo-random number, uniformly distributed in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 // The 53 most significant bits fill the mantissa
+    }
+}
+
+/// Returns `seed` if provided, otherwise a nonzero seed generated from the current time.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::utils::random::seed_or_time_based;
+///
+/// assert_eq!(seed_or_time_based(Some(42)), 42);
+/// assert_ne!(seed_or_time_based(None), 0);
+/// ```
+pub fn seed_or_time_based(seed: Option<u64>) -> u64 {
+    seed.unwrap_or_else(|| {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos() as u64)
+            .unwrap_or(0);
+        // Scramble the time, so that close instants give unrelated seeds
+        Rng::new(nanos).next_u64().max(1)
+    })
+}
+
+// ---- Tests ---- //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seed_or_time_based() {
+        assert_eq!(seed_or_time_based(Some(12345)), 12345);
+        assert_eq!(seed_or_time_based(Some(0)), 0);
+        assert_ne!(seed_or_time_based(None), 0);
+    }
+
+    #[test]
+    fn test_rng_reproducible_and_in_range() {
+        let mut first = Rng::new(7);
+        let mut second = Rng::new(7);
+        for _ in 0..1000 {
+            let value = first.next_f64();
+            assert_eq!(value, second.next_f64());
+            assert!((0.0..1.0).contains(&value));
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! This module renders series of results as text, to compare them at a glance in the terminal.
+
+/// The blocks of a sparkline, from the lowest to the highest.
+const SPARKLINE_BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Renders a series of values as a sparkline, one Unicode block per value, e.g. to show the convergence of a method or to compare the results of several methods.
+///
+/// The range of the finite values is split into eight levels of equal height: the minimum is drawn with the lowest block, the maximum with the highest one.
+/// A series whose values are all equal has no range to split, so it is drawn as a flat line of middle blocks.
+/// The values which are not finite, e.g. `NaN`, are drawn as spaces, so the sparkline keeps one character per value.
+///
+/// # Arguments
+///
+/// * `values` - The series of values.
+///
+/// # Returns
+///
+/// The sparkline, with as many characters as values.
+///
+/// # Example
+///
+/// ```
+/// use rust_math_lib::viz::sparkline;
+///
+/// assert_eq!(sparkline(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]), "▁▂▃▄▅▆▇█");
+/// ```
+pub fn sparkline(values: &[f64]) -> String {
+    let finite = values.iter().copied().filter(|value| value.is_finite());
+    let min = finite.clone().fold(f64::INFINITY, f64::min);
+    let max = finite.fold(f64::NEG_INFINITY, f64::max);
+    let top = SPARKLINE_BLOCKS.len() - 1;
+
+    values
+        .iter()
+        .map(|&value| {
+            if !value.is_finite() {
+                ' '
+            } else if max > min {
+                let level = ((value - min) / (max - min) * top as f64).round() as usize;
+                SPARKLINE_BLOCKS[level.min(top)]
+            } else {
+                SPARKLINE_BLOCKS[top / 2] // All the values are equal
+            }
+        })
+        .collect()
+}
+
+// ---- Tests ---- //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparkline() {
+        let values = [3.0, -1.0, 0.5, 10.0, 2.0];
+        let sparkline = sparkline(&values);
+        let blocks: Vec<char> = sparkline.chars().collect();
+        assert_eq!(blocks.len(), values.len());
+        assert_eq!(blocks[1], '▁'); // Minimum
+        assert_eq!(blocks[3], '█'); // Maximum
+        assert!(blocks.iter().all(|block| SPARKLINE_BLOCKS.contains(block)));
+    }
+
+    #[test]
+    fn test_sparkline_degenerate() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[42.0]), "▄");
+        assert_eq!(sparkline(&[2.0, 2.0, 2.0]), "▄▄▄");
+        assert_eq!(sparkline(&[0.0, f64::NAN, 1.0]), "▁ █");
+    }
+}
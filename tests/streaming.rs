@@ -0,0 +1,86 @@
+//! Checks that the integration methods are streaming: their memory does not grow with the number of intervals.
+//!
+//! The counting allocator replaces the global allocator of the whole test binary, so it lives in its own integration test.
+
+use rust_math_lib::integrals::{Integral, SummationStrategy};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Allocator counting the bytes allocated by each thread.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATED_BYTES: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATED_BYTES.try_with(|bytes| bytes.set(bytes.get() + layout.size()));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+type Method = fn(&mut Integral) -> f64;
+
+/// Integrates `2x` over `[0, 1]` with each summation strategy, checking the result and the bytes allocated.
+fn check_streaming(name: &str, method: Method, num_intervals: u64, tolerance: f64) {
+    for strategy in [
+        SummationStrategy::Naive,
+        SummationStrategy::Kahan,
+        SummationStrategy::Pairwise,
+    ] {
+        let mut integral = Integral::new(Box::new(|x: f64| 2.0 * x), 0.0, 1.0, num_intervals);
+        integral.with_summation(strategy);
+        let allocated_before = ALLOCATED_BYTES.with(|bytes| bytes.get());
+
+        let result = method(&mut integral);
+
+        let allocated = ALLOCATED_BYTES.with(|bytes| bytes.get()) - allocated_before;
+        assert!(
+            (result - 1.0).abs() < tolerance,
+            "{} with {:?}: {}",
+            name,
+            strategy,
+            result
+        );
+        // Only the stack of the pairwise summation, of about log2(n) partial sums
+        assert!(
+            allocated < 4096,
+            "{} with {:?} allocated {} bytes",
+            name,
+            strategy,
+            allocated
+        );
+    }
+}
+
+#[test]
+fn test_streaming_riemann_integration() {
+    // A buffer of the samples would take 800 MB
+    check_streaming(
+        "Riemann",
+        |integral| integral.riemann_integration(),
+        1e8 as u64,
+        1e-6,
+    );
+}
+
+#[test]
+fn test_streaming_uniform_grid_methods() {
+    // Up to three evaluations per slice: fewer intervals keep the test fast, and any buffer would still take megabytes
+    let methods: [(&str, Method); 3] = [
+        ("Simpson", Integral::simpson_integration_one_third),
+        ("trapezoidal", Integral::trapezoidal_composite),
+        ("midpoint", Integral::midpoint_integration),
+    ];
+    for (name, method) in methods {
+        check_streaming(name, method, 1e6 as u64, 1e-5);
+    }
+}